@@ -0,0 +1,217 @@
+//! Saves the files selected by a run to a portable archive and loads them back.
+//!
+//! An archive is a JSON document holding the relative path and raw content of
+//! every file a run processed, as it was read from disk. Loading one with `--from-archive` feeds the content
+//! through [`process_content`](crate::processing::process_content) and the
+//! normal formatting, so different output can be regenerated without access to
+//! the original files.
+
+use crate::core_types::{FileContent, FileInfo};
+use crate::errors::{io_error_with_path, Error, Result};
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The archive format version written by this build and the only one it reads.
+const ARCHIVE_VERSION: u32 = 1;
+
+/// The top-level archive document.
+#[derive(Serialize, Deserialize)]
+struct Archive {
+    version: u32,
+    files: Vec<ArchiveEntry>,
+}
+
+/// One file in an archive.
+///
+/// Content that is valid UTF-8 is stored as `text` to keep archives readable;
+/// anything else is stored as raw `bytes`.
+#[derive(Serialize, Deserialize)]
+struct ArchiveEntry {
+    path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bytes: Option<Vec<u8>>,
+    #[serde(default)]
+    is_process_last: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    process_last_order: Option<usize>,
+}
+
+impl From<FileContent> for ArchiveEntry {
+    fn from(file: FileContent) -> Self {
+        let (text, bytes) = match String::from_utf8(file.content) {
+            Ok(text) => (Some(text), None),
+            Err(e) => (None, Some(e.into_bytes())),
+        };
+        Self {
+            path: file.relative_path,
+            text,
+            bytes,
+            is_process_last: file.is_process_last,
+            process_last_order: file.process_last_order,
+        }
+    }
+}
+
+impl From<ArchiveEntry> for FileContent {
+    fn from(entry: ArchiveEntry) -> Self {
+        let content = match (entry.text, entry.bytes) {
+            (Some(text), _) => text.into_bytes(),
+            (None, Some(bytes)) => bytes,
+            (None, None) => Vec::new(),
+        };
+        Self {
+            relative_path: entry.path,
+            content,
+            is_process_last: entry.is_process_last,
+            process_last_order: entry.process_last_order,
+        }
+    }
+}
+
+/// Takes the raw content kept during processing out of each file.
+///
+/// Only files processed with `ProcessingConfig::keep_raw_content` carry their
+/// raw content; directories, symlinks, and files processed without it are
+/// skipped. The files keep their processed content.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::archive::take_file_contents;
+/// use dircat::core_types::FileInfo;
+///
+/// let mut files = vec![
+///     FileInfo { relative_path: "a.txt".into(), raw_content: Some(b"A".to_vec()), ..Default::default() },
+///     FileInfo { relative_path: "b.txt".into(), ..Default::default() },
+/// ];
+///
+/// let contents = take_file_contents(&mut files);
+/// assert_eq!(contents.len(), 1);
+/// assert_eq!(contents[0].content, b"A");
+/// assert!(files[0].raw_content.is_none());
+/// ```
+pub fn take_file_contents(files: &mut [FileInfo]) -> Vec<FileContent> {
+    files
+        .iter_mut()
+        .filter_map(|fi| {
+            Some(FileContent {
+                content: fi.raw_content.take()?,
+                relative_path: fi.relative_path.clone(),
+                is_process_last: fi.is_process_last,
+                process_last_order: fi.process_last_order,
+            })
+        })
+        .collect()
+}
+
+/// Writes `files` to an archive at `path`, replacing any existing file.
+///
+/// # Errors
+/// Returns an error if the archive cannot be written.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::archive::{load_archive, save_archive};
+/// use dircat::core_types::FileContent;
+///
+/// # fn main() -> dircat::errors::Result<()> {
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("snapshot.json");
+/// let files = vec![FileContent {
+///     relative_path: "src/main.rs".into(),
+///     content: b"fn main() {}".to_vec(),
+///     ..Default::default()
+/// }];
+///
+/// save_archive(&files, &path)?;
+/// let loaded = load_archive(&path)?;
+/// assert_eq!(loaded[0].relative_path.to_str(), Some("src/main.rs"));
+/// assert_eq!(loaded[0].content, b"fn main() {}");
+/// # Ok(())
+/// # }
+/// ```
+pub fn save_archive(files: &[FileContent], path: &Path) -> Result<()> {
+    let archive = Archive {
+        version: ARCHIVE_VERSION,
+        files: files.iter().cloned().map(ArchiveEntry::from).collect(),
+    };
+    let json = serde_json::to_string_pretty(&archive)
+        .map_err(|e| Error::Generic(anyhow!("Failed to serialize archive: {}", e)))?;
+    fs::write(path, json).map_err(|e| io_error_with_path(e, path))
+}
+
+/// Loads the files stored in the archive at `path`.
+///
+/// # Errors
+/// Returns an error if the archive cannot be read, is not valid JSON, or was
+/// written with an unsupported format version.
+pub fn load_archive(path: &Path) -> Result<Vec<FileContent>> {
+    let json = fs::read_to_string(path).map_err(|e| io_error_with_path(e, path))?;
+    let archive: Archive = serde_json::from_str(&json).map_err(|e| {
+        Error::Generic(anyhow!(
+            "Failed to parse archive '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    if archive.version != ARCHIVE_VERSION {
+        return Err(Error::Generic(anyhow!(
+            "Unsupported archive version {} in '{}' (expected {})",
+            archive.version,
+            path.display(),
+            ARCHIVE_VERSION
+        )));
+    }
+    Ok(archive.files.into_iter().map(FileContent::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_archive_round_trip_text_and_binary() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("archive.json");
+        let files = vec![
+            FileContent {
+                relative_path: "a.txt".into(),
+                content: b"hello".to_vec(),
+                ..Default::default()
+            },
+            FileContent {
+                relative_path: "README.md".into(),
+                content: vec![0xff, 0x00, 0x10],
+                is_process_last: true,
+                process_last_order: Some(0),
+            },
+        ];
+
+        save_archive(&files, &path)?;
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(json.contains("\"text\": \"hello\""));
+
+        let loaded = load_archive(&path)?;
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].content, b"hello");
+        assert_eq!(loaded[1].content, vec![0xff, 0x00, 0x10]);
+        assert!(loaded[1].is_process_last);
+        assert_eq!(loaded[1].process_last_order, Some(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_archive_rejects_unknown_version() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("archive.json");
+        fs::write(&path, r#"{"version": 99, "files": []}"#).unwrap();
+        let err = load_archive(&path).unwrap_err();
+        assert!(err.to_string().contains("Unsupported archive version 99"));
+    }
+}
@@ -0,0 +1,67 @@
+// src/capabilities.rs
+
+//! Describes what this build of dircat supports, for tools that integrate with it.
+
+use crate::config::{escape_json, OutputFormat};
+use crate::core_types::known_languages;
+use crate::processing::filters::{BuiltinFilter, BUILTIN_FILTERS};
+use clap::ValueEnum;
+
+/// The optional Cargo features, paired with whether this build enables them.
+const FEATURES: &[(&str, bool)] = &[
+    ("git", cfg!(feature = "git")),
+    ("clipboard", cfg!(feature = "clipboard")),
+    ("progress", cfg!(feature = "progress")),
+    ("archive", cfg!(feature = "archive")),
+];
+
+/// Returns a JSON object listing the built-in content filters with the flags
+/// that enable them, the output formats, the languages accepted by `--lang`,
+/// and which optional features this build was compiled with.
+///
+/// The lists come from [`BUILTIN_FILTERS`], [`OutputFormat`], and
+/// [`known_languages`], so they grow as filters, formats, and languages are
+/// added. This is what `--capabilities` prints.
+///
+/// # Examples
+///
+/// ```
+/// let json = dircat::capabilities::capabilities_json();
+/// assert!(json.starts_with(
+///     "{\"filters\": [{\"name\": \"RemoveCommentsFilter\", \"flags\": \"--remove-comments\"}, "
+/// ));
+/// assert!(json.contains("\"output_formats\": [\"markdown\", \"json\", \"xml\"]"));
+/// ```
+pub fn capabilities_json() -> String {
+    let formats = OutputFormat::value_variants()
+        .iter()
+        .filter_map(ValueEnum::to_possible_value)
+        .map(|value| value.get_name().to_string());
+    let filters: Vec<String> = BUILTIN_FILTERS.iter().map(filter_object).collect();
+    let features: Vec<String> = FEATURES
+        .iter()
+        .map(|(name, enabled)| format!("\"{}\": {}", name, enabled))
+        .collect();
+    format!(
+        "{{\"filters\": [{}], \"output_formats\": {}, \"languages\": {}, \"features\": {{{}}}}}",
+        filters.join(", "),
+        string_array(formats),
+        string_array(known_languages()),
+        features.join(", ")
+    )
+}
+
+fn filter_object(filter: &BuiltinFilter) -> String {
+    format!(
+        "{{\"name\": \"{}\", \"flags\": \"{}\"}}",
+        escape_json(filter.name),
+        escape_json(filter.flags)
+    )
+}
+
+fn string_array<S: AsRef<str>>(items: impl Iterator<Item = S>) -> String {
+    let items: Vec<String> = items
+        .map(|item| format!("\"{}\"", escape_json(item.as_ref())))
+        .collect();
+    format!("[{}]", items.join(", "))
+}
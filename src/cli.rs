@@ -1,5 +1,12 @@
 // src/cli.rs
 
+#[cfg(feature = "git")]
+use crate::config::RelativeBase;
+use crate::config::{
+    BinaryEncoding, ContentStyle, DescribeFormat, IfExists, LastSort, Newline, OutputFormat,
+    Preset, TicksPolicy,
+};
+use crate::errors::ErrorFormat;
 use clap::Parser;
 
 /// A high-performance Rust utility that concatenates and displays directory contents.
@@ -48,33 +55,67 @@ pub struct Cli {
     #[arg(long, action = clap::ArgAction::SetTrue)]
     pub show_download_path: bool,
 
+    #[cfg(feature = "git")]
+    /// What file paths in the output are relative to. 'git-root' uses the root of the git
+    /// working tree containing the input, so paths are the same whichever subdirectory is given.
+    #[arg(long, value_enum, value_name = "BASE")]
+    pub relative_base: Option<RelativeBase>,
+
     // --- Filtering Options ---
     /// Maximum file size to include (e.g., "1M", "512k"). Files larger than this are skipped.
     #[arg(short = 'm', long, value_name = "BYTES")]
     pub max_size: Option<String>, // Will be parsed into u64 later
 
+    /// Take at most N files from any single directory (the first N by path); the rest are skipped.
+    #[arg(long, value_name = "N")]
+    pub max_files_per_dir: Option<usize>,
+
     /// Do not recurse into subdirectories.
     #[arg(short = 'n', long, action = clap::ArgAction::SetTrue)]
     pub no_recursive: bool,
 
+    /// Descend at most N directory levels below the input (1 = direct children only).
+    /// Also limits how deep the GitHub API listing of a folder URL goes.
+    #[arg(long, value_name = "N")]
+    pub max_depth: Option<usize>,
+
     /// Include only files with these extensions (case-insensitive, repeatable).
     #[arg(short = 'e', long = "ext", value_name = "EXT", num_args = 1..)]
     pub extensions: Option<Vec<String>>,
 
+    /// Include only files of these languages (e.g., 'rust', 'python', repeatable). Each name
+    /// expands to the language's known extensions and is combined with --ext.
+    #[arg(long = "lang", value_name = "LANGUAGE", num_args = 1..)]
+    pub languages: Option<Vec<String>>,
+
     /// Exclude files with these extensions (case-insensitive, repeatable).
     #[arg(short = 'x', long = "exclude-ext", value_name = "EXT", num_args = 1..)]
     pub exclude_extensions: Option<Vec<String>>,
 
+    /// Exclude files without an extension (e.g., 'Makefile', compiled binaries).
+    #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "only_extensionless")]
+    pub no_extensionless: bool,
+
+    /// Include only files without an extension (e.g., to find scripts and configs).
+    #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with_all = &["extensions", "languages"])]
+    pub only_extensionless: bool,
+
     /// Exclude files whose relative path matches any of these regexes (case-insensitive, repeatable).
     #[arg(short = 'X', long = "exclude-regex", value_name = "REGEX", num_args = 1..)]
     pub exclude_path_regex: Option<Vec<String>>,
 
     /// Ignore files/directories matching these glob patterns (relative to input path, repeatable).
     /// This filter is applied after all other ignore logic (including .gitignore and --last overrides)
-    /// and will exclude a file even if it is matched by --last or --only.
+    /// and will exclude a file even if it is matched by --last or --only. A '!GLOB' pattern
+    /// re-includes matching files dropped by .gitignore or an earlier --ignore pattern.
     #[arg(short = 'i', long = "ignore", value_name = "GLOB", num_args = 1..)]
     pub ignore_patterns: Option<Vec<String>>,
 
+    /// Read additional --ignore glob patterns from a file, one per line (repeatable).
+    /// Blank lines and lines starting with '#' are skipped.
+    #[arg(long, value_name = "FILE", num_args = 1..)]
+    pub exclude_from: Option<Vec<String>>,
+
     /// Include only files whose relative path matches any of these regexes (case-insensitive, repeatable).
     #[arg(short = 'r', long = "regex", value_name = "REGEX", num_args = 1..)]
     pub path_regex: Option<Vec<String>>,
@@ -83,27 +124,168 @@ pub struct Cli {
     #[arg(short = 'd', long = "filename-regex", value_name = "REGEX", num_args = 1..)]
     pub filename_regex: Option<Vec<String>>,
 
+    /// Reject any --regex, --filename-regex, --exclude-regex or --content-match pattern whose
+    /// compiled form exceeds this size (e.g., "1M"), instead of letting it consume memory.
+    #[arg(long, value_name = "BYTES")]
+    pub regex_size_limit: Option<String>,
+
     /// Do not respect .gitignore, .ignore, or other VCS ignore files.
     #[arg(short = 't', long, action = clap::ArgAction::SetTrue)]
     pub no_gitignore: bool,
 
+    /// Do not skip the built-in excluded directories (node_modules, target, __pycache__, .venv).
+    /// These are skipped even with --no-gitignore unless this flag is given.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub no_default_excludes: bool,
+
+    /// Scan the .git directory, which is skipped by default.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub include_git_dir: bool,
+
+    /// Treat the input file as a list of paths to include, one per line, instead of as content.
+    /// Relative paths are resolved against the list file's directory; blank lines and lines
+    /// starting with '#' are skipped. A file is never treated as a list without this flag.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub treat_as_list: bool,
+
+    /// Also honor ignore files with this name in each directory (e.g., ".aiignore", ".cursorignore", repeatable).
+    /// '.dircatignore' is always honored. Uses gitignore syntax; disabled by --no-gitignore.
+    #[arg(long = "ignore-file", value_name = "NAME", num_args = 1..)]
+    pub ignore_files: Option<Vec<String>>,
+
+    /// List symbolic links in the summary as 'link -> target (symlink)' instead of skipping them.
+    /// Links are never followed, and their targets' content is not included.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub show_symlinks: bool,
+
+    /// Follow symbolic links whose target resolves inside the input directory.
+    /// Links pointing outside it are skipped.
+    #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "show_symlinks")]
+    pub follow_internal_symlinks: bool,
+
+    /// Print the directories that contain no included files after filtering to stderr.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub report_empty_dirs: bool,
+
+    /// Do not read ignore files from directories above the input path.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub no_ignore_parent: bool,
+
+    /// Apply ignore files to an input path that is itself a file. By default a file
+    /// named explicitly is processed even if a .gitignore rule covers it.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub ignore_explicit_input: bool,
+
+    /// Always process an input path that is itself a file, regardless of ignore
+    /// files (the default). Overrides an earlier --ignore-explicit-input.
+    #[arg(long, action = clap::ArgAction::SetTrue, overrides_with = "ignore_explicit_input")]
+    pub no_ignore_explicit_input: bool,
+
     /// Include files detected as binary/non-text (default is to skip them).
     #[arg(short = 'B', long, action = clap::ArgAction::SetTrue)]
     pub include_binary: bool,
 
+    /// How to write included binary files: 'lossy' UTF-8 (default) or 'base64' in a fence
+    /// labeled 'base64', which --unpack restores byte-for-byte.
+    #[arg(long, value_enum, value_name = "ENCODING")]
+    pub binary_encoding: Option<BinaryEncoding>,
+
+    /// Keep only files detected as binary/non-text and list their paths and sizes instead of content.
+    #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "delimiter")]
+    pub only_binary: bool,
+
     /// Skip common lockfiles (e.g., Cargo.lock, package-lock.json).
     #[arg(short = 'K', long, action = clap::ArgAction::SetTrue)]
     pub no_lockfiles: bool,
 
+    /// Skip files likely to contain secrets (.env, *.pem, *.key, id_rsa, credentials, ...).
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub skip_secrets: bool,
+
+    /// Skip files whose content looks minified (very long lines relative to size).
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub skip_minified: bool,
+
+    /// Skip files with a generated-code banner (e.g., '@generated', 'Code generated by',
+    /// 'DO NOT EDIT') in their first lines. Each skipped file is logged with its marker
+    /// at the info level (RUST_LOG=info).
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub skip_generated: bool,
+
+    /// Skip text files with more than N lines (e.g., generated lockfiles or schemas).
+    #[arg(long, value_name = "N")]
+    pub max_lines: Option<usize>,
+
+    /// Include only files whose content matches any of these regexes (case-insensitive, repeatable).
+    #[arg(long, value_name = "REGEX", num_args = 1..)]
+    pub content_match: Option<Vec<String>>,
+
     // --- Content Processing Options ---
+    /// Remove a leading license/copyright comment block (e.g., 'Copyright', 'SPDX-License-Identifier').
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub strip_license: bool,
+
+    /// Leave a '// license header removed' line where a license header was stripped (requires --strip-license).
+    #[arg(long, action = clap::ArgAction::SetTrue, requires = "strip_license")]
+    pub license_marker: bool,
+
     /// Remove C/C++ style comments (// and /* ... */).
     #[arg(short = 'c', long, action = clap::ArgAction::SetTrue)]
     pub remove_comments: bool,
 
+    /// Collapse the double spaces left where block comments were removed (requires -c, which
+    /// `--preset llm` implies).
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub collapse_comment_spaces: bool,
+
+    /// Replace removed block comments with this text (e.g., '/* ... */') instead of dropping them
+    /// (requires -c, which `--preset llm` implies).
+    #[arg(long, value_name = "TEXT", conflicts_with = "collapse_comment_spaces")]
+    pub comment_placeholder: Option<String>,
+
     /// Remove empty lines (containing only whitespace).
     #[arg(short = 'l', long, action = clap::ArgAction::SetTrue)]
     pub remove_empty_lines: bool,
 
+    /// Sort and deduplicate runs of consecutive single-line imports (Rust `use`, Python
+    /// `import`/`from`, JavaScript `import`). Heuristic: recognizes imports by syntax only.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub organize_imports: bool,
+
+    /// Reduce Rust, Python, and JavaScript/TypeScript files to an outline of their declarations,
+    /// replacing function bodies with '{ ... }'. Other files keep their full content.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub outline: bool,
+
+    /// Replace hex and decimal literals of at least LEN characters (hashes, UUIDs, long IDs)
+    /// with a placeholder like '<hash:40>'.
+    #[arg(long, value_name = "LEN")]
+    pub mask_literals: Option<usize>,
+
+    /// Emit only N lines of context around each content match, with '...' between windows.
+    /// Files whose matches are all removed by content filters (e.g. comments) are skipped.
+    #[arg(long, value_name = "N", requires = "content_match")]
+    pub context_lines: Option<usize>,
+
+    /// Truncate each file's processed content to at most N bytes, noting where it was cut.
+    #[arg(long = "max-file-bytes", value_name = "N")]
+    pub max_file_content_bytes: Option<usize>,
+
+    /// Read at most N files at the same time, independent of the number of processing threads.
+    /// Lower values can speed up runs on slow or networked filesystems.
+    #[arg(long, value_name = "N")]
+    pub io_concurrency: Option<usize>,
+
+    /// Classify files as binary by a list of known binary extensions instead of reading each
+    /// file's head. Faster on very large trees, but unlisted binary formats are read as text.
+    #[arg(long, alias = "no-binary-read", action = clap::ArgAction::SetTrue)]
+    pub trust_extensions: bool,
+
+    /// Pipe each text file's content through this shell command and use its stdout instead.
+    /// The file's path is available in the DIRCAT_FILE environment variable.
+    #[arg(long, value_name = "COMMAND")]
+    pub transform_cmd: Option<String>,
+
     // --- Output Formatting Options ---
     /// Only include the filename (basename) in the '## File:' header.
     #[arg(short = 'f', long, action = clap::ArgAction::SetTrue)]
@@ -113,32 +295,164 @@ pub struct Cli {
     #[arg(short = 'L', long, action = clap::ArgAction::SetTrue)]
     pub line_numbers: bool,
 
+    /// Hard-wrap content lines longer than N characters, marking continuation lines with '↪'.
+    #[arg(long, value_name = "N")]
+    pub wrap: Option<usize>,
+
     /// Wrap filenames in the '## File:' header and summary with backticks (`).
     #[arg(short = 'b', long, action = clap::ArgAction::SetTrue)]
     pub backticks: bool,
 
+    /// Wrap each file block in a collapsible <details> element (useful when rendered on GitHub).
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub collapsible: bool,
+
+    /// Write a '<!-- dircat:file path="..." size="..." -->' comment before each file block,
+    /// for tools that split the output without relying on the header format.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub anchor_comments: bool,
+
+    /// Prepend a UTF-8 byte order mark (BOM) to the output file. Has no effect on stdout or clipboard output.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub bom: bool,
+
+    /// Rewrite every line ending in the output, including inside file content, to one style:
+    /// 'lf' (the default), 'crlf', or 'native'.
+    #[arg(long, value_enum, value_name = "STYLE", num_args = 0..=1, require_equals = true, default_missing_value = "lf")]
+    pub canonicalize_output_newlines: Option<Newline>,
+
+    /// Guarantee valid Markdown: widen code fences past any backtick run in the content and
+    /// escape lines that look like fences, so file content can never close a block early.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub markdown_safe: bool,
+
+    /// How to render each file's content: a fenced code block (default), a Markdown
+    /// blockquote, an indented code block, or raw text. Useful for embedding output in prose docs.
+    #[arg(long, value_enum, value_name = "STYLE")]
+    pub content_style: Option<ContentStyle>,
+
+    /// How to size code fences: 'fixed' uses --ticks for every file (default), 'auto' widens
+    /// each fence past the backticks in its content, and 'per-lang' widens only Markdown-like files.
+    #[arg(long, value_enum, value_name = "POLICY")]
+    pub ticks_policy: Option<TicksPolicy>,
+
+    /// Write each file's raw content directly under its '## File:' header, without code
+    /// fences (same as '--content-style raw').
+    #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "content_style")]
+    pub no_fence: bool,
+
+    /// Start the output with a table of contents linking to each file's header.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub toc: bool,
+
+    /// Number each file header with its position in the output, e.g. '## File 7: src/main.rs'.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub index_headers: bool,
+
+    /// Write this string between file blocks instead of a blank line. '\n' and '\t'
+    /// escapes are expanded, e.g. '---\n' for a horizontal rule.
+    #[arg(long, value_name = "STR", allow_hyphen_values = true)]
+    pub block_separator: Option<String>,
+
+    /// Write file blocks back to back, without the blank line between them.
+    #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "block_separator")]
+    pub no_block_separator: bool,
+
     /// Number of backticks to use for the Markdown code fences.
     #[arg(short = 'T', long, value_name = "COUNT", default_value_t = 3)]
     pub ticks: u8,
 
+    /// Separate files with this delimiter line instead of '## File:' headers and code fences.
+    /// '{path}' is replaced with each file's path (e.g., "===== FILE: {path} =====").
+    #[arg(long, value_name = "PATTERN")]
+    pub delimiter: Option<String>,
+
+    /// Write each file's content between '>>>>> DIRCAT FILE' and '<<<<< END' marker lines with
+    /// its path and size, so the output can be split back into files with --unpack.
+    #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "delimiter")]
+    pub roundtrip: bool,
+
+    /// Write a compact index instead of content: one line per file with its path, size,
+    /// and language, sorted. Files are not read (implies --dry-run).
+    #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with_all = &["delimiter", "roundtrip", "only_binary"])]
+    pub index: bool,
+
+    /// Write the output as 'markdown' (default), or as a 'json' or 'xml' document with each
+    /// file's path, size, binary flag, content, and counts.
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = &["delimiter", "roundtrip", "index", "only_binary"])]
+    pub format: Option<OutputFormat>,
+
+    /// Recreate the files in FILE, dircat Markdown or --roundtrip output, under the input
+    /// directory (default: the current directory), then exit.
+    #[arg(long, value_name = "FILE")]
+    pub unpack: Option<String>,
+
+    /// Regenerate the output in memory and compare it byte-for-byte with FILE instead of
+    /// writing it. Exits with status 1 and reports the first differing line if FILE is out
+    /// of date (e.g., to check a committed bundle in CI).
+    #[arg(long, value_name = "FILE", conflicts_with_all = &["output_file", "unpack"])]
+    pub verify: Option<String>,
+
+    /// Flush output after each file block so streaming consumers (e.g., pagers) see results early.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub flush_per_file: bool,
+
+    /// Override the code fence language by extension or filename (e.g., "h=cpp", "Jenkinsfile=groovy", repeatable).
+    #[arg(long, alias = "fence-lang", value_name = "KEY=LANG", num_args = 1..)]
+    pub lang_map: Option<Vec<String>>,
+
     // --- Output Destination & Summary ---
     /// Write output to the specified file instead of stdout.
     #[arg(short = 'o', long, value_name = "FILE")]
     pub output_file: Option<String>, // Using String, convert to PathBuf later
 
+    /// Write output to a file named from a template, e.g. '{input_name}-{date}.{ext}'.
+    /// Placeholders: {input_name}, {date}, {index}, {ext}.
+    #[arg(long, value_name = "TEMPLATE", conflicts_with = "output_file")]
+    pub output_template: Option<String>,
+
+    /// What to do if the output file already exists: overwrite it (default), append to it,
+    /// fail with an error, or rename it to '<name>.bak' first.
+    #[arg(long, value_enum, value_name = "ACTION")]
+    pub if_exists: Option<IfExists>,
+
     #[cfg(feature = "clipboard")]
     /// Copy output to the system clipboard.
     #[arg(short = 'p', long, action = clap::ArgAction::SetTrue)]
     pub paste: bool,
 
-    /// Print a summary list of processed files at the end of the output.
+    /// Print a summary list of processed files at the end of the output. Files
+    /// whose content looks minified are marked `(minified)`.
     #[arg(short = 's', long, action = clap::ArgAction::SetTrue)]
     pub summary: bool,
 
+    /// Output only the list of processed file paths: no content, headers, or counts.
+    /// Unlike --dry-run, this reflects files that passed processing (e.g., binary filtering).
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub summary_only_paths: bool,
+
     /// Include line, char (byte), and word counts in the summary (implies -s).
     #[arg(short = 'C', long, action = clap::ArgAction::SetTrue)]
     pub counts: bool,
 
+    /// Show each file's line, word, and char (byte) counts in its header, as '(L:10 W:50 C:300)'.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub counts_in_header: bool,
+
+    /// List the N largest files by size at the end of the summary (implies -s).
+    #[arg(long, value_name = "N")]
+    pub top: Option<usize>,
+
+    /// End the summary with groups of files whose content is identical, largest
+    /// duplication first (implies -s). The file blocks are not changed.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub content_hash_report: bool,
+
+    /// Open the output with a front page listing the dircat version, the input,
+    /// and the effective configuration.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub front_page: bool,
+
     // --- Processing Order ---
     /// Process files matching these glob patterns last, in the order specified.
     /// This can override .gitignore rules for the matched files, but will not override
@@ -150,6 +464,10 @@ pub struct Cli {
     #[arg(short = 'Z', long, action = clap::ArgAction::SetTrue, requires = "process_last")]
     pub only_last: bool,
 
+    /// How to order files matched by -z/--last: grouped by pattern order (default), or purely by path.
+    #[arg(long, value_enum, value_name = "ORDER", requires = "process_last")]
+    pub last_sort: Option<LastSort>,
+
     /// Shorthand for '--last <GLOB>... --only-last'. Process only files matching these glob patterns.
     /// This can override .gitignore rules for the matched files, but will not override
     /// rules from --ignore (-i).
@@ -157,7 +475,86 @@ pub struct Cli {
     pub only: Option<Vec<String>>,
 
     // --- Execution Control ---
+    /// Print the available content filters, output formats, languages, and compiled-in
+    /// features as JSON, then exit.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub capabilities: bool,
+
+    /// Print the effective configuration and resolved input path, then exit without processing.
+    /// Use '--print-config=json' for machine-readable output.
+    #[arg(long, value_enum, value_name = "FORMAT", num_args = 0..=1, require_equals = true, default_missing_value = "text")]
+    pub print_config: Option<DescribeFormat>,
+
+    /// How to report a failure on stderr. 'json' prints one object with 'error_type',
+    /// 'message', and context such as 'path' or 'url', for wrapping tools.
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = ErrorFormat::Text)]
+    pub error_format: ErrorFormat,
+
     /// Perform a dry run. Print files that would be processed but not their content.
     #[arg(short = 'D', long, action = clap::ArgAction::SetTrue)]
     pub dry_run: bool,
+
+    /// With --dry-run, read each listed file to show its line, character, and word counts.
+    /// The content is counted and discarded, not processed or printed.
+    #[arg(long = "dry-run-count", requires = "dry_run", action = clap::ArgAction::SetTrue)]
+    pub dry_run_counts: bool,
+
+    /// Exit with a non-zero status if no files match, instead of printing a notice and succeeding.
+    #[arg(long, conflicts_with = "allow_empty", action = clap::ArgAction::SetTrue)]
+    pub fail_if_empty: bool,
+
+    /// Treat a run where no files match as a silent success, without printing a notice.
+    /// The output destination is still written, so an --output file ends up empty.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub allow_empty: bool,
+
+    /// Produce reproducible output: omit time-derived fields and use stable ordering everywhere,
+    /// so identical input yields byte-identical output.
+    #[arg(long, alias = "deterministic-time", action = clap::ArgAction::SetTrue)]
+    pub deterministic: bool,
+
+    /// Produce diff-friendly output for files that are committed and regenerated: implies
+    /// --deterministic and normalizes CRLF/CR line endings to LF. Conflicts with --index-headers.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub stable: bool,
+
+    /// Keep files in the order they were discovered instead of sorting them by path, e.g. the
+    /// order of the entries in a --treat-as-list file.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub preserve_order: bool,
+
+    /// Output only the I-th of N equal slices of the sorted file list (e.g., '2/5'),
+    /// to split a large input across several runs.
+    #[arg(long, value_name = "I/N")]
+    pub shard: Option<String>,
+
+    /// Keep files, in output order, only while their estimated tokens fit within N. The
+    /// first file that would exceed the budget and all files after it are dropped and
+    /// listed in the summary. A first file over the budget on its own is kept, with a warning.
+    #[arg(long, value_name = "N")]
+    pub budget_tokens: Option<usize>,
+
+    /// Print a short report to stderr after the run: files included, total bytes and lines,
+    /// an estimated token count, elapsed time, and the throughput of reading the files.
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub stats: bool,
+
+    #[cfg(feature = "archive")]
+    /// Save the raw content of every processed file to an archive, so the run can be replayed
+    /// later. Files filtered out during processing are not saved; nothing is saved on a dry run.
+    #[arg(long, value_name = "PATH")]
+    pub save_archive: Option<String>,
+
+    #[cfg(feature = "archive")]
+    /// Process the files stored in an archive written by --save-archive instead of reading the input path.
+    #[arg(long, value_name = "PATH")]
+    pub from_archive: Option<String>,
+
+    // --- Presets ---
+    /// Apply a named bundle of options. 'llm' removes comments and empty lines, skips
+    /// lockfiles, adds a summary, and reports the estimated tokens (--stats). 'review'
+    /// adds line numbers, counts, and a table of contents.
+    /// Other flags are applied on top of the preset.
+    #[arg(long, value_enum, value_name = "NAME")]
+    pub preset: Option<Preset>,
 }
@@ -1,13 +1,25 @@
 //! Builds the `Config` struct from command-line arguments or other sources.
+#[cfg(feature = "git")]
+use super::RelativeBase;
 use super::{
-    parsing::{compile_regex_vec, normalize_extensions, parse_max_size},
-    Config, DiscoveryConfig, OutputConfig, ProcessingConfig,
+    parsing::{
+        compile_regex_vec, expand_brace_patterns, expand_languages, normalize_extensions,
+        parse_language_map, parse_max_size, parse_shard, unescape_separator,
+    },
+    BinaryEncoding, Config, ContentStyle, DiscoverCallback, DiscoveryConfig, Extensionless,
+    IfExists, LastSort, Newline, OutputConfig, OutputFormat, PostProcessCallback, Preset,
+    ProcessingConfig, TicksPolicy,
 };
 use crate::cli::Cli;
+use crate::core_types::FileInfo;
 use crate::errors::{Error, Result};
+use crate::output::front_page::render_front_page;
+use crate::output::writer::render_output_template;
 use crate::processing::filters::ContentFilter;
+use std::path::PathBuf;
+use std::sync::Arc;
 
-use super::builder_logic;
+use super::{builder_logic, env};
 
 /// A builder for creating a `Config` instance from command-line arguments or programmatically.
 ///
@@ -53,39 +65,121 @@ pub struct ConfigBuilder {
     pub(crate) git_download: Option<bool>,
     #[cfg(feature = "git")]
     pub(crate) show_download_path: Option<bool>,
+    #[cfg(feature = "git")]
+    pub(crate) relative_base: Option<RelativeBase>,
     // --- Filtering Options ---
     pub(crate) max_size: Option<String>,
+    pub(crate) max_files_per_dir: Option<usize>,
     pub(crate) no_recursive: Option<bool>,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) include_dirs: Option<bool>,
+    pub(crate) show_symlinks: Option<bool>,
+    pub(crate) follow_internal_symlinks: Option<bool>,
+    pub(crate) exclude_empty_dirs: Option<bool>,
+    pub(crate) report_empty_dirs: Option<bool>,
+    pub(crate) discover_callback: Option<DiscoverCallback>,
     pub(crate) extensions: Option<Vec<String>>,
+    pub(crate) languages: Option<Vec<String>>,
     pub(crate) exclude_extensions: Option<Vec<String>>,
+    pub(crate) extensionless: Option<Extensionless>,
     pub(crate) exclude_path_regex: Option<Vec<String>>,
     pub(crate) ignore_patterns: Option<Vec<String>>,
+    pub(crate) exclude_from: Option<Vec<String>>,
     pub(crate) path_regex: Option<Vec<String>>,
     pub(crate) filename_regex: Option<Vec<String>>,
+    pub(crate) regex_size_limit: Option<String>,
     pub(crate) no_gitignore: Option<bool>,
+    pub(crate) no_default_excludes: Option<bool>,
+    pub(crate) include_git_dir: Option<bool>,
+    pub(crate) treat_input_as_list: Option<bool>,
+    pub(crate) ignore_files: Option<Vec<String>>,
+    pub(crate) no_ignore_parent: Option<bool>,
+    pub(crate) ignore_explicit_input: Option<bool>,
+    pub(crate) excluded_files: Option<Vec<PathBuf>>,
     pub(crate) include_binary: Option<bool>,
+    pub(crate) binary_encoding: Option<BinaryEncoding>,
+    pub(crate) only_binary: Option<bool>,
     pub(crate) no_lockfiles: Option<bool>,
+    pub(crate) skip_secrets: Option<bool>,
+    pub(crate) skip_minified: Option<bool>,
+    pub(crate) skip_generated: Option<bool>,
+    pub(crate) max_lines: Option<usize>,
+    pub(crate) content_match: Option<Vec<String>>,
     // --- Content Processing Options ---
+    pub(crate) context_lines: Option<usize>,
+    pub(crate) transform_cmd: Option<String>,
+    pub(crate) post_process_callback: Option<PostProcessCallback>,
+    pub(crate) outline: Option<bool>,
+    pub(crate) max_file_content_bytes: Option<usize>,
+    pub(crate) io_concurrency: Option<usize>,
+    pub(crate) trust_extensions: Option<bool>,
     pub(crate) remove_comments: Option<bool>,
+    pub(crate) collapse_comment_spaces: Option<bool>,
+    pub(crate) comment_placeholder: Option<String>,
     pub(crate) remove_empty_lines: Option<bool>,
+    pub(crate) organize_imports: Option<bool>,
+    pub(crate) mask_literals: Option<usize>,
+    pub(crate) strip_license: Option<bool>,
+    pub(crate) license_marker: Option<bool>,
     pub(crate) content_filters: Vec<Box<dyn ContentFilter>>,
     // --- Output Formatting Options ---
     pub(crate) filename_only: Option<bool>,
     pub(crate) line_numbers: Option<bool>,
     pub(crate) backticks: Option<bool>,
     pub(crate) ticks: Option<u8>,
+    pub(crate) markdown_safe: Option<bool>,
+    pub(crate) content_style: Option<ContentStyle>,
+    pub(crate) ticks_policy: Option<TicksPolicy>,
+    pub(crate) block_separator: Option<String>,
+    pub(crate) toc: Option<bool>,
+    pub(crate) index_headers: Option<bool>,
+    pub(crate) flush_per_file: Option<bool>,
+    pub(crate) collapsible: Option<bool>,
+    pub(crate) anchor_comments: Option<bool>,
+    pub(crate) wrap: Option<usize>,
+    pub(crate) bom: Option<bool>,
+    pub(crate) output_newline: Option<Newline>,
+    pub(crate) if_exists: Option<IfExists>,
+    pub(crate) lang_map: Option<Vec<String>>,
+    pub(crate) delimiter: Option<String>,
+    pub(crate) roundtrip: Option<bool>,
+    pub(crate) index: Option<bool>,
+    pub(crate) format: Option<OutputFormat>,
     // --- Output Destination & Summary ---
     pub(crate) output_file: Option<String>,
+    pub(crate) output_template: Option<String>,
     #[cfg(feature = "clipboard")]
     pub(crate) paste: Option<bool>,
     pub(crate) summary: Option<bool>,
     pub(crate) counts: Option<bool>,
+    pub(crate) counts_in_header: Option<bool>,
+    pub(crate) summary_only_paths: Option<bool>,
+    pub(crate) top_files: Option<usize>,
+    pub(crate) content_hash_report: Option<bool>,
+    pub(crate) front_page: Option<bool>,
     // --- Processing Order ---
     pub(crate) process_last: Option<Vec<String>>,
     pub(crate) only_last: Option<bool>,
+    pub(crate) last_sort: Option<LastSort>,
     pub(crate) only: Option<Vec<String>>,
     // --- Execution Control ---
     pub(crate) dry_run: Option<bool>,
+    pub(crate) dry_run_counts: Option<bool>,
+    pub(crate) deterministic: Option<bool>,
+    pub(crate) stable: Option<bool>,
+    pub(crate) preserve_input_order: Option<bool>,
+    pub(crate) shard: Option<String>,
+    pub(crate) token_budget: Option<usize>,
+    pub(crate) stats: Option<bool>,
+    #[cfg(feature = "archive")]
+    pub(crate) save_archive: Option<String>,
+    #[cfg(feature = "archive")]
+    pub(crate) from_archive: Option<String>,
+    // --- Presets ---
+    pub(crate) preset: Option<Preset>,
+    // --- Environment ---
+    /// The first `DIRCAT_*` variable with an unrecognized value, and why; reported by `build`.
+    pub(crate) invalid_env: Option<(String, String)>,
 }
 
 impl ConfigBuilder {
@@ -95,8 +189,15 @@ impl ConfigBuilder {
     }
 
     /// Creates a new `ConfigBuilder` populated from the parsed command-line interface arguments.
+    ///
+    /// Options not given as flags are then read from `DIRCAT_*` environment variables
+    /// (see [`apply_env`](Self::apply_env)); switches that have a variable are left
+    /// unset when their flag is absent so the variable can still apply. If a preset
+    /// was given, it is applied last.
+    /// Since presets only enable options, every flag given explicitly on the command
+    /// line is kept.
     pub fn from_cli(cli: Cli) -> Self {
-        Self {
+        let mut builder = Self {
             input_path: Some(cli.input_path),
             #[cfg(feature = "git")]
             git_branch: cli.git_branch,
@@ -110,34 +211,169 @@ impl ConfigBuilder {
             git_download: Some(cli.git_download),
             #[cfg(feature = "git")]
             show_download_path: Some(cli.show_download_path),
+            #[cfg(feature = "git")]
+            relative_base: cli.relative_base,
             max_size: cli.max_size,
+            max_files_per_dir: cli.max_files_per_dir,
             no_recursive: Some(cli.no_recursive),
+            max_depth: cli.max_depth,
+            include_dirs: None,
+            show_symlinks: Some(cli.show_symlinks),
+            follow_internal_symlinks: Some(cli.follow_internal_symlinks),
+            exclude_empty_dirs: None,
+            report_empty_dirs: Some(cli.report_empty_dirs),
+            discover_callback: None,
             extensions: cli.extensions,
+            languages: cli.languages,
             exclude_extensions: cli.exclude_extensions,
+            extensionless: if cli.no_extensionless {
+                Some(Extensionless::Exclude)
+            } else if cli.only_extensionless {
+                Some(Extensionless::Only)
+            } else {
+                None
+            },
             exclude_path_regex: cli.exclude_path_regex,
             ignore_patterns: cli.ignore_patterns,
+            exclude_from: cli.exclude_from,
             path_regex: cli.path_regex,
             filename_regex: cli.filename_regex,
-            no_gitignore: Some(cli.no_gitignore),
-            include_binary: Some(cli.include_binary),
-            no_lockfiles: Some(cli.no_lockfiles),
-            remove_comments: Some(cli.remove_comments),
-            remove_empty_lines: Some(cli.remove_empty_lines),
+            regex_size_limit: cli.regex_size_limit,
+            no_gitignore: cli.no_gitignore.then_some(true),
+            no_default_excludes: Some(cli.no_default_excludes),
+            include_git_dir: Some(cli.include_git_dir),
+            treat_input_as_list: Some(cli.treat_as_list),
+            ignore_files: cli.ignore_files,
+            no_ignore_parent: Some(cli.no_ignore_parent),
+            ignore_explicit_input: Some(cli.ignore_explicit_input),
+            excluded_files: None,
+            include_binary: cli.include_binary.then_some(true),
+            binary_encoding: cli.binary_encoding,
+            only_binary: Some(cli.only_binary),
+            no_lockfiles: cli.no_lockfiles.then_some(true),
+            skip_secrets: Some(cli.skip_secrets),
+            skip_minified: Some(cli.skip_minified),
+            skip_generated: Some(cli.skip_generated),
+            max_lines: cli.max_lines,
+            content_match: cli.content_match,
+            context_lines: cli.context_lines,
+            transform_cmd: cli.transform_cmd,
+            post_process_callback: None,
+            outline: Some(cli.outline),
+            max_file_content_bytes: cli.max_file_content_bytes,
+            io_concurrency: cli.io_concurrency,
+            trust_extensions: Some(cli.trust_extensions),
+            remove_comments: cli.remove_comments.then_some(true),
+            collapse_comment_spaces: Some(cli.collapse_comment_spaces),
+            comment_placeholder: cli.comment_placeholder,
+            remove_empty_lines: cli.remove_empty_lines.then_some(true),
+            organize_imports: Some(cli.organize_imports),
+            mask_literals: cli.mask_literals,
+            strip_license: Some(cli.strip_license),
+            license_marker: Some(cli.license_marker),
             content_filters: Vec::new(),
             filename_only: Some(cli.filename_only),
-            line_numbers: Some(cli.line_numbers),
+            line_numbers: cli.line_numbers.then_some(true),
             backticks: Some(cli.backticks),
             ticks: Some(cli.ticks),
+            markdown_safe: Some(cli.markdown_safe),
+            ticks_policy: cli.ticks_policy,
+            block_separator: if cli.no_block_separator {
+                Some(String::new())
+            } else {
+                cli.block_separator.as_deref().map(unescape_separator)
+            },
+            content_style: if cli.no_fence {
+                Some(ContentStyle::Raw)
+            } else {
+                cli.content_style
+            },
+            toc: Some(cli.toc),
+            index_headers: Some(cli.index_headers),
+            flush_per_file: Some(cli.flush_per_file),
+            collapsible: Some(cli.collapsible),
+            anchor_comments: Some(cli.anchor_comments),
+            wrap: cli.wrap,
+            bom: Some(cli.bom),
+            output_newline: cli.canonicalize_output_newlines,
+            if_exists: cli.if_exists,
+            lang_map: cli.lang_map,
+            delimiter: cli.delimiter,
+            roundtrip: Some(cli.roundtrip),
+            index: Some(cli.index),
+            format: cli.format,
             output_file: cli.output_file,
+            output_template: cli.output_template,
             #[cfg(feature = "clipboard")]
             paste: Some(cli.paste),
-            summary: Some(cli.summary),
+            summary: cli.summary.then_some(true),
             counts: Some(cli.counts),
+            counts_in_header: Some(cli.counts_in_header),
+            top_files: cli.top,
+            content_hash_report: Some(cli.content_hash_report),
+            front_page: Some(cli.front_page),
+            summary_only_paths: Some(cli.summary_only_paths),
             process_last: cli.process_last,
             only_last: Some(cli.only_last),
+            last_sort: cli.last_sort,
             only: cli.only,
             dry_run: Some(cli.dry_run),
+            dry_run_counts: Some(cli.dry_run_counts),
+            deterministic: Some(cli.deterministic),
+            stable: Some(cli.stable),
+            preserve_input_order: Some(cli.preserve_order),
+            shard: cli.shard,
+            token_budget: cli.budget_tokens,
+            stats: Some(cli.stats),
+            #[cfg(feature = "archive")]
+            save_archive: cli.save_archive,
+            #[cfg(feature = "archive")]
+            from_archive: cli.from_archive,
+            preset: None,
+            invalid_env: None,
+        };
+        builder = builder.apply_env();
+        if let Some(preset) = cli.preset {
+            builder = builder.preset(preset);
         }
+        builder
+    }
+
+    /// Fills options that are not yet set from `DIRCAT_*` environment variables.
+    ///
+    /// Values already set on the builder take precedence, so call this before
+    /// setters that should override the environment. [`from_cli`](Self::from_cli)
+    /// calls it after reading the flags. The supported variables are
+    /// `DIRCAT_EXTENSIONS`, `DIRCAT_EXCLUDE_EXTENSIONS`, `DIRCAT_IGNORE`,
+    /// `DIRCAT_MAX_SIZE`, `DIRCAT_OUTPUT`, `DIRCAT_REMOVE_COMMENTS`,
+    /// `DIRCAT_REMOVE_EMPTY_LINES`, `DIRCAT_NO_GITIGNORE`, `DIRCAT_NO_LOCKFILES`,
+    /// `DIRCAT_INCLUDE_BINARY`, `DIRCAT_LINE_NUMBERS`, and `DIRCAT_SUMMARY`.
+    /// Lists are comma-separated and switches take values like `true` or `0`.
+    ///
+    /// # Errors
+    ///
+    /// An unrecognized switch value makes [`build`](Self::build) return
+    /// `ConfigError::InvalidValue` naming the variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// // An explicitly set option is never replaced by the environment.
+    /// let config = ConfigBuilder::new()
+    ///     .extensions(vec!["rs".to_string()])
+    ///     .apply_env()
+    ///     .build()?;
+    /// assert_eq!(config.discovery.extensions, Some(vec!["rs".to_string()]));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn apply_env(mut self) -> Self {
+        env::apply(&mut self, |name| std::env::var(name).ok());
+        self
     }
 
     // --- Setter Methods ---
@@ -286,26 +522,31 @@ impl ConfigBuilder {
         self
     }
 
-    /// Sets the maximum file size to include (e.g., "1M", "512k").
+    /// Sets what file paths in the output are relative to.
+    ///
+    /// With [`RelativeBase::GitRoot`], paths are relative to the root of the git
+    /// working tree containing the input, rather than to the input itself.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::config::{ConfigBuilder, RelativeBase};
     /// # use dircat::errors::Result;
+    /// # #[cfg(feature = "git")]
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().max_size("10k").build()?;
-    /// assert_eq!(config.discovery.max_size, Some(10_000));
+    /// let config = ConfigBuilder::new().relative_base(RelativeBase::GitRoot).build()?;
+    /// assert_eq!(config.relative_base, RelativeBase::GitRoot);
     /// # Ok(())
     /// # }
     /// ```
+    #[cfg(feature = "git")]
     #[must_use]
-    pub fn max_size(mut self, size: impl Into<String>) -> Self {
-        self.max_size = Some(size.into());
+    pub fn relative_base(mut self, base: RelativeBase) -> Self {
+        self.relative_base = Some(base);
         self
     }
 
-    /// Disables recursive directory traversal if `true`.
+    /// Sets the maximum file size to include (e.g., "1M", "512k").
     ///
     /// # Examples
     ///
@@ -313,18 +554,21 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().no_recursive(true).build()?;
-    /// assert!(!config.discovery.recursive);
+    /// let config = ConfigBuilder::new().max_size("10k").build()?;
+    /// assert_eq!(config.discovery.max_size, Some(10_000));
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn no_recursive(mut self, no_recurse: bool) -> Self {
-        self.no_recursive = Some(no_recurse);
+    pub fn max_size(mut self, size: impl Into<String>) -> Self {
+        self.max_size = Some(size.into());
         self
     }
 
-    /// Sets the list of file extensions to include.
+    /// Sets the maximum number of files taken from any single directory.
+    ///
+    /// Within each directory, the first `count` files by path are kept and the
+    /// rest are skipped, so one noisy directory cannot dominate the output.
     ///
     /// # Examples
     ///
@@ -332,19 +576,18 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let exts = vec!["rs".to_string(), "toml".to_string()];
-    /// let config = ConfigBuilder::new().extensions(exts.clone()).build()?;
-    /// assert_eq!(config.discovery.extensions, Some(exts));
+    /// let config = ConfigBuilder::new().max_files_per_dir(50).build()?;
+    /// assert_eq!(config.discovery.max_files_per_dir, Some(50));
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn extensions(mut self, exts: Vec<String>) -> Self {
-        self.extensions = Some(exts);
+    pub fn max_files_per_dir(mut self, count: usize) -> Self {
+        self.max_files_per_dir = Some(count);
         self
     }
 
-    /// Sets the list of file extensions to exclude.
+    /// Disables recursive directory traversal if `true`.
     ///
     /// # Examples
     ///
@@ -352,19 +595,22 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let exts = vec!["log".to_string(), "tmp".to_string()];
-    /// let config = ConfigBuilder::new().exclude_extensions(exts.clone()).build()?;
-    /// assert_eq!(config.discovery.exclude_extensions, Some(exts));
+    /// let config = ConfigBuilder::new().no_recursive(true).build()?;
+    /// assert!(!config.discovery.recursive);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn exclude_extensions(mut self, exts: Vec<String>) -> Self {
-        self.exclude_extensions = Some(exts);
+    pub fn no_recursive(mut self, no_recurse: bool) -> Self {
+        self.no_recursive = Some(no_recurse);
         self
     }
 
-    /// Sets the list of regular expressions for excluding file paths.
+    /// Limits discovery to `depth` levels below the input directory.
+    ///
+    /// `1` keeps only the directory's direct children, like
+    /// [`no_recursive`](Self::no_recursive). For GitHub folder URLs, the API
+    /// listing also stops at this depth, so deeper directories are never requested.
     ///
     /// # Examples
     ///
@@ -372,19 +618,22 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let regexes = vec!["/tests/".to_string()];
-    /// let config = ConfigBuilder::new().exclude_path_regex(regexes).build()?;
-    /// assert!(config.discovery.exclude_path_regex.is_some());
+    /// let config = ConfigBuilder::new().max_depth(2).build()?;
+    /// assert_eq!(config.discovery.max_depth, Some(2));
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn exclude_path_regex(mut self, regexes: Vec<String>) -> Self {
-        self.exclude_path_regex = Some(regexes);
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
         self
     }
 
-    /// Sets the list of custom glob patterns to ignore.
+    /// Also yields directories from discovery if `true`.
+    ///
+    /// Directories are returned as `FileInfo` entries with `is_dir` set and no content,
+    /// which is useful for enumerating the directory structure. The Markdown output
+    /// skips them.
     ///
     /// # Examples
     ///
@@ -392,19 +641,21 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let patterns = vec!["target/*".to_string()];
-    /// let config = ConfigBuilder::new().ignore_patterns(patterns.clone()).build()?;
-    /// assert_eq!(config.discovery.ignore_patterns, Some(patterns));
+    /// let config = ConfigBuilder::new().include_dirs(true).build()?;
+    /// assert!(config.discovery.include_dirs);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn ignore_patterns(mut self, patterns: Vec<String>) -> Self {
-        self.ignore_patterns = Some(patterns);
+    pub fn include_dirs(mut self, include: bool) -> Self {
+        self.include_dirs = Some(include);
         self
     }
 
-    /// Sets the list of regular expressions for including file paths.
+    /// Also yields symbolic links from discovery if `true`, without following them.
+    ///
+    /// Links are returned as `FileInfo` entries with `symlink_target` set and no
+    /// content. The summary lists them as `link -> target (symlink)`.
     ///
     /// # Examples
     ///
@@ -412,19 +663,23 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let regexes = vec!["^src/".to_string()];
-    /// let config = ConfigBuilder::new().path_regex(regexes).build()?;
-    /// assert!(config.discovery.path_regex.is_some());
+    /// let config = ConfigBuilder::new().show_symlinks(true).build()?;
+    /// assert!(config.discovery.show_symlinks);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn path_regex(mut self, regexes: Vec<String>) -> Self {
-        self.path_regex = Some(regexes);
+    pub fn show_symlinks(mut self, show: bool) -> Self {
+        self.show_symlinks = Some(show);
         self
     }
 
-    /// Sets the list of regular expressions for including filenames.
+    /// Follows symbolic links whose target resolves inside the input root if `true`.
+    ///
+    /// Each link's target is canonicalized and compared against the canonical
+    /// input root, so links that escape the tree, and broken links, are skipped.
+    /// Linked directories are walked like regular ones. Conflicts with
+    /// [`show_symlinks`](Self::show_symlinks).
     ///
     /// # Examples
     ///
@@ -432,19 +687,28 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let regexes = vec![r"\.toml$".to_string()];
-    /// let config = ConfigBuilder::new().filename_regex(regexes).build()?;
-    /// assert!(config.discovery.filename_regex.is_some());
+    /// let config = ConfigBuilder::new().follow_internal_symlinks(true).build()?;
+    /// assert!(config.discovery.follow_internal_symlinks);
+    ///
+    /// let result = ConfigBuilder::new()
+    ///     .follow_internal_symlinks(true)
+    ///     .show_symlinks(true)
+    ///     .build();
+    /// assert!(result.is_err());
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn filename_regex(mut self, regexes: Vec<String>) -> Self {
-        self.filename_regex = Some(regexes);
+    pub fn follow_internal_symlinks(mut self, follow: bool) -> Self {
+        self.follow_internal_symlinks = Some(follow);
         self
     }
 
-    /// Disables `.gitignore` processing if `true`.
+    /// Drops directories with no included file beneath them if `true`.
+    ///
+    /// Only meaningful together with [`include_dirs`](Self::include_dirs). A directory
+    /// counts as empty when every file under it was filtered out, during discovery
+    /// or processing.
     ///
     /// # Examples
     ///
@@ -452,18 +716,29 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().no_gitignore(true).build()?;
-    /// assert!(!config.discovery.use_gitignore);
+    /// let config = ConfigBuilder::new()
+    ///     .include_dirs(true)
+    ///     .exclude_empty_dirs(true)
+    ///     .build()?;
+    /// assert!(config.discovery.exclude_empty_dirs);
+    ///
+    /// let result = ConfigBuilder::new().exclude_empty_dirs(true).build();
+    /// assert!(result.is_err());
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn no_gitignore(mut self, no_gitignore: bool) -> Self {
-        self.no_gitignore = Some(no_gitignore);
+    pub fn exclude_empty_dirs(mut self, exclude: bool) -> Self {
+        self.exclude_empty_dirs = Some(exclude);
         self
     }
 
-    /// Includes binary files in the output if `true`.
+    /// Collects the directories with no included file beneath them if `true`.
+    ///
+    /// The sorted relative paths are returned in
+    /// [`DircatResult::empty_dirs`](crate::DircatResult::empty_dirs), and the CLI
+    /// prints them to stderr. Directory entries are not added to the results
+    /// unless [`include_dirs`](Self::include_dirs) is also set.
     ///
     /// # Examples
     ///
@@ -471,18 +746,21 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().include_binary(true).build()?;
-    /// assert!(config.processing.include_binary);
+    /// let config = ConfigBuilder::new().report_empty_dirs(true).build()?;
+    /// assert!(config.discovery.report_empty_dirs);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn include_binary(mut self, include: bool) -> Self {
-        self.include_binary = Some(include);
+    pub fn report_empty_dirs(mut self, report: bool) -> Self {
+        self.report_empty_dirs = Some(report);
         self
     }
 
-    /// Skips common lockfiles if `true`.
+    /// Sets a callback that is invoked for each file passing the discovery filters.
+    ///
+    /// Returning `false` skips the file. The callback runs on the walker's threads,
+    /// so it may be called concurrently and in any order.
     ///
     /// # Examples
     ///
@@ -490,18 +768,23 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().no_lockfiles(true).build()?;
-    /// assert!(config.discovery.skip_lockfiles);
+    /// let config = ConfigBuilder::new()
+    ///     .discover_callback(|file_info| !file_info.relative_path.starts_with("generated"))
+    ///     .build()?;
+    /// assert!(config.discovery.discover_callback.is_some());
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn no_lockfiles(mut self, no_lockfiles: bool) -> Self {
-        self.no_lockfiles = Some(no_lockfiles);
+    pub fn discover_callback(
+        mut self,
+        callback: impl Fn(&FileInfo) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.discover_callback = Some(DiscoverCallback(Arc::new(callback)));
         self
     }
 
-    /// Enables removal of C-style comments if `true`.
+    /// Sets the list of file extensions to include.
     ///
     /// # Examples
     ///
@@ -509,18 +792,24 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().remove_comments(true).build()?;
-    /// assert!(config.processing.content_filters.iter().any(|f| f.name() == "RemoveCommentsFilter"));
+    /// let exts = vec!["rs".to_string(), "toml".to_string()];
+    /// let config = ConfigBuilder::new().extensions(exts.clone()).build()?;
+    /// assert_eq!(config.discovery.extensions, Some(exts));
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn remove_comments(mut self, remove: bool) -> Self {
-        self.remove_comments = Some(remove);
+    pub fn extensions(mut self, exts: Vec<String>) -> Self {
+        self.extensions = Some(exts);
         self
     }
 
-    /// Enables removal of empty lines if `true`.
+    /// Includes only files of these languages, by their known file extensions.
+    ///
+    /// Each name (e.g., `"rust"`, `"python"`) is expanded with
+    /// [`language_extensions`](crate::core_types::language_extensions) and added
+    /// to the `extensions` list, so files are matched by extension only. An
+    /// unknown name is an error at build time.
     ///
     /// # Examples
     ///
@@ -528,66 +817,65 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().remove_empty_lines(true).build()?;
-    /// assert!(config.processing.content_filters.iter().any(|f| f.name() == "RemoveEmptyLinesFilter"));
+    /// let config = ConfigBuilder::new()
+    ///     .languages(vec!["rust".to_string(), "yaml".to_string()])
+    ///     .build()?;
+    /// assert_eq!(
+    ///     config.discovery.extensions,
+    ///     Some(vec!["rs".to_string(), "yaml".to_string(), "yml".to_string()])
+    /// );
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn remove_empty_lines(mut self, remove: bool) -> Self {
-        self.remove_empty_lines = Some(remove);
+    pub fn languages(mut self, languages: Vec<String>) -> Self {
+        self.languages = Some(languages);
         self
     }
 
-    /// Adds a custom content filter to the processing pipeline.
+    /// Sets the list of file extensions to exclude.
     ///
     /// # Examples
     ///
     /// ```
     /// # use dircat::config::ConfigBuilder;
-    /// # use dircat::processing::filters::ContentFilter;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// #[derive(Clone)]
-    /// struct UppercaseFilter;
-    /// impl ContentFilter for UppercaseFilter {
-    ///     fn apply(&self, content: &str) -> String { content.to_uppercase() }
-    ///     fn name(&self) -> &'static str { "UppercaseFilter" }
-    /// }
-    ///
-    /// let config = ConfigBuilder::new()
-    ///     .content_filter(Box::new(UppercaseFilter))
-    ///     .build()?;
-    /// assert_eq!(config.processing.content_filters[0].name(), "UppercaseFilter");
+    /// let exts = vec!["log".to_string(), "tmp".to_string()];
+    /// let config = ConfigBuilder::new().exclude_extensions(exts.clone()).build()?;
+    /// assert_eq!(config.discovery.exclude_extensions, Some(exts));
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn content_filter(mut self, filter: Box<dyn ContentFilter>) -> Self {
-        self.content_filters.push(filter);
+    pub fn exclude_extensions(mut self, exts: Vec<String>) -> Self {
+        self.exclude_extensions = Some(exts);
         self
     }
 
-    /// Displays only the filename in headers if `true`.
+    /// Sets whether files without an extension are kept, dropped, or the only ones kept.
+    ///
+    /// [`Extensionless::Only`] cannot be combined with `extensions`, which
+    /// already requires an extension.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::config::{ConfigBuilder, Extensionless};
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().filename_only(true).build()?;
-    /// assert!(config.output.filename_only_header);
+    /// let config = ConfigBuilder::new().extensionless(Extensionless::Exclude).build()?;
+    /// assert_eq!(config.discovery.extensionless, Extensionless::Exclude);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn filename_only(mut self, filename_only: bool) -> Self {
-        self.filename_only = Some(filename_only);
+    pub fn extensionless(mut self, extensionless: Extensionless) -> Self {
+        self.extensionless = Some(extensionless);
         self
     }
 
-    /// Adds line numbers to the output if `true`.
+    /// Sets the list of regular expressions for excluding file paths.
     ///
     /// # Examples
     ///
@@ -595,18 +883,19 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().line_numbers(true).build()?;
-    /// assert!(config.output.line_numbers);
+    /// let regexes = vec!["/tests/".to_string()];
+    /// let config = ConfigBuilder::new().exclude_path_regex(regexes).build()?;
+    /// assert!(config.discovery.exclude_path_regex.is_some());
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
-        self.line_numbers = Some(line_numbers);
+    pub fn exclude_path_regex(mut self, regexes: Vec<String>) -> Self {
+        self.exclude_path_regex = Some(regexes);
         self
     }
 
-    /// Wraps filenames in backticks if `true`.
+    /// Sets the list of custom glob patterns to ignore.
     ///
     /// # Examples
     ///
@@ -614,18 +903,23 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().backticks(true).build()?;
-    /// assert!(config.output.backticks);
+    /// let patterns = vec!["target/*".to_string()];
+    /// let config = ConfigBuilder::new().ignore_patterns(patterns.clone()).build()?;
+    /// assert_eq!(config.discovery.ignore_patterns, Some(patterns));
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn backticks(mut self, backticks: bool) -> Self {
-        self.backticks = Some(backticks);
+    pub fn ignore_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.ignore_patterns = Some(patterns);
         self
     }
 
-    /// Sets the number of backticks for Markdown code fences.
+    /// Sets files to read additional ignore glob patterns from.
+    ///
+    /// Each file lists one pattern per line; blank lines and `#` comments are
+    /// skipped. The patterns are appended to those set with `ignore_patterns`
+    /// when the configuration is built.
     ///
     /// # Examples
     ///
@@ -633,59 +927,70 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().ticks(4).build()?;
-    /// assert_eq!(config.output.num_ticks, 4);
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let list = dir.path().join("excludes.txt");
+    /// std::fs::write(&list, "# build output\ntarget/*\n\n*.log\n").unwrap();
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .exclude_from(vec![list.to_string_lossy().into_owned()])
+    ///     .build()?;
+    /// assert_eq!(
+    ///     config.discovery.ignore_patterns,
+    ///     Some(vec!["target/*".to_string(), "*.log".to_string()])
+    /// );
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn ticks(mut self, count: u8) -> Self {
-        self.ticks = Some(count);
+    pub fn exclude_from(mut self, paths: Vec<String>) -> Self {
+        self.exclude_from = Some(paths);
         self
     }
 
-    /// Sets the output file path.
+    /// Sets the list of regular expressions for including file paths.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use dircat::config::{ConfigBuilder, OutputDestination};
+    /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
-    /// # use std::path::PathBuf;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().output_file("output.md").build()?;
-    /// assert_eq!(config.output_destination, OutputDestination::File(PathBuf::from("output.md")));
+    /// let regexes = vec!["^src/".to_string()];
+    /// let config = ConfigBuilder::new().path_regex(regexes).build()?;
+    /// assert!(config.discovery.path_regex.is_some());
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn output_file(mut self, path: impl Into<String>) -> Self {
-        self.output_file = Some(path.into());
+    pub fn path_regex(mut self, regexes: Vec<String>) -> Self {
+        self.path_regex = Some(regexes);
         self
     }
 
-    /// Copies the output to the clipboard if `true`.
+    /// Sets the list of regular expressions for including filenames.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use dircat::config::{ConfigBuilder, OutputDestination};
+    /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
-    /// # #[cfg(feature = "clipboard")]
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().paste(true).build()?;
-    /// assert_eq!(config.output_destination, OutputDestination::Clipboard);
+    /// let regexes = vec![r"\.toml$".to_string()];
+    /// let config = ConfigBuilder::new().filename_regex(regexes).build()?;
+    /// assert!(config.discovery.filename_regex.is_some());
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "clipboard")]
     #[must_use]
-    pub fn paste(mut self, paste: bool) -> Self {
-        self.paste = Some(paste);
+    pub fn filename_regex(mut self, regexes: Vec<String>) -> Self {
+        self.filename_regex = Some(regexes);
         self
     }
 
-    /// Appends a summary of processed files if `true`.
+    /// Sets the maximum compiled size of each user-supplied regex (e.g., "1M").
+    ///
+    /// Applies to the path, filename, exclude and content-match regexes. A pattern
+    /// that compiles to a larger program makes [`build`](Self::build) fail.
     ///
     /// # Examples
     ///
@@ -693,18 +998,21 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().summary(true).build()?;
-    /// assert!(config.output.summary);
+    /// let result = ConfigBuilder::new()
+    ///     .path_regex(vec!["a{1000}".to_string()])
+    ///     .regex_size_limit("10k")
+    ///     .build();
+    /// assert!(result.is_err());
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn summary(mut self, summary: bool) -> Self {
-        self.summary = Some(summary);
+    pub fn regex_size_limit(mut self, limit: impl Into<String>) -> Self {
+        self.regex_size_limit = Some(limit.into());
         self
     }
 
-    /// Includes file counts in the summary if `true`.
+    /// Disables `.gitignore` processing if `true`.
     ///
     /// # Examples
     ///
@@ -712,19 +1020,21 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().counts(true).build()?;
-    /// assert!(config.output.counts);
-    /// assert!(config.output.summary); // Implies summary
+    /// let config = ConfigBuilder::new().no_gitignore(true).build()?;
+    /// assert!(!config.discovery.use_gitignore);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn counts(mut self, counts: bool) -> Self {
-        self.counts = Some(counts);
+    pub fn no_gitignore(mut self, no_gitignore: bool) -> Self {
+        self.no_gitignore = Some(no_gitignore);
         self
     }
 
-    /// Sets the list of glob patterns for files to be processed last.
+    /// Stops skipping the built-in excluded directories if `true`.
+    ///
+    /// By default, directories such as `node_modules` and `target` are skipped,
+    /// even when `.gitignore` handling is disabled.
     ///
     /// # Examples
     ///
@@ -732,19 +1042,18 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let patterns = vec!["README.md".to_string()];
-    /// let config = ConfigBuilder::new().process_last(patterns.clone()).build()?;
-    /// assert_eq!(config.discovery.process_last, Some(patterns));
+    /// let config = ConfigBuilder::new().no_default_excludes(true).build()?;
+    /// assert!(!config.discovery.use_default_excludes);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn process_last(mut self, patterns: Vec<String>) -> Self {
-        self.process_last = Some(patterns);
+    pub fn no_default_excludes(mut self, no_default_excludes: bool) -> Self {
+        self.no_default_excludes = Some(no_default_excludes);
         self
     }
 
-    /// Processes only the files matching `process_last` patterns if `true`.
+    /// Scans the `.git` directory if `true`. It is skipped by default.
     ///
     /// # Examples
     ///
@@ -752,19 +1061,24 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let config = ConfigBuilder::new().process_last(vec!["*.rs".to_string()]).only_last(true).build()?;
-    /// assert!(config.discovery.only_last);
+    /// let config = ConfigBuilder::new().include_git_dir(true).build()?;
+    /// assert!(config.discovery.include_git_dir);
     /// # Ok(())
     /// # }
     /// ```
     #[must_use]
-    pub fn only_last(mut self, only_last: bool) -> Self {
-        self.only_last = Some(only_last);
+    pub fn include_git_dir(mut self, include: bool) -> Self {
+        self.include_git_dir = Some(include);
         self
     }
 
-    /// Sets the list of glob patterns for files to be processed, skipping all others.
-    /// This is a shorthand for `process_last` and `only_last`.
+    /// Treats the input file as a list of paths to include if `true`.
+    ///
+    /// The list holds one path per line. Relative paths are resolved against the
+    /// list file's directory, which is also the base for the relative paths shown
+    /// in the output. Blank lines and lines starting with `#` are skipped, and
+    /// listed directories are walked like a normal input. The input must be a
+    /// file; a file is never treated as a list unless this is set.
     ///
     /// # Examples
     ///
@@ -772,9 +1086,1455 @@ impl ConfigBuilder {
     /// # use dircat::config::ConfigBuilder;
     /// # use dircat::errors::Result;
     /// # fn main() -> Result<()> {
-    /// let patterns = vec!["*.rs".to_string()];
-    /// let config = ConfigBuilder::new().only(patterns.clone()).build()?;
-    /// assert_eq!(config.discovery.process_last, Some(patterns));
+    /// let config = ConfigBuilder::new()
+    ///     .input_path("files.txt")
+    ///     .treat_input_as_list(true)
+    ///     .build()?;
+    /// assert!(config.discovery.treat_input_as_list);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn treat_input_as_list(mut self, treat_as_list: bool) -> Self {
+        self.treat_input_as_list = Some(treat_as_list);
+        self
+    }
+
+    /// Sets additional ignore file names to honor in each directory.
+    ///
+    /// Files with these names use gitignore syntax, like the tool-specific
+    /// `.aiignore` or `.cursorignore` conventions. `.dircatignore` is always
+    /// honored in addition to these.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .ignore_files(vec![".aiignore".to_string()])
+    ///     .build()?;
+    /// assert_eq!(config.discovery.ignore_filenames, vec![".dircatignore", ".aiignore"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn ignore_files(mut self, names: Vec<String>) -> Self {
+        self.ignore_files = Some(names);
+        self
+    }
+
+    /// Ignores ignore files in directories above the input path if `true`.
+    ///
+    /// By default, `.gitignore` and `.ignore` files in every ancestor directory
+    /// apply, up to the filesystem root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().no_ignore_parent(true).build()?;
+    /// assert!(!config.discovery.use_parent_ignores);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn no_ignore_parent(mut self, no_ignore_parent: bool) -> Self {
+        self.no_ignore_parent = Some(no_ignore_parent);
+        self
+    }
+
+    /// Applies ignore files to a file given directly as the input if `true`.
+    ///
+    /// By default, naming a file explicitly includes it even when a `.gitignore`
+    /// rule covers it, such as a file inside an ignored directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().ignore_explicit_input(true).build()?;
+    /// assert!(config.discovery.ignore_explicit_input);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn ignore_explicit_input(mut self, ignore: bool) -> Self {
+        self.ignore_explicit_input = Some(ignore);
+        self
+    }
+
+    /// Never includes the file at `path`, even if it is inside the input.
+    ///
+    /// This is for files dircat writes or reads itself, such as a saved bundle
+    /// checked by `--verify`. The output file is always excluded this way. The
+    /// path does not have to exist yet. Can be called more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = ConfigBuilder::new().exclude_file("bundle.md").build()?;
+    /// let cwd = std::env::current_dir()?.canonicalize()?;
+    /// assert_eq!(config.discovery.excluded_files, vec![cwd.join("bundle.md")]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn exclude_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.excluded_files
+            .get_or_insert_with(Vec::new)
+            .push(path.into());
+        self
+    }
+
+    /// Includes binary files in the output if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().include_binary(true).build()?;
+    /// assert!(config.processing.include_binary);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn include_binary(mut self, include: bool) -> Self {
+        self.include_binary = Some(include);
+        self
+    }
+
+    /// Sets how the content of included binary files is written.
+    ///
+    /// The default, [`BinaryEncoding::Lossy`], decodes the bytes as UTF-8 and
+    /// replaces invalid sequences. [`BinaryEncoding::Base64`] writes them as
+    /// base64 in a code fence labeled `base64`, with the original size in the
+    /// header, so `--unpack` can restore the file exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{BinaryEncoding, ConfigBuilder};
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .include_binary(true)
+    ///     .binary_encoding(BinaryEncoding::Base64)
+    ///     .build()?;
+    /// assert_eq!(config.processing.binary_encoding, BinaryEncoding::Base64);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn binary_encoding(mut self, encoding: BinaryEncoding) -> Self {
+        self.binary_encoding = Some(encoding);
+        self
+    }
+
+    /// Keeps *only* files detected as binary if `true`, listing their paths and sizes
+    /// instead of their content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().only_binary(true).build()?;
+    /// assert!(config.processing.only_binary);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn only_binary(mut self, only: bool) -> Self {
+        self.only_binary = Some(only);
+        self
+    }
+
+    /// Skips common lockfiles if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().no_lockfiles(true).build()?;
+    /// assert!(config.discovery.skip_lockfiles);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn no_lockfiles(mut self, no_lockfiles: bool) -> Self {
+        self.no_lockfiles = Some(no_lockfiles);
+        self
+    }
+
+    /// Skips files likely to contain secrets (e.g., `.env`, `*.pem`, `id_rsa`) if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().skip_secrets(true).build()?;
+    /// assert!(config.discovery.skip_secrets);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn skip_secrets(mut self, skip: bool) -> Self {
+        self.skip_secrets = Some(skip);
+        self
+    }
+
+    /// Skips files whose content looks minified if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().skip_minified(true).build()?;
+    /// assert!(config.processing.skip_minified);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn skip_minified(mut self, skip: bool) -> Self {
+        self.skip_minified = Some(skip);
+        self
+    }
+
+    /// Skips files with a generated-code marker in their first lines if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().skip_generated(true).build()?;
+    /// assert!(config.processing.skip_generated);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn skip_generated(mut self, skip: bool) -> Self {
+        self.skip_generated = Some(skip);
+        self
+    }
+
+    /// Skips text files with more than `max_lines` lines.
+    ///
+    /// Unlike `max_size`, this needs the file's content, so it is checked
+    /// during processing rather than discovery.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().max_lines(5000).build()?;
+    /// assert_eq!(config.processing.max_lines, Some(5000));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = Some(max_lines);
+        self
+    }
+
+    /// Includes only files whose content matches any of these regexes (case-insensitive).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .content_match(vec!["fn main".to_string()])
+    ///     .build()?;
+    /// assert!(config.processing.content_match.is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn content_match(mut self, regexes: Vec<String>) -> Self {
+        self.content_match = Some(regexes);
+        self
+    }
+
+    /// Condenses matched files to windows of `lines` lines around each content match.
+    ///
+    /// Omitted regions are replaced with a `...` line. Requires [`content_match`](Self::content_match).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .content_match(vec!["TODO".to_string()])
+    ///     .context_lines(2)
+    ///     .build()?;
+    /// assert_eq!(config.processing.context_lines, Some(2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn context_lines(mut self, lines: usize) -> Self {
+        self.context_lines = Some(lines);
+        self
+    }
+
+    /// Truncates each text file's processed content to at most `max_bytes` bytes.
+    ///
+    /// The cut is made at a UTF-8 character boundary after all content filters
+    /// have run, and a `... (truncated at N bytes)` line is appended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().max_file_content_bytes(4096).build()?;
+    /// assert_eq!(config.processing.max_file_content_bytes, Some(4096));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_file_content_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_file_content_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Limits the number of files read from disk at the same time.
+    ///
+    /// This is separate from the number of processing threads: workers wait
+    /// for a free slot before opening a file, which avoids flooding slow or
+    /// networked filesystems with concurrent reads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().io_concurrency(4).build()?;
+    /// assert_eq!(config.processing.io_concurrency, Some(4));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn io_concurrency(mut self, limit: usize) -> Self {
+        self.io_concurrency = Some(limit);
+        self
+    }
+
+    /// Classifies files as binary by extension alone if `true`.
+    ///
+    /// Files with a well-known binary extension (images, archives, compiled
+    /// objects, ...) are treated as binary and everything else as text, so the
+    /// per-file head read is skipped. This is faster on very large trees, but a
+    /// binary file with an unlisted extension will be included as text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().trust_extensions(true).build()?;
+    /// assert!(config.processing.trust_extensions);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn trust_extensions(mut self, trust: bool) -> Self {
+        self.trust_extensions = Some(trust);
+        self
+    }
+
+    /// Pipes each text file's content through an external shell command.
+    ///
+    /// The command's stdout replaces the file's content before any other content
+    /// filters run. The file's path is passed in the `DIRCAT_FILE` environment
+    /// variable. A command that exits unsuccessfully fails the run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().transform_cmd("rustfmt --emit stdout").build()?;
+    /// assert_eq!(config.processing.transform_cmd.as_deref(), Some("rustfmt --emit stdout"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn transform_cmd(mut self, command: impl Into<String>) -> Self {
+        self.transform_cmd = Some(command.into());
+        self
+    }
+
+    /// Sets a callback that rewrites each text file's content after the content filters.
+    ///
+    /// The callback receives the file and its filtered content and returns the
+    /// content to output. It runs on the processing thread pool, so it must be
+    /// `Send + Sync` and may be called for several files at once. Binary files and
+    /// content passed to `process_content` are not passed to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .post_process_callback(|_file_info, content| content.replace("\t", "    "))
+    ///     .build()?;
+    /// assert!(config.processing.post_process_callback.is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn post_process_callback(
+        mut self,
+        callback: impl Fn(&FileInfo, String) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.post_process_callback = Some(PostProcessCallback(Arc::new(callback)));
+        self
+    }
+
+    /// Enables removal of C-style comments if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().remove_comments(true).build()?;
+    /// assert!(config.processing.content_filters.iter().any(|f| f.name() == "RemoveCommentsFilter"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn remove_comments(mut self, remove: bool) -> Self {
+        self.remove_comments = Some(remove);
+        self
+    }
+
+    /// Collapses the whitespace left behind by removed block comments if `true`.
+    ///
+    /// Requires [`remove_comments`](Self::remove_comments).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .remove_comments(true)
+    ///     .collapse_comment_spaces(true)
+    ///     .build()?;
+    /// assert!(config.processing.content_filters.iter().any(|f| f.name() == "CompactRemoveCommentsFilter"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn collapse_comment_spaces(mut self, collapse: bool) -> Self {
+        self.collapse_comment_spaces = Some(collapse);
+        self
+    }
+
+    /// Replaces each removed block comment with `placeholder` instead of dropping it.
+    ///
+    /// Requires [`remove_comments`](Self::remove_comments).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .remove_comments(true)
+    ///     .comment_placeholder("/* ... */")
+    ///     .build()?;
+    /// let filter = &config.processing.content_filters[0];
+    /// assert_eq!(filter.apply("a /* b */ c"), "a /* ... */ c");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn comment_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.comment_placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Enables removal of empty lines if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().remove_empty_lines(true).build()?;
+    /// assert!(config.processing.content_filters.iter().any(|f| f.name() == "RemoveEmptyLinesFilter"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn remove_empty_lines(mut self, remove: bool) -> Self {
+        self.remove_empty_lines = Some(remove);
+        self
+    }
+
+    /// Sorts and deduplicates runs of single-line imports if `true`.
+    ///
+    /// Rust `use`, Python `import`/`from`, and JavaScript `import` lines are
+    /// recognized by their syntax alone. This is a heuristic, cosmetic transform;
+    /// see [`organize_imports`](crate::processing::filters::organize_imports)
+    /// for what it does and does not handle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().organize_imports(true).build()?;
+    /// assert!(config
+    ///     .processing
+    ///     .content_filters
+    ///     .iter()
+    ///     .any(|f| f.name() == "OrganizeImportsFilter"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn organize_imports(mut self, organize: bool) -> Self {
+        self.organize_imports = Some(organize);
+        self
+    }
+
+    /// Reduces Rust, Python, and JavaScript/TypeScript files to an outline if `true`.
+    ///
+    /// Function bodies are replaced with `{ ... }`, and only declarations are kept.
+    /// Files in other languages keep their full content. See
+    /// [`outline`](crate::processing::outline) for what is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().outline(true).build()?;
+    /// assert!(config.processing.outline);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn outline(mut self, outline: bool) -> Self {
+        self.outline = Some(outline);
+        self
+    }
+
+    /// Replaces hex and decimal literals of at least `min_len` characters, such as
+    /// hashes, UUIDs, and long IDs, with a placeholder like `<hash:40>`.
+    ///
+    /// See [`mask_long_literals`](crate::processing::filters::mask_long_literals)
+    /// for what counts as a literal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().mask_literals(16).build()?;
+    /// assert!(config
+    ///     .processing
+    ///     .content_filters
+    ///     .iter()
+    ///     .any(|f| f.name() == "MaskLongLiteralsFilter"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn mask_literals(mut self, min_len: usize) -> Self {
+        self.mask_literals = Some(min_len);
+        self
+    }
+
+    /// Enables removal of a leading license/copyright comment block if `true`.
+    ///
+    /// The header is stripped before any other content filters run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().strip_license(true).build()?;
+    /// assert!(config.processing.content_filters.iter().any(|f| f.name() == "StripLicenseHeaderFilter"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn strip_license(mut self, strip: bool) -> Self {
+        self.strip_license = Some(strip);
+        self
+    }
+
+    /// Leaves a `// license header removed` line in place of a stripped license header if `true`.
+    ///
+    /// Requires [`strip_license`](Self::strip_license).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .strip_license(true)
+    ///     .license_marker(true)
+    ///     .build()?;
+    /// let filter = &config.processing.content_filters[0];
+    /// assert_eq!(
+    ///     filter.apply("// Copyright 2024 Me\nfn main() {}"),
+    ///     "// license header removed\nfn main() {}"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn license_marker(mut self, marker: bool) -> Self {
+        self.license_marker = Some(marker);
+        self
+    }
+
+    /// Adds a custom content filter to the processing pipeline.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::processing::filters::ContentFilter;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// #[derive(Clone)]
+    /// struct UppercaseFilter;
+    /// impl ContentFilter for UppercaseFilter {
+    ///     fn apply(&self, content: &str) -> String { content.to_uppercase() }
+    ///     fn name(&self) -> &'static str { "UppercaseFilter" }
+    /// }
+    ///
+    /// let config = ConfigBuilder::new()
+    ///     .content_filter(Box::new(UppercaseFilter))
+    ///     .build()?;
+    /// assert_eq!(config.processing.content_filters[0].name(), "UppercaseFilter");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn content_filter(mut self, filter: Box<dyn ContentFilter>) -> Self {
+        self.content_filters.push(filter);
+        self
+    }
+
+    /// Displays only the filename in headers if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().filename_only(true).build()?;
+    /// assert!(config.output.filename_only_header);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn filename_only(mut self, filename_only: bool) -> Self {
+        self.filename_only = Some(filename_only);
+        self
+    }
+
+    /// Adds line numbers to the output if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().line_numbers(true).build()?;
+    /// assert!(config.output.line_numbers);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = Some(line_numbers);
+        self
+    }
+
+    /// Wraps filenames in backticks if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().backticks(true).build()?;
+    /// assert!(config.output.backticks);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn backticks(mut self, backticks: bool) -> Self {
+        self.backticks = Some(backticks);
+        self
+    }
+
+    /// Sets the number of backticks for Markdown code fences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().ticks(4).build()?;
+    /// assert_eq!(config.output.num_ticks, 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn ticks(mut self, count: u8) -> Self {
+        self.ticks = Some(count);
+        self
+    }
+
+    /// Makes file blocks safe for strict Markdown renderers if `true`.
+    ///
+    /// Code fences are widened past the longest backtick run in each file, and
+    /// content lines that look like fences are escaped, so file content can never
+    /// close its block early.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().markdown_safe(true).build()?;
+    /// assert!(config.output.markdown_safe);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn markdown_safe(mut self, markdown_safe: bool) -> Self {
+        self.markdown_safe = Some(markdown_safe);
+        self
+    }
+
+    /// Sets how each file's content is rendered under its header.
+    ///
+    /// The default, [`ContentStyle::CodeFence`], uses a fenced code block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, ContentStyle};
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .content_style(ContentStyle::Blockquote)
+    ///     .build()?;
+    /// assert_eq!(config.output.content_style, ContentStyle::Blockquote);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn content_style(mut self, content_style: ContentStyle) -> Self {
+        self.content_style = Some(content_style);
+        self
+    }
+
+    /// Sets how the width of each file's code fence is chosen.
+    ///
+    /// The default, [`TicksPolicy::Fixed`], always uses the configured tick count.
+    /// [`TicksPolicy::PerLang`] widens fences only for fence-prone languages like
+    /// Markdown, so documentation files cannot close their block early while other
+    /// files keep the usual fence. [`markdown_safe`](Self::markdown_safe) widens every fence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, TicksPolicy};
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .ticks_policy(TicksPolicy::PerLang)
+    ///     .build()?;
+    /// assert_eq!(config.output.ticks_policy, TicksPolicy::PerLang);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn ticks_policy(mut self, ticks_policy: TicksPolicy) -> Self {
+        self.ticks_policy = Some(ticks_policy);
+        self
+    }
+
+    /// Sets the string written between file blocks in Markdown output.
+    ///
+    /// The default is `"\n"`, which leaves a blank line between blocks. An empty
+    /// string writes the blocks back to back. The string is written verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().block_separator("").build()?;
+    /// assert_eq!(config.output.block_separator, "");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn block_separator(mut self, separator: impl Into<String>) -> Self {
+        self.block_separator = Some(separator.into());
+        self
+    }
+
+    /// Starts the Markdown output with a table of contents if `true`.
+    ///
+    /// Each entry links to the GitHub-style anchor of a file's `## File:` header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().toc(true).build()?;
+    /// assert!(config.output.toc);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn toc(mut self, toc: bool) -> Self {
+        self.toc = Some(toc);
+        self
+    }
+
+    /// Numbers each file header with its 1-based position in the output if `true`.
+    ///
+    /// Headers then read `## File 7: src/main.rs`. The numbering follows the final
+    /// sorted order, so it matches the order the blocks appear in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().index_headers(true).build()?;
+    /// assert!(config.output.index_headers);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn index_headers(mut self, index_headers: bool) -> Self {
+        self.index_headers = Some(index_headers);
+        self
+    }
+
+    /// Wraps each file block in a collapsible `<details>` element if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().collapsible(true).build()?;
+    /// assert!(config.output.collapsible);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = Some(collapsible);
+        self
+    }
+
+    /// Writes a machine-readable HTML comment before each file block if `true`.
+    ///
+    /// The comment has the form `<!-- dircat:file path="src/main.rs" size="120" -->`,
+    /// with the relative path using `/` separators. It is invisible in rendered
+    /// Markdown and does not depend on the header format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().anchor_comments(true).build()?;
+    /// assert!(config.output.anchor_comments);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn anchor_comments(mut self, anchor_comments: bool) -> Self {
+        self.anchor_comments = Some(anchor_comments);
+        self
+    }
+
+    /// Hard-wraps content lines longer than `width` characters.
+    ///
+    /// Lines are broken at the last whitespace before the limit where possible.
+    /// Continuation lines start with a marker and, when line numbers are enabled,
+    /// get a blank gutter instead of a new number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().wrap(100).build()?;
+    /// assert_eq!(config.output.wrap_width, Some(100));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn wrap(mut self, width: usize) -> Self {
+        self.wrap = Some(width);
+        self
+    }
+
+    /// Prepends a UTF-8 byte order mark (BOM) to file output if `true`.
+    ///
+    /// Some Windows tools only recognize a file as UTF-8 when it starts with a BOM.
+    /// Output to stdout or the clipboard is never prefixed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().output_file("out.md").bom(true).build()?;
+    /// assert!(config.output.write_bom);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn bom(mut self, bom: bool) -> Self {
+        self.bom = Some(bom);
+        self
+    }
+
+    /// Rewrites every line ending in the output to one style as it is written.
+    ///
+    /// This covers the document's own line breaks and those inside file content,
+    /// so files with mixed endings still produce a consistent document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, Newline};
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().output_newline(Newline::Crlf).build()?;
+    /// assert_eq!(config.output.output_newline, Some(Newline::Crlf));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn output_newline(mut self, newline: Newline) -> Self {
+        self.output_newline = Some(newline);
+        self
+    }
+
+    /// Sets what to do when the output file already exists.
+    ///
+    /// The default, [`IfExists::Overwrite`], truncates the file. This only applies
+    /// to regular files; special files such as named pipes are always written as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, IfExists};
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .output_file("out.md")
+    ///     .if_exists(IfExists::Append)
+    ///     .build()?;
+    /// assert_eq!(config.output.if_exists, IfExists::Append);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn if_exists(mut self, if_exists: IfExists) -> Self {
+        self.if_exists = Some(if_exists);
+        self
+    }
+
+    /// Overrides the code fence language for files by extension or filename.
+    ///
+    /// Each entry has the form `KEY=LANG`, e.g., `"h=cpp"` or `"Jenkinsfile=groovy"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().lang_map(vec!["h=cpp".to_string()]).build()?;
+    /// assert_eq!(config.output.language_overrides.get("h").map(String::as_str), Some("cpp"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn lang_map(mut self, pairs: Vec<String>) -> Self {
+        self.lang_map = Some(pairs);
+        self
+    }
+
+    /// Flushes the output after each file block if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().flush_per_file(true).build()?;
+    /// assert!(config.output.flush_per_file);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn flush_per_file(mut self, flush: bool) -> Self {
+        self.flush_per_file = Some(flush);
+        self
+    }
+
+    /// Separates files with a custom delimiter line instead of Markdown headers and fences.
+    ///
+    /// Every occurrence of `{path}` in the pattern is replaced with the file's path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().delimiter("===== FILE: {path} =====").build()?;
+    /// assert_eq!(config.delimiter, Some("===== FILE: {path} =====".to_string()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn delimiter(mut self, pattern: impl Into<String>) -> Self {
+        self.delimiter = Some(pattern.into());
+        self
+    }
+
+    /// Writes files between machine-readable markers that can be split back apart.
+    ///
+    /// See [`RoundtripFormatter`](crate::output::RoundtripFormatter) for the format
+    /// and [`unpack`](crate::output::roundtrip::unpack) for the reverse operation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().roundtrip(true).build()?;
+    /// assert!(config.roundtrip);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn roundtrip(mut self, roundtrip: bool) -> Self {
+        self.roundtrip = Some(roundtrip);
+        self
+    }
+
+    /// Writes a compact index with one line per file instead of content.
+    ///
+    /// See [`IndexFormatter`](crate::output::IndexFormatter) for the format. This
+    /// implies `dry_run`, so the files are listed without being read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().index(true).build()?;
+    /// assert!(config.index);
+    /// assert!(config.dry_run); // Implies dry_run
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn index(mut self, index: bool) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Sets the document format of the output.
+    ///
+    /// [`OutputFormat::Json`] and [`OutputFormat::Xml`] write the files as a
+    /// JSON or XML document; see [`JsonFormatter`](crate::output::JsonFormatter)
+    /// and [`XmlFormatter`](crate::output::XmlFormatter) for their layouts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, OutputFormat};
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().format(OutputFormat::Json).build()?;
+    /// assert_eq!(config.format, OutputFormat::Json);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn format(mut self, format: OutputFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Sets the output file path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, OutputDestination};
+    /// # use dircat::errors::Result;
+    /// # use std::path::PathBuf;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().output_file("output.md").build()?;
+    /// assert_eq!(config.output_destination, OutputDestination::File(PathBuf::from("output.md")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn output_file(mut self, path: impl Into<String>) -> Self {
+        self.output_file = Some(path.into());
+        self
+    }
+
+    /// Sets a template for the output file path, with placeholders filled in at build time.
+    ///
+    /// Supports `{input_name}`, `{date}`, `{index}`, and `{ext}`. See
+    /// [`render_output_template`](crate::output::writer::render_output_template).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, OutputDestination};
+    /// # use dircat::errors::Result;
+    /// # use std::path::PathBuf;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .input_path("https://github.com/user/frontend.git")
+    ///     .output_template("{input_name}.{ext}")
+    ///     .build()?;
+    /// assert_eq!(config.output_destination, OutputDestination::File(PathBuf::from("frontend.md")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn output_template(mut self, template: impl Into<String>) -> Self {
+        self.output_template = Some(template.into());
+        self
+    }
+
+    /// Copies the output to the clipboard if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, OutputDestination};
+    /// # use dircat::errors::Result;
+    /// # #[cfg(feature = "clipboard")]
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().paste(true).build()?;
+    /// assert_eq!(config.output_destination, OutputDestination::Clipboard);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "clipboard")]
+    #[must_use]
+    pub fn paste(mut self, paste: bool) -> Self {
+        self.paste = Some(paste);
+        self
+    }
+
+    /// Appends a summary of processed files if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().summary(true).build()?;
+    /// assert!(config.output.summary);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn summary(mut self, summary: bool) -> Self {
+        self.summary = Some(summary);
+        self
+    }
+
+    /// Outputs only the list of processed file paths if `true`.
+    ///
+    /// No file blocks, summary header, or counts are written. Unlike a dry run,
+    /// the list reflects files that passed processing, such as binary filtering.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().summary_only_paths(true).build()?;
+    /// assert!(config.output.summary_only_paths);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn summary_only_paths(mut self, summary_only_paths: bool) -> Self {
+        self.summary_only_paths = Some(summary_only_paths);
+        self
+    }
+
+    /// Includes file counts in the summary if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().counts(true).build()?;
+    /// assert!(config.output.counts);
+    /// assert!(config.output.summary); // Implies summary
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn counts(mut self, counts: bool) -> Self {
+        self.counts = Some(counts);
+        self
+    }
+
+    /// Shows each file's line, word, and character counts in its header, as
+    /// `## File: path (L:10 W:50 C:300)`, if `true`.
+    ///
+    /// Counts are calculated during processing. Unlike [`counts`](Self::counts),
+    /// this does not imply a summary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().counts_in_header(true).build()?;
+    /// assert!(config.output.counts_in_header);
+    /// assert!(config.processing.counts);
+    /// assert!(!config.output.summary);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn counts_in_header(mut self, counts_in_header: bool) -> Self {
+        self.counts_in_header = Some(counts_in_header);
+        self
+    }
+
+    /// Ends the summary with a section listing the `n` largest files by size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().top_files(5).build()?;
+    /// assert_eq!(config.output.top_files, Some(5));
+    /// assert!(config.output.summary); // Implies summary
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn top_files(mut self, n: usize) -> Self {
+        self.top_files = Some(n);
+        self
+    }
+
+    /// Ends the summary with the groups of files whose original content is identical.
+    ///
+    /// Each text file's content is hashed with SHA-256 while it is read. The output
+    /// itself is not changed, so this shows how much duplication there is before
+    /// anything is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().content_hash_report(true).build()?;
+    /// assert!(config.output.content_hash_report);
+    /// assert!(config.processing.hash_content);
+    /// assert!(config.output.summary); // Implies summary
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn content_hash_report(mut self, report: bool) -> Self {
+        self.content_hash_report = Some(report);
+        self
+    }
+
+    /// Opens the Markdown output with a front page listing the dircat version,
+    /// the input, the date, and the effective configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().front_page(true).build()?;
+    /// let front_page = config.output.front_page.as_deref().unwrap();
+    /// assert!(front_page.contains("output.front_page = true"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn front_page(mut self, front_page: bool) -> Self {
+        self.front_page = Some(front_page);
+        self
+    }
+
+    /// Sets the list of glob patterns for files to be processed last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let patterns = vec!["README.md".to_string()];
+    /// let config = ConfigBuilder::new().process_last(patterns.clone()).build()?;
+    /// assert_eq!(config.discovery.process_last, Some(patterns));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn process_last(mut self, patterns: Vec<String>) -> Self {
+        self.process_last = Some(patterns);
+        self
+    }
+
+    /// Processes only the files matching `process_last` patterns if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().process_last(vec!["*.rs".to_string()]).only_last(true).build()?;
+    /// assert!(config.discovery.only_last);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn only_last(mut self, only_last: bool) -> Self {
+        self.only_last = Some(only_last);
+        self
+    }
+
+    /// Sets how files matching `process_last` patterns are ordered among themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, LastSort};
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .process_last(vec!["*.md".to_string()])
+    ///     .last_sort(LastSort::ByPath)
+    ///     .build()?;
+    /// assert_eq!(config.discovery.last_sort, LastSort::ByPath);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn last_sort(mut self, last_sort: LastSort) -> Self {
+        self.last_sort = Some(last_sort);
+        self
+    }
+
+    /// Sets the list of glob patterns for files to be processed, skipping all others.
+    /// This is a shorthand for `process_last` and `only_last`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let patterns = vec!["*.rs".to_string()];
+    /// let config = ConfigBuilder::new().only(patterns.clone()).build()?;
+    /// assert_eq!(config.discovery.process_last, Some(patterns));
     /// assert!(config.discovery.only_last);
     /// # Ok(())
     /// # }
@@ -804,6 +2564,241 @@ impl ConfigBuilder {
         self
     }
 
+    /// Reads each file listed by a dry run to report its counts if `true`.
+    ///
+    /// The content is counted and discarded, which is cheaper than a full run
+    /// but, unlike a plain dry run, reads every file. Requires `dry_run`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().dry_run(true).dry_run_counts(true).build()?;
+    /// assert!(config.dry_run_counts);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn dry_run_counts(mut self, counts: bool) -> Self {
+        self.dry_run_counts = Some(counts);
+        self
+    }
+
+    /// Guarantees reproducible, byte-identical output if `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().deterministic(true).build()?;
+    /// assert!(config.deterministic);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = Some(deterministic);
+        self
+    }
+
+    /// Produces diff-friendly output that is byte-stable across runs if `true`.
+    ///
+    /// This enables `deterministic` and normalizes `\r\n` and `\r` line endings
+    /// to `\n` before any other content filter runs. It cannot be combined with
+    /// `index_headers`, since adding one file would renumber every header after it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().stable(true).build()?;
+    /// assert!(config.deterministic);
+    /// assert_eq!(
+    ///     config.processing.content_filters[0].name(),
+    ///     "NormalizeLineEndingsFilter"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stable(mut self, stable: bool) -> Self {
+        self.stable = Some(stable);
+        self
+    }
+
+    /// Keeps files in discovery order after processing if `true`.
+    ///
+    /// By default, `execute` sorts its results by path, with `--last` files at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().preserve_input_order(true).build()?;
+    /// assert!(config.preserve_input_order);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn preserve_input_order(mut self, preserve_input_order: bool) -> Self {
+        self.preserve_input_order = Some(preserve_input_order);
+        self
+    }
+
+    /// Keeps only one slice of the sorted results, given as `"I/N"` for the
+    /// `I`-th of `N` slices (1-based).
+    ///
+    /// The slices are contiguous and differ in size by at most one file, so
+    /// running every shard from `1/N` to `N/N` covers each file exactly once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().shard("2/5").build()?;
+    /// assert_eq!(config.shard, Some((2, 5)));
+    ///
+    /// assert!(ConfigBuilder::new().shard("6/5").build().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn shard(mut self, spec: impl Into<String>) -> Self {
+        self.shard = Some(spec.into());
+        self
+    }
+
+    /// Keeps only as many files as fit within `tokens` estimated tokens.
+    ///
+    /// Files are taken in their final sorted order (so `--last` files go last)
+    /// until the next one would exceed the budget; it and every file after it
+    /// are dropped and listed in the summary. Tokens are estimated from the
+    /// processed content, as in [`FileInfo::estimated_tokens`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().token_budget(100_000).build()?;
+    /// assert_eq!(config.token_budget, Some(100_000));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn token_budget(mut self, tokens: usize) -> Self {
+        self.token_budget = Some(tokens);
+        self
+    }
+
+    /// Prints a human-readable report to stderr after `run` if `true`.
+    ///
+    /// The report lists the number of files included, their total bytes and
+    /// lines, an estimated token count, and the elapsed time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new().stats(true).build()?;
+    /// assert!(config.stats);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stats(mut self, stats: bool) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    #[cfg(feature = "archive")]
+    /// Saves the raw content of every processed file to an archive at `path`.
+    ///
+    /// The content is kept as it is read during processing, so files filtered
+    /// out there (such as binaries) are not saved, and a dry run saves nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// # #[cfg(feature = "archive")]
+    /// # {
+    /// let config = ConfigBuilder::new().save_archive("snapshot.json").build()?;
+    /// assert_eq!(config.save_archive, Some("snapshot.json".into()));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn save_archive(mut self, path: impl Into<String>) -> Self {
+        self.save_archive = Some(path.into());
+        self
+    }
+
+    #[cfg(feature = "archive")]
+    /// Loads files from the archive at `path` instead of discovering them on disk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::ConfigBuilder;
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// # #[cfg(feature = "archive")]
+    /// # {
+    /// let config = ConfigBuilder::new().from_archive("snapshot.json").build()?;
+    /// assert_eq!(config.from_archive, Some("snapshot.json".into()));
+    /// # }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn from_archive(mut self, path: impl Into<String>) -> Self {
+        self.from_archive = Some(path.into());
+        self
+    }
+
+    /// Applies a named bundle of options (see [`Preset`]).
+    ///
+    /// The preset is applied immediately, so setters called afterwards override it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use dircat::config::{ConfigBuilder, Preset};
+    /// # use dircat::errors::Result;
+    /// # fn main() -> Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .preset(Preset::Review)
+    ///     .line_numbers(false)
+    ///     .build()?;
+    /// assert!(config.output.counts);
+    /// assert!(!config.output.line_numbers);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn preset(mut self, preset: Preset) -> Self {
+        preset.apply(&mut self);
+        self.preset = Some(preset);
+        self
+    }
+
     /// Builds the final `Config` struct.
     ///
     /// This method performs all necessary setup and validation:
@@ -832,45 +2827,103 @@ impl ConfigBuilder {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn build(self) -> Result<Config> {
+    pub fn build(mut self) -> Result<Config> {
         builder_logic::validate_builder_options(&self)?;
 
-        let content_filters = builder_logic::build_content_filters(
-            self.content_filters,
-            self.remove_comments,
-            self.remove_empty_lines,
-        );
+        let content_filters = builder_logic::build_content_filters(&mut self);
+
+        let ignore_patterns =
+            builder_logic::merge_exclude_files(self.ignore_patterns, self.exclude_from)?;
 
         let (process_last, only_last) =
             builder_logic::determine_process_order(self.only, self.process_last, self.only_last);
 
+        let output_file = match &self.output_template {
+            Some(template) => Some(
+                render_output_template(template, self.input_path.as_deref().unwrap_or("."), 1)
+                    .map_err(Error::from)?
+                    .to_string_lossy()
+                    .into_owned(),
+            ),
+            None => self.output_file,
+        };
         let output_destination = builder_logic::determine_output_destination(
-            self.output_file,
+            output_file,
             #[cfg(feature = "clipboard")]
             self.paste,
         );
 
+        let regex_size_limit = parse_max_size(self.regex_size_limit)
+            .map_err(Error::from)?
+            .map(|limit| usize::try_from(limit).unwrap_or(usize::MAX));
         let discovery_config = DiscoveryConfig {
             max_size: parse_max_size(self.max_size).map_err(Error::from)?,
+            max_files_per_dir: self.max_files_per_dir,
             recursive: !self.no_recursive.unwrap_or(false),
-            extensions: normalize_extensions(self.extensions),
+            max_depth: self.max_depth,
+            extensions: normalize_extensions(
+                expand_languages(self.extensions, self.languages).map_err(Error::from)?,
+            ),
             exclude_extensions: normalize_extensions(self.exclude_extensions),
-            ignore_patterns: self.ignore_patterns,
-            exclude_path_regex: compile_regex_vec(self.exclude_path_regex, "exclude path")
+            extensionless: self.extensionless.unwrap_or_default(),
+            ignore_patterns: expand_brace_patterns(ignore_patterns),
+            exclude_path_regex: compile_regex_vec(
+                self.exclude_path_regex,
+                "exclude path",
+                regex_size_limit,
+            )
+            .map_err(Error::from)?,
+            path_regex: compile_regex_vec(self.path_regex, "path", regex_size_limit)
                 .map_err(Error::from)?,
-            path_regex: compile_regex_vec(self.path_regex, "path").map_err(Error::from)?,
-            filename_regex: compile_regex_vec(self.filename_regex, "filename")
+            filename_regex: compile_regex_vec(self.filename_regex, "filename", regex_size_limit)
                 .map_err(Error::from)?,
             use_gitignore: !self.no_gitignore.unwrap_or(false),
+            ignore_filenames: builder_logic::collect_ignore_filenames(self.ignore_files),
+            use_parent_ignores: !self.no_ignore_parent.unwrap_or(false),
+            ignore_explicit_input: self.ignore_explicit_input.unwrap_or(false),
+            excluded_files: builder_logic::collect_excluded_files(
+                self.excluded_files,
+                &output_destination,
+            ),
+            use_default_excludes: !self.no_default_excludes.unwrap_or(false),
+            include_git_dir: self.include_git_dir.unwrap_or(false),
+            treat_input_as_list: self.treat_input_as_list.unwrap_or(false),
             skip_lockfiles: self.no_lockfiles.unwrap_or(false),
+            skip_secrets: self.skip_secrets.unwrap_or(false),
             process_last,
             only_last,
+            last_sort: self.last_sort.unwrap_or_default(),
+            include_dirs: self.include_dirs.unwrap_or(false),
+            show_symlinks: self.show_symlinks.unwrap_or(false),
+            follow_internal_symlinks: self.follow_internal_symlinks.unwrap_or(false),
+            exclude_empty_dirs: self.exclude_empty_dirs.unwrap_or(false),
+            report_empty_dirs: self.report_empty_dirs.unwrap_or(false),
+            discover_callback: self.discover_callback,
         };
 
         let processing_config = ProcessingConfig {
             include_binary: self.include_binary.unwrap_or(false),
-            counts: self.counts.unwrap_or(false),
+            binary_encoding: self.binary_encoding.unwrap_or_default(),
+            only_binary: self.only_binary.unwrap_or(false),
+            counts: self.counts.unwrap_or(false) || self.counts_in_header.unwrap_or(false),
+            skip_minified: self.skip_minified.unwrap_or(false),
+            skip_generated: self.skip_generated.unwrap_or(false),
+            max_lines: self.max_lines,
+            content_match: compile_regex_vec(self.content_match, "content match", regex_size_limit)
+                .map_err(Error::from)?,
+            context_lines: self.context_lines,
             content_filters,
+            transform_cmd: self.transform_cmd,
+            post_process_callback: self.post_process_callback,
+            outline: self.outline.unwrap_or(false),
+            hash_content: self.content_hash_report.unwrap_or(false),
+            max_file_content_bytes: self.max_file_content_bytes,
+            io_concurrency: self.io_concurrency,
+            trust_extensions: self.trust_extensions.unwrap_or(false),
+            #[cfg(feature = "archive")]
+            keep_raw_content: self.save_archive.is_some(),
+            #[cfg(not(feature = "archive"))]
+            keep_raw_content: false,
         };
 
         let output_config = OutputConfig {
@@ -878,17 +2931,54 @@ impl ConfigBuilder {
             line_numbers: self.line_numbers.unwrap_or(false),
             backticks: self.backticks.unwrap_or(false),
             num_ticks: self.ticks.unwrap_or(3),
-            summary: self.summary.unwrap_or(false) || self.counts.unwrap_or(false),
+            markdown_safe: self.markdown_safe.unwrap_or(false),
+            content_style: self.content_style.unwrap_or_default(),
+            ticks_policy: self.ticks_policy.unwrap_or_default(),
+            toc: self.toc.unwrap_or(false),
+            index_headers: self.index_headers.unwrap_or(false),
+            block_separator: self.block_separator.unwrap_or_else(|| "\n".to_string()),
+            summary: self.summary.unwrap_or(false)
+                || self.counts.unwrap_or(false)
+                || self.top_files.is_some()
+                || self.content_hash_report.unwrap_or(false),
             counts: self.counts.unwrap_or(false),
+            counts_in_header: self.counts_in_header.unwrap_or(false),
+            summary_only_paths: self.summary_only_paths.unwrap_or(false),
+            top_files: self.top_files,
+            content_hash_report: self.content_hash_report.unwrap_or(false),
+            front_page: None,
+            flush_per_file: self.flush_per_file.unwrap_or(false),
+            collapsible: self.collapsible.unwrap_or(false),
+            binary_encoding: self.binary_encoding.unwrap_or_default(),
+            anchor_comments: self.anchor_comments.unwrap_or(false),
+            wrap_width: self.wrap,
+            write_bom: self.bom.unwrap_or(false),
+            output_newline: self.output_newline,
+            if_exists: self.if_exists.unwrap_or_default(),
+            language_overrides: parse_language_map(self.lang_map).map_err(Error::from)?,
         };
 
-        let config = Config {
+        let mut config = Config {
             input_path: self.input_path.unwrap_or_else(|| ".".to_string()),
             discovery: discovery_config,
             processing: processing_config,
             output: output_config,
             output_destination,
-            dry_run: self.dry_run.unwrap_or(false),
+            delimiter: self.delimiter,
+            roundtrip: self.roundtrip.unwrap_or(false),
+            index: self.index.unwrap_or(false),
+            format: self.format.unwrap_or_default(),
+            dry_run: self.dry_run.unwrap_or(false) || self.index.unwrap_or(false),
+            dry_run_counts: self.dry_run_counts.unwrap_or(false),
+            deterministic: self.deterministic.unwrap_or(false) || self.stable.unwrap_or(false),
+            preserve_input_order: self.preserve_input_order.unwrap_or(false),
+            shard: parse_shard(self.shard).map_err(Error::from)?,
+            token_budget: self.token_budget,
+            stats: self.stats.unwrap_or(false),
+            #[cfg(feature = "archive")]
+            save_archive: self.save_archive.map(std::path::PathBuf::from),
+            #[cfg(feature = "archive")]
+            from_archive: self.from_archive.map(std::path::PathBuf::from),
             #[cfg(feature = "git")]
             git_branch: self.git_branch,
             #[cfg(feature = "git")]
@@ -901,8 +2991,17 @@ impl ConfigBuilder {
             git_download: self.git_download.unwrap_or(false),
             #[cfg(feature = "git")]
             show_download_path: self.show_download_path.unwrap_or(false),
+            #[cfg(feature = "git")]
+            relative_base: self.relative_base.unwrap_or_default(),
         };
 
+        if self.front_page.unwrap_or(false) {
+            // The page describes the finished config, so mark it as enabled first
+            // for the description to report it.
+            config.output.front_page = Some(String::new());
+            config.output.front_page = Some(render_front_page(&config));
+        }
+
         Ok(config)
     }
 }
@@ -938,6 +3037,13 @@ mod tests {
             .to_string()
             .contains("must be 3 or greater"));
 
+        // Zero wrap width
+        let res_wrap = ConfigBuilder::new().wrap(0).build();
+        assert!(matches!(
+            res_wrap,
+            Err(Error::Config(ConfigError::InvalidValue { .. }))
+        ));
+
         // --only-last without --last
         let res3 = ConfigBuilder::new().only_last(true).build();
         assert!(matches!(
@@ -962,6 +3068,13 @@ mod tests {
             .unwrap_err()
             .to_string()
             .contains("cannot be used simultaneously"));
+
+        // Delimiter without a path placeholder
+        let res5 = ConfigBuilder::new().delimiter("-----").build();
+        assert!(matches!(
+            res5,
+            Err(Error::Config(ConfigError::InvalidValue { .. }))
+        ));
     }
 
     #[test]
@@ -997,6 +3110,42 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_builder_preset_from_cli() -> Result<()> {
+        let cli = Cli::parse_from(["dircat", ".", "--preset", "llm", "-L"]);
+        let config = ConfigBuilder::from_cli(cli).build()?;
+        assert!(config.output.summary);
+        assert!(config.output.line_numbers); // Explicit flag is kept
+        assert!(config.discovery.skip_lockfiles);
+        assert!(config.stats);
+        assert_eq!(config.processing.content_filters.len(), 2);
+
+        let cli_review = Cli::parse_from(["dircat", ".", "--preset", "review"]);
+        let config_review = ConfigBuilder::from_cli(cli_review).build()?;
+        assert!(config_review.output.line_numbers);
+        assert!(config_review.output.counts);
+        assert!(config_review.output.summary);
+        assert!(config_review.output.toc);
+        assert!(config_review.processing.content_filters.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_setters_override_preset() -> Result<()> {
+        let config = ConfigBuilder::new()
+            .preset(Preset::Llm)
+            .remove_comments(false)
+            .summary(false)
+            .build()?;
+        assert!(!config.output.summary);
+        assert_eq!(config.processing.content_filters.len(), 1);
+        assert_eq!(
+            config.processing.content_filters[0].name(),
+            "RemoveEmptyLinesFilter"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_builder_content_filters_from_cli() -> Result<()> {
         // No filters
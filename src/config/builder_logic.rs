@@ -1,13 +1,25 @@
 // src/config/builder_logic.rs
 
 use super::ConfigBuilder;
-use crate::config::OutputDestination;
-use crate::errors::{ConfigError, Result};
-use crate::processing::filters::{ContentFilter, RemoveCommentsFilter, RemoveEmptyLinesFilter};
-use std::path::PathBuf;
+use crate::config::{Extensionless, OutputDestination, OutputFormat};
+use crate::constants::{DEFAULT_IGNORE_FILENAME, LICENSE_HEADER_MARKER};
+use crate::errors::{io_error_with_path, ConfigError, Result};
+use crate::output::delimited::PATH_PLACEHOLDER;
+use crate::processing::filters::{
+    CompactRemoveCommentsFilter, ContentFilter, MaskLongLiteralsFilter, NormalizeLineEndingsFilter,
+    OrganizeImportsFilter, RemoveCommentsFilter, RemoveEmptyLinesFilter, StripLicenseHeaderFilter,
+};
+use std::path::{Path, PathBuf};
 
 /// Validates combinations of options on the `ConfigBuilder`.
 pub(super) fn validate_builder_options(builder: &ConfigBuilder) -> Result<()> {
+    if let Some((variable, reason)) = &builder.invalid_env {
+        return Err(ConfigError::InvalidValue {
+            option: variable.clone(),
+            reason: reason.clone(),
+        }
+        .into());
+    }
     #[cfg(feature = "clipboard")]
     {
         if builder.output_file.is_some() && builder.paste.unwrap_or(false) {
@@ -17,6 +29,51 @@ pub(super) fn validate_builder_options(builder: &ConfigBuilder) -> Result<()> {
             }
             .into());
         }
+        if builder.output_template.is_some() && builder.paste.unwrap_or(false) {
+            return Err(ConfigError::Conflict {
+                option1: "--output-template".to_string(),
+                option2: "--paste".to_string(),
+            }
+            .into());
+        }
+    }
+    if builder.output_file.is_some() && builder.output_template.is_some() {
+        return Err(ConfigError::Conflict {
+            option1: "--output".to_string(),
+            option2: "--output-template".to_string(),
+        }
+        .into());
+    }
+    #[cfg(feature = "archive")]
+    if builder.save_archive.is_some() && builder.from_archive.is_some() {
+        return Err(ConfigError::Conflict {
+            option1: "--save-archive".to_string(),
+            option2: "--from-archive".to_string(),
+        }
+        .into());
+    }
+    if builder.follow_internal_symlinks.unwrap_or(false) && builder.show_symlinks.unwrap_or(false) {
+        return Err(ConfigError::Conflict {
+            option1: "--follow-internal-symlinks".to_string(),
+            option2: "--show-symlinks".to_string(),
+        }
+        .into());
+    }
+    if builder.extensionless == Some(Extensionless::Only) {
+        let other = if builder.extensions.is_some() {
+            Some("--ext")
+        } else if builder.languages.is_some() {
+            Some("--lang")
+        } else {
+            None
+        };
+        if let Some(other) = other {
+            return Err(ConfigError::Conflict {
+                option1: "--only-extensionless".to_string(),
+                option2: other.to_string(),
+            }
+            .into());
+        }
     }
     if builder.ticks.unwrap_or(3) < 3 {
         return Err(ConfigError::InvalidValue {
@@ -25,6 +82,169 @@ pub(super) fn validate_builder_options(builder: &ConfigBuilder) -> Result<()> {
         }
         .into());
     }
+    if builder.max_files_per_dir == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            option: "--max-files-per-dir".to_string(),
+            reason: "must be greater than 0".to_string(),
+        }
+        .into());
+    }
+    if builder
+        .transform_cmd
+        .as_deref()
+        .is_some_and(|cmd| cmd.trim().is_empty())
+    {
+        return Err(ConfigError::InvalidValue {
+            option: "--transform-cmd".to_string(),
+            reason: "must not be empty".to_string(),
+        }
+        .into());
+    }
+    if builder.stable.unwrap_or(false) && builder.index_headers.unwrap_or(false) {
+        return Err(ConfigError::Conflict {
+            option1: "--stable".to_string(),
+            option2: "--index-headers".to_string(),
+        }
+        .into());
+    }
+    if builder.io_concurrency == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            option: "--io-concurrency".to_string(),
+            reason: "must be greater than 0".to_string(),
+        }
+        .into());
+    }
+    if builder.top_files == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            option: "--top".to_string(),
+            reason: "must be greater than 0".to_string(),
+        }
+        .into());
+    }
+    if builder.max_depth == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            option: "--max-depth".to_string(),
+            reason: "must be greater than 0".to_string(),
+        }
+        .into());
+    }
+    if builder.mask_literals == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            option: "--mask-literals".to_string(),
+            reason: "must be greater than 0".to_string(),
+        }
+        .into());
+    }
+    if builder.max_lines == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            option: "--max-lines".to_string(),
+            reason: "must be greater than 0".to_string(),
+        }
+        .into());
+    }
+    if builder.max_file_content_bytes == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            option: "--max-file-bytes".to_string(),
+            reason: "must be greater than 0".to_string(),
+        }
+        .into());
+    }
+    if builder.wrap == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            option: "--wrap".to_string(),
+            reason: "must be greater than 0".to_string(),
+        }
+        .into());
+    }
+    if builder.roundtrip.unwrap_or(false) {
+        if builder.delimiter.is_some() {
+            return Err(ConfigError::Conflict {
+                option1: "--roundtrip".to_string(),
+                option2: "--delimiter".to_string(),
+            }
+            .into());
+        }
+        if builder.only_binary.unwrap_or(false) {
+            return Err(ConfigError::Conflict {
+                option1: "--roundtrip".to_string(),
+                option2: "--only-binary".to_string(),
+            }
+            .into());
+        }
+    }
+    if builder.index.unwrap_or(false) {
+        let other = if builder.roundtrip.unwrap_or(false) {
+            Some("--roundtrip")
+        } else if builder.delimiter.is_some() {
+            Some("--delimiter")
+        } else if builder.only_binary.unwrap_or(false) {
+            Some("--only-binary")
+        } else {
+            None
+        };
+        if let Some(other) = other {
+            return Err(ConfigError::Conflict {
+                option1: "--index".to_string(),
+                option2: other.to_string(),
+            }
+            .into());
+        }
+    }
+    if builder
+        .format
+        .is_some_and(|format| format != OutputFormat::Markdown)
+    {
+        let other = if builder.delimiter.is_some() {
+            Some("--delimiter")
+        } else if builder.roundtrip.unwrap_or(false) {
+            Some("--roundtrip")
+        } else if builder.index.unwrap_or(false) {
+            Some("--index")
+        } else if builder.only_binary.unwrap_or(false) {
+            Some("--only-binary")
+        } else {
+            None
+        };
+        if let Some(other) = other {
+            return Err(ConfigError::Conflict {
+                option1: "--format".to_string(),
+                option2: other.to_string(),
+            }
+            .into());
+        }
+    }
+    if builder.only_binary.unwrap_or(false) && builder.delimiter.is_some() {
+        return Err(ConfigError::Conflict {
+            option1: "--only-binary".to_string(),
+            option2: "--delimiter".to_string(),
+        }
+        .into());
+    }
+    if builder.summary_only_paths.unwrap_or(false) {
+        if builder.delimiter.is_some() {
+            return Err(ConfigError::Conflict {
+                option1: "--summary-only-paths".to_string(),
+                option2: "--delimiter".to_string(),
+            }
+            .into());
+        }
+        if builder.only_binary.unwrap_or(false) {
+            return Err(ConfigError::Conflict {
+                option1: "--summary-only-paths".to_string(),
+                option2: "--only-binary".to_string(),
+            }
+            .into());
+        }
+    }
+    if let Some(delimiter) = &builder.delimiter {
+        if !delimiter.contains(PATH_PLACEHOLDER) {
+            return Err(ConfigError::InvalidValue {
+                option: "--delimiter".to_string(),
+                reason: format!("must contain the '{}' placeholder", PATH_PLACEHOLDER),
+            }
+            .into());
+        }
+    }
     if builder.only_last.unwrap_or(false) && builder.process_last.is_none() {
         return Err(ConfigError::MissingDependency {
             option: "--only-last".to_string(),
@@ -32,6 +252,51 @@ pub(super) fn validate_builder_options(builder: &ConfigBuilder) -> Result<()> {
         }
         .into());
     }
+    if builder.exclude_empty_dirs.unwrap_or(false) && !builder.include_dirs.unwrap_or(false) {
+        return Err(ConfigError::MissingDependency {
+            option: "exclude_empty_dirs".to_string(),
+            required: "include_dirs".to_string(),
+        }
+        .into());
+    }
+    if builder.dry_run_counts.unwrap_or(false) && !builder.dry_run.unwrap_or(false) {
+        return Err(ConfigError::MissingDependency {
+            option: "--dry-run-count".to_string(),
+            required: "--dry-run".to_string(),
+        }
+        .into());
+    }
+    if builder.context_lines.is_some() && builder.content_match.is_none() {
+        return Err(ConfigError::MissingDependency {
+            option: "--context-lines".to_string(),
+            required: "--content-match".to_string(),
+        }
+        .into());
+    }
+    if builder.collapse_comment_spaces.unwrap_or(false) && !builder.remove_comments.unwrap_or(false)
+    {
+        return Err(ConfigError::MissingDependency {
+            option: "--collapse-comment-spaces".to_string(),
+            required: "--remove-comments".to_string(),
+        }
+        .into());
+    }
+    if builder.comment_placeholder.is_some() {
+        if !builder.remove_comments.unwrap_or(false) {
+            return Err(ConfigError::MissingDependency {
+                option: "--comment-placeholder".to_string(),
+                required: "--remove-comments".to_string(),
+            }
+            .into());
+        }
+        if builder.collapse_comment_spaces.unwrap_or(false) {
+            return Err(ConfigError::Conflict {
+                option1: "--comment-placeholder".to_string(),
+                option2: "--collapse-comment-spaces".to_string(),
+            }
+            .into());
+        }
+    }
     if builder.only.is_some()
         && (builder.process_last.is_some() || builder.only_last.unwrap_or(false))
     {
@@ -45,20 +310,84 @@ pub(super) fn validate_builder_options(builder: &ConfigBuilder) -> Result<()> {
 }
 
 /// Constructs the vector of content filters based on builder settings.
-pub(super) fn build_content_filters(
-    mut content_filters: Vec<Box<dyn ContentFilter>>,
-    remove_comments: Option<bool>,
-    remove_empty_lines: Option<bool>,
-) -> Vec<Box<dyn ContentFilter>> {
-    if remove_comments.unwrap_or(false) {
-        content_filters.push(Box::new(RemoveCommentsFilter));
-    }
-    if remove_empty_lines.unwrap_or(false) {
+///
+/// The custom filters are taken from the builder and run before the built-in ones.
+pub(super) fn build_content_filters(builder: &mut ConfigBuilder) -> Vec<Box<dyn ContentFilter>> {
+    let mut content_filters = std::mem::take(&mut builder.content_filters);
+    // For `--stable`, normalize line endings first, so every filter sees `\n` only.
+    if builder.stable.unwrap_or(false) {
+        content_filters.insert(0, Box::new(NormalizeLineEndingsFilter));
+    }
+    // License headers are comments, so strip them before comment removal sees them.
+    if builder.strip_license.unwrap_or(false) {
+        content_filters.push(Box::new(StripLicenseHeaderFilter {
+            marker: builder
+                .license_marker
+                .unwrap_or(false)
+                .then(|| LICENSE_HEADER_MARKER.to_string()),
+        }));
+    }
+    if builder.remove_comments.unwrap_or(false) {
+        if builder.collapse_comment_spaces.unwrap_or(false) {
+            content_filters.push(Box::new(CompactRemoveCommentsFilter));
+        } else {
+            content_filters.push(Box::new(RemoveCommentsFilter {
+                replacement: builder.comment_placeholder.clone(),
+            }));
+        }
+    }
+    // Trailing comments would keep an import line from being recognized, and the
+    // blank lines separating import groups must still be there.
+    if builder.organize_imports.unwrap_or(false) {
+        content_filters.push(Box::new(OrganizeImportsFilter));
+    }
+    if builder.remove_empty_lines.unwrap_or(false) {
         content_filters.push(Box::new(RemoveEmptyLinesFilter));
     }
+    if let Some(min_len) = builder.mask_literals {
+        content_filters.push(Box::new(MaskLongLiteralsFilter { min_len }));
+    }
     content_filters
 }
 
+/// Combines the always-honored `.dircatignore` with user-provided ignore file names.
+///
+/// Duplicates are dropped, keeping the first occurrence.
+pub(super) fn collect_ignore_filenames(ignore_files: Option<Vec<String>>) -> Vec<String> {
+    let mut names = vec![DEFAULT_IGNORE_FILENAME.to_string()];
+    for name in ignore_files.unwrap_or_default() {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Appends the patterns listed in `exclude_files` to the `--ignore` patterns.
+///
+/// Each file lists one pattern per line. Surrounding whitespace is trimmed, and
+/// blank lines and lines starting with `#` are skipped.
+pub(super) fn merge_exclude_files(
+    ignore_patterns: Option<Vec<String>>,
+    exclude_files: Option<Vec<String>>,
+) -> Result<Option<Vec<String>>> {
+    let Some(exclude_files) = exclude_files else {
+        return Ok(ignore_patterns);
+    };
+    let mut patterns = ignore_patterns.unwrap_or_default();
+    for path in exclude_files {
+        let contents = std::fs::read_to_string(&path).map_err(|e| io_error_with_path(e, &path))?;
+        patterns.extend(
+            contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(String::from),
+        );
+    }
+    Ok(Some(patterns))
+}
+
 /// Determines the final `process_last` and `only_last` values, handling the `--only` shorthand.
 pub(super) fn determine_process_order(
     only: Option<Vec<String>>,
@@ -72,6 +401,38 @@ pub(super) fn determine_process_order(
     }
 }
 
+/// Canonicalizes the files to exclude from discovery, adding the output file.
+///
+/// A path that does not exist yet is resolved through its parent directory, so
+/// an output file is excluded on the run that creates it as well as later ones.
+pub(super) fn collect_excluded_files(
+    excluded_files: Option<Vec<PathBuf>>,
+    output_destination: &OutputDestination,
+) -> Vec<PathBuf> {
+    let output_file = match output_destination {
+        OutputDestination::File(path) => Some(path.clone()),
+        _ => None,
+    };
+    excluded_files
+        .unwrap_or_default()
+        .into_iter()
+        .chain(output_file)
+        .filter_map(|path| canonical_file_path(&path))
+        .collect()
+}
+
+fn canonical_file_path(path: &Path) -> Option<PathBuf> {
+    if let Ok(canonical) = path.canonicalize() {
+        return Some(canonical);
+    }
+    let name = path.file_name()?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    parent.canonicalize().ok().map(|parent| parent.join(name))
+}
+
 /// Determines the final output destination.
 pub(super) fn determine_output_destination(
     output_file: Option<String>,
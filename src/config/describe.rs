@@ -0,0 +1,405 @@
+// src/config/describe.rs
+
+//! Renders a human-readable description of a fully-built `Config`.
+
+use super::{Config, OutputDestination};
+use regex::Regex;
+use std::fmt::{Display, Write};
+use std::path::Path;
+
+/// The format used to render a configuration description.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum DescribeFormat {
+    /// One `key = value` line per setting.
+    #[default]
+    Text,
+    /// A flat JSON object mapping each setting's key to its value as a string.
+    Json,
+}
+
+impl Config {
+    /// Returns a human-readable description of the effective configuration.
+    ///
+    /// Every setting is printed as a `key = value` line, including values derived
+    /// while building the config, such as the compiled regexes, the active content
+    /// filters, and the resolved output destination. This helps diagnose why a file
+    /// was included or excluded without enabling trace logging.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dircat::config::ConfigBuilder;
+    /// # fn main() -> dircat::errors::Result<()> {
+    /// let config = ConfigBuilder::new()
+    ///     .extensions(vec!["rs".to_string()])
+    ///     .remove_comments(true)
+    ///     .build()?;
+    ///
+    /// let description = config.describe();
+    /// assert!(description.contains("discovery.extensions = rs"));
+    /// assert!(description.contains("processing.content_filters = RemoveCommentsFilter"));
+    /// assert!(description.contains("output.destination = stdout"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn describe(&self) -> String {
+        self.describe_with(DescribeFormat::Text, None)
+    }
+
+    /// Returns a description of the effective configuration in the given format.
+    ///
+    /// If `resolved_path` is given, it is included as the `resolved_input` setting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dircat::config::{ConfigBuilder, DescribeFormat};
+    /// use std::path::Path;
+    /// # fn main() -> dircat::errors::Result<()> {
+    /// let config = ConfigBuilder::new().build()?;
+    ///
+    /// let json = config.describe_with(DescribeFormat::Json, Some(Path::new("/work")));
+    /// assert!(json.starts_with('{'));
+    /// assert!(json.contains(r#""resolved_input": "/work""#));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn describe_with(&self, format: DescribeFormat, resolved_path: Option<&Path>) -> String {
+        let mut entries = self.describe_entries();
+        if let Some(path) = resolved_path {
+            entries.insert(1, ("resolved_input", path.display().to_string()));
+        }
+        match format {
+            DescribeFormat::Text => entries
+                .into_iter()
+                .map(|(key, value)| format!("{} = {}\n", key, value))
+                .collect(),
+            DescribeFormat::Json => {
+                let mut json = String::from("{\n");
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    let separator = if i + 1 < entries.len() { "," } else { "" };
+                    let _ = writeln!(
+                        json,
+                        "  \"{}\": \"{}\"{}",
+                        key,
+                        escape_json(value),
+                        separator
+                    );
+                }
+                json.push_str("}\n");
+                json
+            }
+        }
+    }
+
+    /// Returns the effective settings as ordered `(key, value)` pairs.
+    pub(crate) fn describe_entries(&self) -> Vec<(&'static str, String)> {
+        let d = &self.discovery;
+        let p = &self.processing;
+        let o = &self.output;
+        #[cfg_attr(not(any(feature = "git", feature = "archive")), allow(unused_mut))]
+        let mut entries = vec![
+            ("input_path", self.input_path.clone()),
+            ("dry_run", self.dry_run.to_string()),
+            ("dry_run_counts", self.dry_run_counts.to_string()),
+            ("deterministic", self.deterministic.to_string()),
+            (
+                "preserve_input_order",
+                self.preserve_input_order.to_string(),
+            ),
+            (
+                "shard",
+                optional(
+                    self.shard
+                        .map(|(index, count)| format!("{}/{}", index, count)),
+                ),
+            ),
+            ("token_budget", optional(self.token_budget)),
+            ("stats", self.stats.to_string()),
+            ("delimiter", optional(self.delimiter.as_ref())),
+            ("roundtrip", self.roundtrip.to_string()),
+            ("index", self.index.to_string()),
+            ("format", format!("{:?}", self.format)),
+            (
+                "discovery.max_size",
+                optional(d.max_size.map(|size| format!("{} bytes", size))),
+            ),
+            ("discovery.max_files_per_dir", optional(d.max_files_per_dir)),
+            ("discovery.recursive", d.recursive.to_string()),
+            ("discovery.max_depth", optional(d.max_depth)),
+            ("discovery.extensions", list(d.extensions.as_deref())),
+            (
+                "discovery.exclude_extensions",
+                list(d.exclude_extensions.as_deref()),
+            ),
+            ("discovery.extensionless", format!("{:?}", d.extensionless)),
+            (
+                "discovery.ignore_patterns",
+                list(d.ignore_patterns.as_deref()),
+            ),
+            ("discovery.path_regex", regexes(d.path_regex.as_deref())),
+            (
+                "discovery.exclude_path_regex",
+                regexes(d.exclude_path_regex.as_deref()),
+            ),
+            (
+                "discovery.filename_regex",
+                regexes(d.filename_regex.as_deref()),
+            ),
+            ("discovery.use_gitignore", d.use_gitignore.to_string()),
+            (
+                "discovery.ignore_filenames",
+                list(Some(&d.ignore_filenames)),
+            ),
+            (
+                "discovery.use_parent_ignores",
+                d.use_parent_ignores.to_string(),
+            ),
+            (
+                "discovery.ignore_explicit_input",
+                d.ignore_explicit_input.to_string(),
+            ),
+            (
+                "discovery.excluded_files",
+                joined(d.excluded_files.iter().map(|p| p.display())),
+            ),
+            (
+                "discovery.use_default_excludes",
+                d.use_default_excludes.to_string(),
+            ),
+            ("discovery.include_git_dir", d.include_git_dir.to_string()),
+            (
+                "discovery.treat_input_as_list",
+                d.treat_input_as_list.to_string(),
+            ),
+            ("discovery.skip_lockfiles", d.skip_lockfiles.to_string()),
+            ("discovery.skip_secrets", d.skip_secrets.to_string()),
+            ("discovery.process_last", list(d.process_last.as_deref())),
+            ("discovery.only_last", d.only_last.to_string()),
+            ("discovery.last_sort", format!("{:?}", d.last_sort)),
+            ("discovery.include_dirs", d.include_dirs.to_string()),
+            ("discovery.show_symlinks", d.show_symlinks.to_string()),
+            (
+                "discovery.follow_internal_symlinks",
+                d.follow_internal_symlinks.to_string(),
+            ),
+            (
+                "discovery.exclude_empty_dirs",
+                d.exclude_empty_dirs.to_string(),
+            ),
+            (
+                "discovery.report_empty_dirs",
+                d.report_empty_dirs.to_string(),
+            ),
+            (
+                "discovery.discover_callback",
+                optional(d.discover_callback.as_ref().map(|_| "set")),
+            ),
+            ("processing.include_binary", p.include_binary.to_string()),
+            (
+                "processing.binary_encoding",
+                format!("{:?}", p.binary_encoding),
+            ),
+            ("processing.only_binary", p.only_binary.to_string()),
+            ("processing.counts", p.counts.to_string()),
+            ("processing.skip_minified", p.skip_minified.to_string()),
+            ("processing.skip_generated", p.skip_generated.to_string()),
+            ("processing.max_lines", optional(p.max_lines)),
+            (
+                "processing.content_match",
+                regexes(p.content_match.as_deref()),
+            ),
+            ("processing.context_lines", optional(p.context_lines)),
+            (
+                "processing.max_file_content_bytes",
+                optional(p.max_file_content_bytes),
+            ),
+            ("processing.io_concurrency", optional(p.io_concurrency)),
+            (
+                "processing.trust_extensions",
+                p.trust_extensions.to_string(),
+            ),
+            (
+                "processing.keep_raw_content",
+                p.keep_raw_content.to_string(),
+            ),
+            (
+                "processing.transform_cmd",
+                optional(p.transform_cmd.as_deref()),
+            ),
+            ("processing.outline", p.outline.to_string()),
+            ("processing.hash_content", p.hash_content.to_string()),
+            (
+                "processing.post_process_callback",
+                optional(p.post_process_callback.as_ref().map(|_| "set")),
+            ),
+            (
+                "processing.content_filters",
+                joined(p.content_filters.iter().map(|f| f.name())),
+            ),
+            ("output.destination", destination(&self.output_destination)),
+            (
+                "output.filename_only_header",
+                o.filename_only_header.to_string(),
+            ),
+            ("output.line_numbers", o.line_numbers.to_string()),
+            ("output.backticks", o.backticks.to_string()),
+            ("output.num_ticks", o.num_ticks.to_string()),
+            ("output.block_separator", format!("{:?}", o.block_separator)),
+            ("output.markdown_safe", o.markdown_safe.to_string()),
+            ("output.content_style", format!("{:?}", o.content_style)),
+            ("output.binary_encoding", format!("{:?}", o.binary_encoding)),
+            ("output.ticks_policy", format!("{:?}", o.ticks_policy)),
+            ("output.toc", o.toc.to_string()),
+            ("output.index_headers", o.index_headers.to_string()),
+            ("output.summary", o.summary.to_string()),
+            ("output.counts", o.counts.to_string()),
+            ("output.counts_in_header", o.counts_in_header.to_string()),
+            (
+                "output.summary_only_paths",
+                o.summary_only_paths.to_string(),
+            ),
+            ("output.top_files", optional(o.top_files)),
+            (
+                "output.content_hash_report",
+                o.content_hash_report.to_string(),
+            ),
+            ("output.front_page", o.front_page.is_some().to_string()),
+            ("output.flush_per_file", o.flush_per_file.to_string()),
+            ("output.collapsible", o.collapsible.to_string()),
+            ("output.anchor_comments", o.anchor_comments.to_string()),
+            ("output.wrap_width", optional(o.wrap_width)),
+            ("output.write_bom", o.write_bom.to_string()),
+            (
+                "output.output_newline",
+                optional(o.output_newline.map(|n| format!("{:?}", n))),
+            ),
+            ("output.if_exists", format!("{:?}", o.if_exists)),
+            ("output.language_overrides", {
+                let mut overrides: Vec<String> = o
+                    .language_overrides
+                    .iter()
+                    .map(|(key, lang)| format!("{}={}", key, lang))
+                    .collect();
+                overrides.sort();
+                joined(overrides.iter())
+            }),
+        ];
+
+        #[cfg(feature = "archive")]
+        entries.extend([
+            (
+                "save_archive",
+                optional(self.save_archive.as_ref().map(|p| p.display())),
+            ),
+            (
+                "from_archive",
+                optional(self.from_archive.as_ref().map(|p| p.display())),
+            ),
+        ]);
+
+        #[cfg(feature = "git")]
+        entries.extend([
+            ("git.branch", optional(self.git_branch.as_ref())),
+            ("git.depth", optional(self.git_depth)),
+            ("git.cache_path", optional(self.git_cache_path.as_ref())),
+            (
+                "git.download_path",
+                optional(self.git_download_path.as_ref()),
+            ),
+            ("git.download", self.git_download.to_string()),
+            (
+                "git.show_download_path",
+                self.show_download_path.to_string(),
+            ),
+            ("git.relative_base", format!("{:?}", self.relative_base)),
+        ]);
+
+        entries
+    }
+}
+
+/// The placeholder shown for settings that are not set.
+const UNSET: &str = "-";
+
+fn optional(value: Option<impl Display>) -> String {
+    value.map_or_else(|| UNSET.to_string(), |v| v.to_string())
+}
+
+fn joined(items: impl Iterator<Item = impl Display>) -> String {
+    let joined = items.map(|i| i.to_string()).collect::<Vec<_>>().join(", ");
+    if joined.is_empty() {
+        UNSET.to_string()
+    } else {
+        joined
+    }
+}
+
+fn list(items: Option<&[String]>) -> String {
+    joined(items.unwrap_or_default().iter())
+}
+
+fn regexes(items: Option<&[Regex]>) -> String {
+    joined(items.unwrap_or_default().iter().map(Regex::as_str))
+}
+
+/// Escapes a string for use inside a JSON string literal.
+pub(crate) fn escape_json(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn destination(destination: &OutputDestination) -> String {
+    match destination {
+        OutputDestination::Stdout => "stdout".to_string(),
+        OutputDestination::File(path) => format!("file {}", path.display()),
+        #[cfg(feature = "clipboard")]
+        OutputDestination::Clipboard => "clipboard".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+
+    #[test]
+    fn test_describe_lists_derived_values() {
+        let config = ConfigBuilder::new()
+            .path_regex(vec!["^src/".to_string(), r"\.rs$".to_string()])
+            .output_file("out.md")
+            .build()
+            .unwrap();
+
+        let description = config.describe();
+        assert!(description.contains("discovery.path_regex = ^src/, \\.rs$\n"));
+        assert!(description.contains("output.destination = file out.md\n"));
+        assert!(description.contains("processing.content_filters = -\n"));
+    }
+
+    #[test]
+    fn test_describe_json_escapes_values() {
+        let config = ConfigBuilder::new()
+            .path_regex(vec![r#"a"b\.rs"#.to_string()])
+            .build()
+            .unwrap();
+
+        let json = config.describe_with(DescribeFormat::Json, None);
+        assert!(json.contains(r#""discovery.path_regex": "a\"b\\.rs","#));
+        assert!(json.trim_end().ends_with('}'));
+        assert!(!json.contains(",\n}"));
+    }
+}
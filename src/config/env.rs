@@ -0,0 +1,211 @@
+// src/config/env.rs
+
+//! Reads a documented set of options from `DIRCAT_*` environment variables.
+//!
+//! The variables sit between the built-in defaults and the command line: a
+//! variable only fills in an option that was not given as a flag.
+//!
+//! | Variable | Option |
+//! |---|---|
+//! | `DIRCAT_EXTENSIONS` | `--ext` |
+//! | `DIRCAT_EXCLUDE_EXTENSIONS` | `--exclude-ext` |
+//! | `DIRCAT_IGNORE` | `--ignore` |
+//! | `DIRCAT_MAX_SIZE` | `--max-size` |
+//! | `DIRCAT_OUTPUT` | `--output` |
+//! | `DIRCAT_REMOVE_COMMENTS` | `--remove-comments` |
+//! | `DIRCAT_REMOVE_EMPTY_LINES` | `--remove-empty-lines` |
+//! | `DIRCAT_NO_GITIGNORE` | `--no-gitignore` |
+//! | `DIRCAT_NO_LOCKFILES` | `--no-lockfiles` |
+//! | `DIRCAT_INCLUDE_BINARY` | `--include-binary` |
+//! | `DIRCAT_LINE_NUMBERS` | `--line-numbers` |
+//! | `DIRCAT_SUMMARY` | `--summary` |
+//!
+//! Lists are comma-separated; commas inside `{...}` brace groups are kept, so
+//! `DIRCAT_IGNORE="*.{lock,log},target/**"` holds two patterns. Switches accept
+//! `1`/`true`/`yes`/`on` and `0`/`false`/`no`/`off`, in any case.
+
+use super::ConfigBuilder;
+
+/// Fills options not already set on `builder` from the variables `var` returns.
+///
+/// An unrecognized switch value is recorded on the builder and reported by `build`.
+pub(super) fn apply(builder: &mut ConfigBuilder, var: impl Fn(&str) -> Option<String>) {
+    list(&var, "DIRCAT_EXTENSIONS", &mut builder.extensions);
+    list(
+        &var,
+        "DIRCAT_EXCLUDE_EXTENSIONS",
+        &mut builder.exclude_extensions,
+    );
+    list(&var, "DIRCAT_IGNORE", &mut builder.ignore_patterns);
+    value(&var, "DIRCAT_MAX_SIZE", &mut builder.max_size);
+    // `--paste` and `--output-template` conflict with `--output`, so they take
+    // precedence over the variable like a flag would.
+    #[cfg(feature = "clipboard")]
+    let paste = builder.paste == Some(true);
+    #[cfg(not(feature = "clipboard"))]
+    let paste = false;
+    if !paste && builder.output_template.is_none() {
+        value(&var, "DIRCAT_OUTPUT", &mut builder.output_file);
+    }
+
+    let invalid = &mut builder.invalid_env;
+    let switches = [
+        ("DIRCAT_REMOVE_COMMENTS", &mut builder.remove_comments),
+        ("DIRCAT_REMOVE_EMPTY_LINES", &mut builder.remove_empty_lines),
+        ("DIRCAT_NO_GITIGNORE", &mut builder.no_gitignore),
+        ("DIRCAT_NO_LOCKFILES", &mut builder.no_lockfiles),
+        ("DIRCAT_INCLUDE_BINARY", &mut builder.include_binary),
+        ("DIRCAT_LINE_NUMBERS", &mut builder.line_numbers),
+        ("DIRCAT_SUMMARY", &mut builder.summary),
+    ];
+    for (name, field) in switches {
+        if field.is_some() {
+            continue;
+        }
+        let Some(raw) = var(name) else { continue };
+        match parse_switch(&raw) {
+            Some(enabled) => *field = Some(enabled),
+            None => {
+                invalid.get_or_insert_with(|| {
+                    (
+                        name.to_string(),
+                        format!("expected true or false, got '{}'", raw),
+                    )
+                });
+            }
+        }
+    }
+}
+
+fn value(var: &impl Fn(&str) -> Option<String>, name: &str, field: &mut Option<String>) {
+    if field.is_none() {
+        *field = var(name).filter(|v| !v.trim().is_empty());
+    }
+}
+
+fn list(var: &impl Fn(&str) -> Option<String>, name: &str, field: &mut Option<Vec<String>>) {
+    if field.is_none() {
+        *field = var(name)
+            .map(|v| split_list(&v))
+            .filter(|items| !items.is_empty());
+    }
+}
+
+fn parse_switch(raw: &str) -> Option<bool> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
+
+/// Splits a comma-separated list, keeping commas that are inside brace groups.
+fn split_list(raw: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in raw.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(current);
+    items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn apply_vars(builder: &mut ConfigBuilder, vars: &[(&str, &str)]) {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        apply(builder, |name| vars.get(name).cloned());
+    }
+
+    #[test]
+    fn test_split_list_keeps_brace_groups() {
+        assert_eq!(
+            split_list("*.{lock,log}, target/** ,,"),
+            vec!["*.{lock,log}", "target/**"]
+        );
+    }
+
+    #[test]
+    fn test_env_fills_unset_options() {
+        let mut builder = ConfigBuilder::new();
+        apply_vars(
+            &mut builder,
+            &[
+                ("DIRCAT_EXTENSIONS", "rs,toml"),
+                ("DIRCAT_REMOVE_COMMENTS", "Yes"),
+                ("DIRCAT_MAX_SIZE", "1M"),
+            ],
+        );
+        assert_eq!(
+            builder.extensions,
+            Some(vec!["rs".to_string(), "toml".to_string()])
+        );
+        assert_eq!(builder.remove_comments, Some(true));
+        assert_eq!(builder.max_size.as_deref(), Some("1M"));
+        assert!(builder.invalid_env.is_none());
+    }
+
+    #[test]
+    fn test_env_does_not_override_flags() {
+        let mut builder = ConfigBuilder::new().extensions(vec!["md".to_string()]);
+        builder.summary = Some(true);
+        apply_vars(
+            &mut builder,
+            &[("DIRCAT_EXTENSIONS", "rs"), ("DIRCAT_SUMMARY", "false")],
+        );
+        assert_eq!(builder.extensions, Some(vec!["md".to_string()]));
+        assert_eq!(builder.summary, Some(true));
+    }
+
+    #[test]
+    fn test_env_keeps_switch_set_to_false() {
+        let mut builder = ConfigBuilder::new().remove_comments(false);
+        apply_vars(&mut builder, &[("DIRCAT_REMOVE_COMMENTS", "1")]);
+        assert_eq!(builder.remove_comments, Some(false));
+    }
+
+    #[test]
+    #[cfg(feature = "clipboard")]
+    fn test_env_output_skipped_with_paste() {
+        let mut builder = ConfigBuilder::new().paste(true);
+        apply_vars(&mut builder, &[("DIRCAT_OUTPUT", "out.md")]);
+        assert!(builder.output_file.is_none());
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn test_env_output_skipped_with_output_template() {
+        let mut builder = ConfigBuilder::new().output_template("{input_name}.md");
+        apply_vars(&mut builder, &[("DIRCAT_OUTPUT", "out.md")]);
+        assert!(builder.output_file.is_none());
+        assert!(builder.build().is_ok());
+    }
+
+    #[test]
+    fn test_env_records_invalid_switch() {
+        let mut builder = ConfigBuilder::new();
+        apply_vars(&mut builder, &[("DIRCAT_LINE_NUMBERS", "maybe")]);
+        let err = builder.build().unwrap_err();
+        assert!(err.to_string().contains("DIRCAT_LINE_NUMBERS"));
+    }
+}
@@ -4,16 +4,201 @@
 //! making them available to the rest of the application in a structured and
 //! type-safe manner.
 
+use crate::constants::DEFAULT_IGNORE_FILENAME;
+use crate::core_types::FileInfo;
 use crate::processing::filters::ContentFilter;
+use crate::progress::ProgressReporter;
 use regex::Regex;
+use std::collections::HashMap;
 use std::fmt;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 pub use builder::ConfigBuilder;
+pub(crate) use describe::escape_json;
+pub use describe::DescribeFormat;
+pub use presets::Preset;
 mod builder;
 mod builder_logic;
+mod describe;
+mod env;
 mod parsing;
 pub mod path_resolve;
+mod presets;
+
+/// How files matching the `--last` patterns are ordered among themselves.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LastSort {
+    /// Group files by the order of the pattern they matched, then sort alphabetically.
+    #[default]
+    ByPatternThenPath,
+    /// Sort all matched files alphabetically, regardless of which pattern they matched.
+    ByPath,
+}
+
+impl LastSort {
+    /// Returns the key used to sort a "last" file under this ordering.
+    pub(crate) fn sort_key(self, file_info: &FileInfo) -> (Option<usize>, PathBuf) {
+        let order = match self {
+            LastSort::ByPatternThenPath => file_info.process_last_order,
+            LastSort::ByPath => None,
+        };
+        (order, file_info.relative_path.clone())
+    }
+}
+
+/// What the relative paths shown in the output are relative to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum RelativeBase {
+    /// The input path (the default).
+    #[default]
+    Input,
+    /// The root of the git working tree containing the input path, if any.
+    #[value(alias = "auto-git-root")]
+    GitRoot,
+}
+
+/// A line ending style for the generated output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Newline {
+    /// `\n` (the default).
+    #[default]
+    Lf,
+    /// `\r\n`.
+    Crlf,
+    /// `\r\n` on Windows and `\n` elsewhere.
+    Native,
+}
+
+impl Newline {
+    /// Resolves `Native` to the current platform's style.
+    pub fn resolve(self) -> Self {
+        match self {
+            Newline::Native if cfg!(windows) => Newline::Crlf,
+            Newline::Native => Newline::Lf,
+            style => style,
+        }
+    }
+}
+
+/// Which files without an extension (e.g., `Makefile`, `.bashrc`) are kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Extensionless {
+    /// Keep them, subject to the other filters (the default).
+    #[default]
+    Include,
+    /// Drop every file without an extension.
+    Exclude,
+    /// Keep only files without an extension.
+    Only,
+}
+
+/// How each file's content is rendered under its header in Markdown output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ContentStyle {
+    /// A fenced code block with a language hint.
+    #[default]
+    CodeFence,
+    /// A blockquote, with every line prefixed by `> `.
+    Blockquote,
+    /// An indented code block, with every line prefixed by four spaces.
+    Indented,
+    /// The content as-is, with no fence or prefix, directly under the header.
+    Raw,
+}
+
+/// How the content of binary files is written when they are included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BinaryEncoding {
+    /// Decoded as UTF-8, with invalid sequences replaced (the default). Lossy.
+    #[default]
+    Lossy,
+    /// Base64-encoded in a code fence labeled `base64`, so the bytes can be restored.
+    Base64,
+}
+
+/// The document format `run` writes the processed files in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Markdown headers and code fences (the default).
+    #[default]
+    Markdown,
+    /// A JSON document, written by [`JsonFormatter`](crate::output::JsonFormatter).
+    Json,
+    /// An XML document, written by [`XmlFormatter`](crate::output::XmlFormatter).
+    Xml,
+}
+
+/// How the width of each file's code fence is chosen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TicksPolicy {
+    /// Every fence uses the configured number of backticks (the default).
+    #[default]
+    Fixed,
+    /// Every fence is widened past the longest backtick run in its file's content.
+    Auto,
+    /// Only files in fence-prone languages, like Markdown, are widened as with `Auto`.
+    PerLang,
+}
+
+/// What to do when the output file already exists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum IfExists {
+    /// Truncate the existing file and replace its contents.
+    #[default]
+    Overwrite,
+    /// Add the output to the end of the existing file.
+    Append,
+    /// Stop with an error, leaving the existing file untouched.
+    Fail,
+    /// Rename the existing file by appending `.bak` to its name, then write a new file.
+    Backup,
+}
+
+/// A callback invoked for each file that passes the discovery filters.
+///
+/// Returning `false` drops the file. Set it with
+/// [`ConfigBuilder::discover_callback`] to add inclusion logic beyond the
+/// built-in filters.
+#[derive(Clone)]
+pub struct DiscoverCallback(pub Arc<dyn Fn(&FileInfo) -> bool + Send + Sync>);
+
+impl DiscoverCallback {
+    /// Returns `true` if the callback keeps `file_info`.
+    pub fn keeps(&self, file_info: &FileInfo) -> bool {
+        (self.0)(file_info)
+    }
+}
+
+impl fmt::Debug for DiscoverCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DiscoverCallback")
+    }
+}
+
+/// A callback that rewrites each text file's content after the built-in content
+/// filters have run.
+///
+/// It receives the file and its filtered content, and returns the content to keep.
+/// Set it with [`ConfigBuilder::post_process_callback`] for one-off
+/// transformations that do not warrant a [`ContentFilter`].
+#[derive(Clone)]
+pub struct PostProcessCallback(pub Arc<PostProcessFn>);
+
+type PostProcessFn = dyn Fn(&FileInfo, String) -> String + Send + Sync;
+
+impl PostProcessCallback {
+    /// Returns the content for `file_info` after applying the callback to `content`.
+    pub fn apply(&self, file_info: &FileInfo, content: String) -> String {
+        (self.0)(file_info, content)
+    }
+}
+
+impl fmt::Debug for PostProcessCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PostProcessCallback")
+    }
+}
 
 /// Configuration options related to file discovery and filtering.
 ///
@@ -44,12 +229,21 @@ pub mod path_resolve;
 pub struct DiscoveryConfig {
     /// Maximum file size in bytes. Files larger than this are skipped.
     pub max_size: Option<u128>,
+    /// If `Some(n)`, at most `n` files are taken from any single directory, keeping
+    /// the first `n` by path. Files matching the `process_last` patterns are exempt.
+    pub max_files_per_dir: Option<usize>,
     /// Whether to recurse into subdirectories.
     pub recursive: bool,
+    /// If `Some(n)`, discovery descends at most `n` levels below the input directory,
+    /// so `Some(1)` keeps only its direct children. Also bounds the GitHub API listing
+    /// of folder URLs.
+    pub max_depth: Option<usize>,
     /// List of file extensions (lowercase) to include. If `Some`, only files with these extensions are processed.
     pub extensions: Option<Vec<String>>,
     /// List of file extensions (lowercase) to exclude. Takes precedence over `extensions`.
     pub exclude_extensions: Option<Vec<String>>,
+    /// Whether files without an extension are kept, dropped, or the only ones kept.
+    pub extensionless: Extensionless,
     /// List of custom ignore patterns (gitignore syntax) provided via the `--ignore` flag.
     pub ignore_patterns: Option<Vec<String>>,
     /// List of compiled regexes to exclude files by relative path. If `Some`, any path matching one of these is skipped.
@@ -60,12 +254,58 @@ pub struct DiscoveryConfig {
     pub filename_regex: Option<Vec<Regex>>,
     /// Whether to respect `.gitignore`, `.ignore`, and other VCS ignore files.
     pub use_gitignore: bool,
+    /// Names of additional gitignore-syntax files honored in each directory,
+    /// such as `.dircatignore` or `.aiignore`. Has no effect when `use_gitignore` is `false`.
+    pub ignore_filenames: Vec<String>,
+    /// Whether ignore files in directories above the input path also apply.
+    /// Parents are searched up to the filesystem root, not just the enclosing repository.
+    /// Has no effect when `use_gitignore` is `false`.
+    pub use_parent_ignores: bool,
+    /// Whether ignore files also apply to a file given directly as the input (or
+    /// listed in a path list). By default a file named explicitly is always
+    /// included. Has no effect when `use_gitignore` is `false`.
+    pub ignore_explicit_input: bool,
+    /// Canonical paths of files that are never included, such as the output file
+    /// and a `--verify` file, so a saved bundle inside the input is not read back.
+    pub excluded_files: Vec<PathBuf>,
+    /// Whether to skip directories in `DEFAULT_EXCLUDED_DIRS` (e.g., `node_modules`, `target`).
+    /// Applies independently of `use_gitignore`.
+    pub use_default_excludes: bool,
+    /// Whether to scan the `.git` directory, which is otherwise skipped unless the
+    /// input path or a `--last`/path regex pattern explicitly refers to it.
+    pub include_git_dir: bool,
+    /// Whether the input path is a file listing the paths to include, one per line,
+    /// rather than content to include. Never inferred from the file name or contents.
+    pub treat_input_as_list: bool,
     /// Whether to skip common lockfiles (e.g., Cargo.lock, package-lock.json).
     pub skip_lockfiles: bool,
+    /// Whether to skip files likely to contain secrets (e.g., `.env`, `*.pem`, `id_rsa`).
+    pub skip_secrets: bool,
     /// List of glob patterns for files to be processed last, in the specified order.
     pub process_last: Option<Vec<String>>,
     /// If `true`, only process files matching the `process_last` patterns.
     pub only_last: bool,
+    /// How files matching the `process_last` patterns are ordered among themselves.
+    pub last_sort: LastSort,
+    /// If `true`, directories (other than the input root) are also yielded by discovery,
+    /// as `FileInfo` entries with `is_dir` set and no content.
+    pub include_dirs: bool,
+    /// If `true`, symbolic links are yielded by discovery (without being followed),
+    /// as `FileInfo` entries with `symlink_target` set and no content.
+    pub show_symlinks: bool,
+    /// If `true`, symbolic links are followed when their target resolves inside the
+    /// input root. Links pointing outside it, and broken links, are skipped.
+    pub follow_internal_symlinks: bool,
+    /// If `true` (and `include_dirs` is set), directories with no included file
+    /// beneath them after filtering are dropped from the results.
+    pub exclude_empty_dirs: bool,
+    /// If `true`, directories with no included file beneath them after filtering
+    /// are collected into `DircatResult::empty_dirs`.
+    pub report_empty_dirs: bool,
+    /// A library hook called for each file that passes the other discovery
+    /// filters; files it rejects are skipped. Directory and symlink entries are
+    /// not passed to it.
+    pub discover_callback: Option<DiscoverCallback>,
 }
 
 /// Configuration options related to processing file content.
@@ -96,10 +336,55 @@ pub struct DiscoveryConfig {
 pub struct ProcessingConfig {
     /// Whether to include files detected as binary/non-text.
     pub include_binary: bool,
+    /// Whether to keep *only* files detected as binary/non-text, listing them instead of their content.
+    pub only_binary: bool,
+    /// How included binary files' content is stored in `processed_content`.
+    pub binary_encoding: BinaryEncoding,
     /// Whether to calculate line, character, and word counts for the summary.
     pub counts: bool,
+    /// Whether to skip text files whose content looks minified.
+    pub skip_minified: bool,
+    /// Whether to skip text files whose first lines carry a generated-code marker
+    /// (see [`generated_marker`](crate::filtering::generated_marker)). Each skipped
+    /// file is logged with its marker at the info level.
+    pub skip_generated: bool,
+    /// If `Some(n)`, text files with more than `n` lines are skipped.
+    pub max_lines: Option<usize>,
+    /// List of compiled regexes matched against file content. If `Some`, the content must match at least one.
+    pub content_match: Option<Vec<Regex>>,
+    /// If `Some(n)`, matched files are condensed to windows of `n` lines around each content match.
+    /// Files left with no matching line once the content filters have run are skipped.
+    pub context_lines: Option<usize>,
     /// A vector of content filters to be applied sequentially to each file's content.
     pub content_filters: Vec<Box<dyn ContentFilter>>,
+    /// A shell command each text file's content is piped through before the content
+    /// filters run. Its stdout replaces the content. Only applies to files read from
+    /// disk, not to content passed to `process_content`.
+    pub transform_cmd: Option<String>,
+    /// A library hook applied to each text file's content after the content filters,
+    /// and before condensing to `context_lines` or truncating. It runs on the Rayon
+    /// pool, so it may be called concurrently for different files. Like
+    /// `transform_cmd`, it only applies to files read from disk.
+    pub post_process_callback: Option<PostProcessCallback>,
+    /// Whether to reduce supported source files to an outline of their declarations
+    /// (see [`outline`](crate::processing::outline)), after the content filters.
+    pub outline: bool,
+    /// Whether to record the SHA-256 hash of each text file's original content in
+    /// `FileInfo::content_hash`, for the duplicate-files report.
+    pub hash_content: bool,
+    /// If `Some(n)`, each text file's processed content is truncated to at most `n` bytes
+    /// (at a UTF-8 character boundary), followed by a note saying it was truncated.
+    pub max_file_content_bytes: Option<usize>,
+    /// If `Some(n)`, at most `n` files are read from disk at the same time, independent
+    /// of the number of processing threads. Useful on slow or networked filesystems.
+    pub io_concurrency: Option<usize>,
+    /// Whether to classify files as binary by extension alone (see
+    /// [`has_binary_extension`](crate::filtering::has_binary_extension)) instead of
+    /// reading their head. Files filtered out on that basis are never opened.
+    pub trust_extensions: bool,
+    /// Whether to keep each processed file's original bytes in
+    /// `FileInfo::raw_content`, so `save_archive` can store what was read.
+    pub keep_raw_content: bool,
 }
 
 // Custom Debug implementation for ProcessingConfig
@@ -107,8 +392,23 @@ impl fmt::Debug for ProcessingConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ProcessingConfig")
             .field("include_binary", &self.include_binary)
+            .field("only_binary", &self.only_binary)
+            .field("binary_encoding", &self.binary_encoding)
             .field("counts", &self.counts)
+            .field("skip_minified", &self.skip_minified)
+            .field("skip_generated", &self.skip_generated)
+            .field("max_lines", &self.max_lines)
+            .field("content_match", &self.content_match)
+            .field("context_lines", &self.context_lines)
             .field("content_filters", &self.content_filters)
+            .field("transform_cmd", &self.transform_cmd)
+            .field("post_process_callback", &self.post_process_callback)
+            .field("outline", &self.outline)
+            .field("hash_content", &self.hash_content)
+            .field("max_file_content_bytes", &self.max_file_content_bytes)
+            .field("io_concurrency", &self.io_concurrency)
+            .field("trust_extensions", &self.trust_extensions)
+            .field("keep_raw_content", &self.keep_raw_content)
             .finish()
     }
 }
@@ -121,6 +421,10 @@ impl fmt::Debug for ProcessingConfig {
 /// An instance of this struct is typically created as part of a `Config` object
 /// via a `ConfigBuilder` rather than being constructed manually.
 ///
+/// `OutputConfig` is `Clone` but, unlike in dircat 1.1, not `Copy`: it holds
+/// owned data such as `block_separator` and `language_overrides`. Clone it
+/// explicitly where a copy used to be made implicitly.
+///
 /// # Examples
 ///
 /// ```
@@ -138,7 +442,7 @@ impl fmt::Debug for ProcessingConfig {
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct OutputConfig {
     /// Whether to display only the filename (basename) in the `## File:` header.
     pub filename_only_header: bool,
@@ -148,28 +452,144 @@ pub struct OutputConfig {
     pub backticks: bool,
     /// The number of backticks to use for Markdown code fences.
     pub num_ticks: u8,
-    /// Whether to print a summary list of processed files at the end. Implied by `counts`.
+    /// Whether to widen code fences past any backtick run in the content and escape
+    /// fence-like lines, so file content can never close its block early.
+    pub markdown_safe: bool,
+    /// How file content is rendered under its header.
+    pub content_style: ContentStyle,
+    /// How the width of each code fence is chosen. `markdown_safe` always widens.
+    pub ticks_policy: TicksPolicy,
+    /// Whether to start the output with a table of contents linking to each file's header.
+    pub toc: bool,
+    /// Whether each file header includes the file's 1-based position in the output (`## File 7: ...`).
+    pub index_headers: bool,
+    /// Written between consecutive file blocks in Markdown output. Defaults to `"\n"`,
+    /// a blank line; an empty string puts the blocks back to back.
+    pub block_separator: String,
+    /// Whether to print a summary list of processed files at the end. Implied by `counts`
+    /// and `top_files`.
     pub summary: bool,
     /// Whether to display line, character, and word counts in the summary.
     pub counts: bool,
+    /// Whether to display each file's line, word, and character counts in its
+    /// `## File:` header, as `(L:10 W:50 C:300)`.
+    pub counts_in_header: bool,
+    /// Whether to output only the summary's list of paths, with no file blocks,
+    /// summary header, or counts.
+    pub summary_only_paths: bool,
+    /// If `Some(n)`, the summary ends with a section listing the `n` largest files by size.
+    pub top_files: Option<usize>,
+    /// Whether to end the summary with groups of files whose original content is
+    /// identical. Implies `summary`.
+    pub content_hash_report: bool,
+    /// If `Some`, the Markdown output opens with this text as a front page.
+    /// `ConfigBuilder::front_page` fills it with the tool version, the input,
+    /// and the effective configuration.
+    pub front_page: Option<String>,
+    /// Whether to flush the writer after each file block, so streaming consumers
+    /// see results as soon as they are written.
+    pub flush_per_file: bool,
+    /// Whether to wrap each file block in a collapsible `<details>` element.
+    pub collapsible: bool,
+    /// How binary files' content was encoded, so base64 blocks can be labeled and
+    /// their original size noted in the header.
+    pub binary_encoding: BinaryEncoding,
+    /// Whether to write a `<!-- dircat:file path="..." size="..." -->` comment before
+    /// each file block, so tools can find blocks without parsing the header.
+    pub anchor_comments: bool,
+    /// If `Some(n)`, content lines longer than `n` characters are hard-wrapped,
+    /// with continuation lines prefixed by a marker and given a blank line-number gutter.
+    pub wrap_width: Option<usize>,
+    /// Whether to prepend a UTF-8 byte order mark when writing to a file.
+    /// Output to stdout or the clipboard is never prefixed.
+    pub write_bom: bool,
+    /// If `Some`, every line ending in the output, including those inside file
+    /// content, is rewritten to this style as it is written.
+    pub output_newline: Option<Newline>,
+    /// What to do when the output file already exists as a regular file.
+    pub if_exists: IfExists,
+    /// User-provided code fence languages, keyed by lowercase file extension or filename.
+    /// These take precedence over the built-in mapping of `FileInfo::language`.
+    pub language_overrides: HashMap<String, String>,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            filename_only_header: false,
+            line_numbers: false,
+            backticks: false,
+            num_ticks: 3,
+            markdown_safe: false,
+            content_style: ContentStyle::default(),
+            ticks_policy: TicksPolicy::default(),
+            toc: false,
+            index_headers: false,
+            block_separator: "\n".to_string(),
+            summary: false,
+            counts: false,
+            counts_in_header: false,
+            summary_only_paths: false,
+            top_files: None,
+            content_hash_report: false,
+            front_page: None,
+            flush_per_file: false,
+            collapsible: false,
+            binary_encoding: BinaryEncoding::Lossy,
+            anchor_comments: false,
+            wrap_width: None,
+            write_bom: false,
+            output_newline: None,
+            if_exists: IfExists::default(),
+            language_overrides: HashMap::new(),
+        }
+    }
 }
 
 impl DiscoveryConfig {
+    /// Returns the deepest level below the input directory that discovery visits,
+    /// combining `max_depth` with `recursive`.
+    pub(crate) fn effective_max_depth(&self) -> Option<usize> {
+        if self.recursive {
+            self.max_depth
+        } else {
+            Some(1)
+        }
+    }
+
     #[doc(hidden)]
     pub fn default_for_test() -> Self {
         Self {
             max_size: None,
+            max_files_per_dir: None,
             recursive: true,
+            max_depth: None,
             extensions: None,
             exclude_extensions: None,
+            extensionless: Extensionless::default(),
             ignore_patterns: None,
             path_regex: None,
             exclude_path_regex: None,
             filename_regex: None,
             use_gitignore: true,
+            ignore_filenames: vec![DEFAULT_IGNORE_FILENAME.to_string()],
+            use_parent_ignores: true,
+            ignore_explicit_input: false,
+            excluded_files: Vec::new(),
+            use_default_excludes: true,
+            include_git_dir: false,
+            treat_input_as_list: false,
             skip_lockfiles: false,
+            skip_secrets: false,
             process_last: None,
             only_last: false,
+            last_sort: LastSort::default(),
+            include_dirs: false,
+            show_symlinks: false,
+            follow_internal_symlinks: false,
+            exclude_empty_dirs: false,
+            report_empty_dirs: false,
+            discover_callback: None,
         }
     }
 }
@@ -248,8 +668,50 @@ pub struct Config {
     pub output: OutputConfig,
     /// Specifies where the final output should be written.
     pub output_destination: OutputDestination,
+    /// If `Some`, files are separated by this delimiter pattern (with `{path}` replaced
+    /// by the file's path) instead of Markdown headers and code fences.
+    pub delimiter: Option<String>,
+    /// If `true`, files are written between machine-readable markers that
+    /// [`unpack`](crate::output::roundtrip::unpack) can split back into files.
+    pub roundtrip: bool,
+    /// If `true`, `run` writes a compact index with one line per file (path, size,
+    /// and language) instead of content. Implies `dry_run`, so files are not read.
+    pub index: bool,
+    /// The document format of the output. [`OutputFormat::Json`] and
+    /// [`OutputFormat::Xml`] write a JSON or XML document instead of Markdown.
+    pub format: OutputFormat,
     /// If `true`, performs a dry run: prints the list of files that would be processed, but not their content.
     pub dry_run: bool,
+    /// If `true` during a dry run, each listed file is read once to fill in
+    /// `FileInfo::counts`; the content itself is discarded.
+    pub dry_run_counts: bool,
+    /// If `true`, guarantees byte-identical output for identical input: time-derived
+    /// fields are omitted and all ordering is stable. The current output contains no
+    /// time-derived fields; features that add them must honor this flag.
+    pub deterministic: bool,
+    /// If `true`, `execute` returns files in discovery order, as recorded in
+    /// `FileInfo::discovery_index`, rather than sorting them by path. This keeps
+    /// the order of a `--treat-as-list` file.
+    pub preserve_input_order: bool,
+    /// If `Some((index, count))`, `execute` keeps only the `index`-th of `count`
+    /// equal slices of its sorted results (1-based), so a large input can be split
+    /// across several runs.
+    pub shard: Option<(usize, usize)>,
+    /// If `Some(n)`, `execute` keeps files in their sorted order only while their
+    /// estimated tokens add up to at most `n`, dropping the file that would exceed
+    /// the budget and every file after it. Applied after `shard`. If the first file
+    /// alone exceeds the budget, it is kept and a warning is logged.
+    pub token_budget: Option<usize>,
+    /// If `true`, `run` prints a human-readable report of files, bytes, lines,
+    /// estimated tokens, and elapsed time to stderr after writing the output.
+    pub stats: bool,
+    #[cfg(feature = "archive")]
+    /// If `Some`, the raw content of every processed file, as read from disk, is
+    /// saved to this archive. Nothing is saved on a dry run.
+    pub save_archive: Option<PathBuf>,
+    #[cfg(feature = "archive")]
+    /// If `Some`, files are loaded from this archive instead of being discovered on disk.
+    pub from_archive: Option<PathBuf>,
     #[cfg(feature = "git")]
     /// For git URL inputs, the specific branch, tag, or commit to check out.
     pub git_branch: Option<String>,
@@ -268,6 +730,42 @@ pub struct Config {
     #[cfg(feature = "git")]
     /// Print the path where the git repository is cached or downloaded and exit.
     pub show_download_path: bool,
+    #[cfg(feature = "git")]
+    /// What file paths in the output are relative to. With `RelativeBase::GitRoot`,
+    /// `execute` rebases them onto the root of the enclosing git working tree.
+    pub relative_base: RelativeBase,
+}
+
+impl Config {
+    /// Resolves this config's input path, cloning or downloading git repositories as needed.
+    ///
+    /// This is a convenience wrapper around [`resolve_input`] that passes the git
+    /// options stored in the config. The GitHub API listing of a folder URL is
+    /// bounded by the discovery depth.
+    ///
+    /// # Errors
+    /// Returns an error if the path does not exist or a repository cannot be fetched.
+    pub fn resolve_input(
+        &self,
+        progress: Option<Arc<dyn ProgressReporter>>,
+    ) -> crate::errors::Result<ResolvedInput> {
+        #[cfg(feature = "git")]
+        {
+            let options = path_resolve::GitResolveOptions {
+                branch: &self.git_branch,
+                depth: self.git_depth,
+                cache_path: &self.git_cache_path,
+                download_path: &self.git_download_path,
+                download: self.git_download,
+                api_max_depth: self.discovery.effective_max_depth(),
+            };
+            path_resolve::resolve_input_with_options(&self.input_path, &options, progress)
+        }
+        #[cfg(not(feature = "git"))]
+        {
+            resolve_input(&self.input_path, &None, None, &None, &None, false, progress)
+        }
+    }
 }
 
 // Custom Debug implementation for Config, as Box<dyn ContentFilter> does not implement Debug.
@@ -280,7 +778,23 @@ impl fmt::Debug for Config {
             .field("processing", &self.processing)
             .field("output", &self.output)
             .field("output_destination", &self.output_destination)
-            .field("dry_run", &self.dry_run);
+            .field("delimiter", &self.delimiter)
+            .field("roundtrip", &self.roundtrip)
+            .field("index", &self.index)
+            .field("dry_run", &self.dry_run)
+            .field("dry_run_counts", &self.dry_run_counts)
+            .field("deterministic", &self.deterministic)
+            .field("preserve_input_order", &self.preserve_input_order)
+            .field("shard", &self.shard)
+            .field("token_budget", &self.token_budget)
+            .field("stats", &self.stats);
+
+        #[cfg(feature = "archive")]
+        {
+            builder
+                .field("save_archive", &self.save_archive)
+                .field("from_archive", &self.from_archive);
+        }
 
         #[cfg(feature = "git")]
         {
@@ -290,7 +804,8 @@ impl fmt::Debug for Config {
                 .field("git_cache_path", &self.git_cache_path)
                 .field("git_download_path", &self.git_download_path)
                 .field("git_download", &self.git_download)
-                .field("show_download_path", &self.show_download_path);
+                .field("show_download_path", &self.show_download_path)
+                .field("relative_base", &self.relative_base);
         }
 
         builder.finish()
@@ -307,33 +822,100 @@ impl Config {
             input_path: ".".to_string(),
             discovery: DiscoveryConfig {
                 max_size: None,
+                max_files_per_dir: None,
                 recursive: true,
+                max_depth: None,
                 extensions: None,
                 exclude_extensions: None,
+                extensionless: Extensionless::default(),
                 ignore_patterns: None,
                 path_regex: None,
                 exclude_path_regex: None,
                 filename_regex: None,
                 use_gitignore: true,
+                ignore_filenames: vec![DEFAULT_IGNORE_FILENAME.to_string()],
+                use_parent_ignores: true,
+                ignore_explicit_input: false,
+                excluded_files: Vec::new(),
+                use_default_excludes: true,
+                include_git_dir: false,
+                treat_input_as_list: false,
                 skip_lockfiles: false,
+                skip_secrets: false,
                 process_last: None,
                 only_last: false,
+                last_sort: LastSort::default(),
+                include_dirs: false,
+                show_symlinks: false,
+                follow_internal_symlinks: false,
+                exclude_empty_dirs: false,
+                report_empty_dirs: false,
+                discover_callback: None,
             },
             processing: ProcessingConfig {
                 include_binary: false,
+                only_binary: false,
+                binary_encoding: BinaryEncoding::Lossy,
                 counts: false,
+                skip_minified: false,
+                skip_generated: false,
+                max_lines: None,
+                content_match: None,
+                context_lines: None,
                 content_filters: Vec::new(),
+                transform_cmd: None,
+                post_process_callback: None,
+                outline: false,
+                hash_content: false,
+                max_file_content_bytes: None,
+                io_concurrency: None,
+                trust_extensions: false,
+                keep_raw_content: false,
             },
             output: OutputConfig {
                 filename_only_header: false,
                 line_numbers: false,
                 backticks: false,
                 num_ticks: 3,
+                markdown_safe: false,
+                content_style: ContentStyle::default(),
+                ticks_policy: TicksPolicy::default(),
+                toc: false,
+                index_headers: false,
+                block_separator: "\n".to_string(),
                 summary: false,
                 counts: false,
+                counts_in_header: false,
+                summary_only_paths: false,
+                top_files: None,
+                content_hash_report: false,
+                front_page: None,
+                flush_per_file: false,
+                collapsible: false,
+                binary_encoding: BinaryEncoding::Lossy,
+                anchor_comments: false,
+                wrap_width: None,
+                write_bom: false,
+                output_newline: None,
+                if_exists: IfExists::default(),
+                language_overrides: HashMap::new(),
             },
             output_destination: OutputDestination::Stdout,
+            delimiter: None,
+            roundtrip: false,
+            index: false,
+            format: OutputFormat::Markdown,
             dry_run: false,
+            dry_run_counts: false,
+            deterministic: false,
+            preserve_input_order: false,
+            shard: None,
+            token_budget: None,
+            stats: false,
+            #[cfg(feature = "archive")]
+            save_archive: None,
+            #[cfg(feature = "archive")]
+            from_archive: None,
             #[cfg(feature = "git")]
             git_branch: None,
             #[cfg(feature = "git")]
@@ -346,6 +928,8 @@ impl Config {
             git_download: false,
             #[cfg(feature = "git")]
             show_download_path: false,
+            #[cfg(feature = "git")]
+            relative_base: RelativeBase::default(),
         }
     }
 }
@@ -1,9 +1,12 @@
 // src/config/parsing.rs
 
+use crate::core_types::{known_languages, language_extensions};
 use crate::errors::ConfigError;
+use crate::filtering::expand_braces;
 use anyhow::Result;
 use byte_unit::Byte;
 use regex::Regex;
+use std::collections::HashMap;
 use std::str::FromStr; // Import the FromStr trait
 
 /// Parses the optional max size string into Option<u128>.
@@ -19,37 +22,183 @@ pub(super) fn parse_max_size(max_size_str: Option<String>) -> Result<Option<u128
 }
 
 /// Compiles a vector of pattern strings into a vector of Regex objects.
+///
+/// Matching always runs in linear time, but a pattern with large bounded
+/// repetitions can compile to a huge program. If `size_limit` is set, patterns
+/// whose compiled form exceeds that many bytes are rejected instead of being
+/// allowed to exhaust memory.
 pub(super) fn compile_regex_vec(
     patterns: Option<Vec<String>>,
     name: &str,
+    size_limit: Option<usize>,
 ) -> Result<Option<Vec<Regex>>, ConfigError> {
     patterns
         .map(|vec| {
             vec.into_iter()
                 .map(|p| {
-                    regex::RegexBuilder::new(&p)
-                        .case_insensitive(true) // Make regex case-insensitive as per docs
-                        .build()
-                        .map_err(|e| ConfigError::InvalidRegex {
-                            name: name.to_string(),
-                            pattern: p.clone(),
-                            source: e,
-                        })
+                    let mut builder = regex::RegexBuilder::new(&p);
+                    builder.case_insensitive(true); // Make regex case-insensitive as per docs
+                    if let Some(limit) = size_limit {
+                        builder.size_limit(limit);
+                    }
+                    builder.build().map_err(|e| ConfigError::InvalidRegex {
+                        name: name.to_string(),
+                        pattern: p.clone(),
+                        source: e,
+                    })
                 })
                 .collect::<Result<Vec<_>, _>>()
         })
         .transpose()
 }
 
+/// Expands brace groups (`*.{rs,toml}`) in a vector of glob patterns.
+pub(super) fn expand_brace_patterns(patterns: Option<Vec<String>>) -> Option<Vec<String>> {
+    patterns.map(|v| v.iter().flat_map(|p| expand_braces(p)).collect())
+}
+
 /// Normalizes a vector of extension strings to lowercase.
 pub(super) fn normalize_extensions(exts: Option<Vec<String>>) -> Option<Vec<String>> {
     exts.map(|v| v.into_iter().map(|s| s.to_lowercase()).collect())
 }
 
+/// Adds the extensions of each `--lang` language to the `--ext` list.
+///
+/// Returns `None` only if neither option was given. Extensions already in the
+/// list are not repeated.
+pub(super) fn expand_languages(
+    extensions: Option<Vec<String>>,
+    languages: Option<Vec<String>>,
+) -> Result<Option<Vec<String>>, ConfigError> {
+    let Some(languages) = languages else {
+        return Ok(extensions);
+    };
+    let mut extensions = extensions.unwrap_or_default();
+    for name in languages {
+        let exts = language_extensions(&name).ok_or_else(|| ConfigError::InvalidValue {
+            option: "--lang".to_string(),
+            reason: format!(
+                "unknown language '{}' (known: {})",
+                name,
+                known_languages().collect::<Vec<_>>().join(", ")
+            ),
+        })?;
+        for ext in exts {
+            if !extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)) {
+                extensions.push(ext.to_string());
+            }
+        }
+    }
+    Ok(Some(extensions))
+}
+
+/// Parses `KEY=LANG` pairs into a map of code fence language overrides.
+///
+/// Keys are file extensions (a leading `.` is optional) or full filenames, and are
+/// normalized to lowercase.
+pub(super) fn parse_language_map(
+    pairs: Option<Vec<String>>,
+) -> Result<HashMap<String, String>, ConfigError> {
+    let mut map = HashMap::new();
+    for pair in pairs.unwrap_or_default() {
+        let (key, lang) = pair
+            .split_once('=')
+            .map(|(k, l)| (k.trim(), l.trim()))
+            .filter(|(k, l)| !k.is_empty() && !l.is_empty())
+            .ok_or_else(|| ConfigError::InvalidValue {
+                option: "--lang-map".to_string(),
+                reason: format!("expected KEY=LANG, got '{}'", pair),
+            })?;
+        let key = key.strip_prefix('.').unwrap_or(key);
+        map.insert(key.to_lowercase(), lang.to_string());
+    }
+    Ok(map)
+}
+
+/// Parses a `--shard I/N` spec into a 1-based shard index and a shard count.
+pub(super) fn parse_shard(spec: Option<String>) -> Result<Option<(usize, usize)>, ConfigError> {
+    spec.map(|spec| {
+        spec.split_once('/')
+            .and_then(|(index, count)| {
+                Some((index.trim().parse().ok()?, count.trim().parse().ok()?))
+            })
+            .filter(|&(index, count)| index >= 1 && index <= count)
+            .ok_or_else(|| ConfigError::InvalidValue {
+                option: "--shard".to_string(),
+                reason: format!("expected I/N with 1 <= I <= N, got '{}'", spec),
+            })
+    })
+    .transpose()
+}
+
+/// Expands the `\n`, `\t` and `\\` escapes in a separator given on the command line.
+///
+/// Any other backslash is kept as-is.
+pub(super) fn unescape_separator(separator: &str) -> String {
+    let mut unescaped = String::with_capacity(separator.len());
+    let mut chars = separator.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('\\') => unescaped.push('\\'),
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_unescape_separator() {
+        assert_eq!(unescape_separator(""), "");
+        assert_eq!(unescape_separator("---\\n"), "---\n");
+        assert_eq!(unescape_separator("\\t|\\\\"), "\t|\\");
+        assert_eq!(unescape_separator("a\\qb\\"), "a\\qb\\");
+    }
+
+    #[test]
+    fn test_expand_languages() -> Result<(), ConfigError> {
+        assert_eq!(expand_languages(None, None)?, None);
+        assert_eq!(
+            expand_languages(
+                Some(vec!["RS".to_string(), "md".to_string()]),
+                Some(vec!["Rust".to_string(), "yaml".to_string()])
+            )?,
+            Some(vec![
+                "RS".to_string(),
+                "md".to_string(),
+                "yaml".to_string(),
+                "yml".to_string()
+            ])
+        );
+        let err = expand_languages(None, Some(vec!["klingon".to_string()])).unwrap_err();
+        assert!(err.to_string().contains("unknown language 'klingon'"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_shard() {
+        assert_eq!(parse_shard(None).unwrap(), None);
+        assert_eq!(parse_shard(Some("2/5".to_string())).unwrap(), Some((2, 5)));
+        assert_eq!(parse_shard(Some("1/1".to_string())).unwrap(), Some((1, 1)));
+        for invalid in ["0/5", "6/5", "1/0", "2", "a/b", "-1/3"] {
+            let result = parse_shard(Some(invalid.to_string()));
+            assert!(matches!(result, Err(ConfigError::InvalidValue { .. })));
+        }
+    }
+
     #[test]
     fn test_parse_valid_max_size() -> Result<()> {
         // Use 1000 for 'k' (SI prefix)
@@ -79,17 +228,25 @@ mod tests {
     #[test]
     fn test_compile_valid_regex() -> Result<()> {
         let patterns = Some(vec!["^abc$".to_string(), ".*".to_string()]);
-        let regexes = compile_regex_vec(patterns, "test")?;
+        let regexes = compile_regex_vec(patterns, "test", None)?;
         assert!(regexes.is_some());
         assert_eq!(regexes.unwrap().len(), 2);
-        assert!(compile_regex_vec(None, "test")?.is_none());
+        assert!(compile_regex_vec(None, "test", None)?.is_none());
         Ok(())
     }
 
+    #[test]
+    fn test_compile_regex_over_size_limit() {
+        let patterns = Some(vec!["a{1000}".to_string()]);
+        assert!(compile_regex_vec(patterns.clone(), "test", None).is_ok());
+        let result = compile_regex_vec(patterns, "test", Some(10_000));
+        assert!(matches!(result, Err(ConfigError::InvalidRegex { .. })));
+    }
+
     #[test]
     fn test_compile_invalid_regex() {
         let patterns = Some(vec!["[".to_string()]); // Invalid regex
-        let result = compile_regex_vec(patterns, "test");
+        let result = compile_regex_vec(patterns, "test", None);
         assert!(matches!(result, Err(ConfigError::InvalidRegex { .. })));
         assert!(result
             .unwrap_err()
@@ -104,4 +261,26 @@ mod tests {
         assert_eq!(normalized, Some(vec!["txt".to_string(), "rs".to_string()]));
         assert!(normalize_extensions(None).is_none());
     }
+
+    #[test]
+    fn test_parse_language_map() -> Result<()> {
+        let map = parse_language_map(Some(vec![
+            "h=cpp".to_string(),
+            ".TPL = html".to_string(),
+            "Justfile=make".to_string(),
+        ]))?;
+        assert_eq!(map.get("h").map(String::as_str), Some("cpp"));
+        assert_eq!(map.get("tpl").map(String::as_str), Some("html"));
+        assert_eq!(map.get("justfile").map(String::as_str), Some("make"));
+        assert!(parse_language_map(None)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid_language_map() {
+        for invalid in ["rs", "=rust", "rs="] {
+            let result = parse_language_map(Some(vec![invalid.to_string()]));
+            assert!(matches!(result, Err(ConfigError::InvalidValue { .. })));
+        }
+    }
 }
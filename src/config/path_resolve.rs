@@ -157,6 +157,50 @@ pub fn resolve_input(
     git_download: bool,
     progress: Option<Arc<dyn ProgressReporter>>,
 ) -> Result<ResolvedInput> {
+    let options = GitResolveOptions {
+        branch: git_branch,
+        depth: git_depth,
+        cache_path: git_cache_path_str,
+        download_path: git_download_path_str,
+        download: git_download,
+        api_max_depth: None,
+    };
+    resolve_input_with_options(input_path_str, &options, progress)
+}
+
+/// The git options used to resolve an input path, borrowed from a `Config`.
+#[cfg(feature = "git")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GitResolveOptions<'a> {
+    /// The branch, tag, or commit to check out.
+    pub(crate) branch: &'a Option<String>,
+    /// The depth of a shallow clone, if any.
+    pub(crate) depth: Option<u32>,
+    /// The directory cloned repositories are cached in, instead of the default.
+    pub(crate) cache_path: &'a Option<String>,
+    /// The directory a repository or folder is downloaded into, instead of the cache.
+    pub(crate) download_path: &'a Option<String>,
+    /// Whether to download a whole GitHub repository through the API instead of cloning it.
+    pub(crate) download: bool,
+    /// How many levels below a folder URL the GitHub API listing descends.
+    pub(crate) api_max_depth: Option<usize>,
+}
+
+/// Like [`resolve_input`], with the git options gathered in `options`.
+#[cfg(feature = "git")]
+pub(crate) fn resolve_input_with_options(
+    input_path_str: &str,
+    options: &GitResolveOptions<'_>,
+    progress: Option<Arc<dyn ProgressReporter>>,
+) -> Result<ResolvedInput> {
+    let GitResolveOptions {
+        branch: git_branch,
+        depth: git_depth,
+        cache_path: git_cache_path_str,
+        download_path: git_download_path_str,
+        download: git_download,
+        api_max_depth,
+    } = *options;
     let cache_path = determine_cache_dir(git_cache_path_str.as_deref()).map_err(Error::from)?;
     let download_path = git_download_path_str.as_ref().map(PathBuf::from);
 
@@ -169,6 +213,7 @@ pub fn resolve_input(
             git_branch,
             &cache_path,
             download_path.as_deref(),
+            api_max_depth,
             progress,
         )?
     } else if git_download && git::parse_github_repo_url(input_path_str).is_some() {
@@ -182,6 +227,7 @@ pub fn resolve_input(
             git_branch,
             &cache_path,
             download_path.as_deref(),
+            api_max_depth,
             progress,
         )?
     } else if git::is_git_url(input_path_str) {
@@ -214,6 +260,7 @@ fn handle_github_folder_url(
     cli_branch: &Option<String>,
     cache_path: &Path,
     download_path: Option<&Path>,
+    api_max_depth: Option<usize>,
     progress: Option<Arc<dyn ProgressReporter>>,
 ) -> Result<PathBuf> {
     if download_path.is_none() {
@@ -241,7 +288,12 @@ fn handle_github_folder_url(
     }
 
     // If no valid cache entry, proceed with API download and its own fallback logic.
-    match git::download_directory_via_api(&parsed_url, cli_branch, download_path) {
+    match git::download_directory_via_api_with_max_depth(
+        &parsed_url,
+        cli_branch,
+        download_path,
+        api_max_depth,
+    ) {
         Ok(temp_dir_root) => {
             log::debug!("Successfully downloaded from GitHub API.");
             let path = temp_dir_root.join(&parsed_url.subdirectory);
@@ -0,0 +1,57 @@
+// src/config/presets.rs
+
+use super::ConfigBuilder;
+
+/// A named bundle of commonly combined options.
+///
+/// Presets only ever *enable* options, so any flag given alongside a preset
+/// is still honored. When using the builder programmatically, setters called
+/// after `.preset()` override the values the preset applied.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::config::{ConfigBuilder, Preset};
+/// # fn main() -> dircat::errors::Result<()> {
+/// let config = ConfigBuilder::new().preset(Preset::Llm).build()?;
+///
+/// assert!(config.output.summary);
+/// assert!(config.processing.content_filters.iter().any(|f| f.name() == "RemoveCommentsFilter"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Preset {
+    /// Prepare compact output for an LLM: removes comments and empty lines,
+    /// skips lockfiles, prints a summary, and reports the estimated token count
+    /// on stderr (as `--stats` does).
+    Llm,
+    /// Prepare output for code review: adds line numbers, a summary with counts,
+    /// and a table of contents. There is no directory tree view, so the table of
+    /// contents is the overview of the files.
+    Review,
+}
+
+impl Preset {
+    /// Applies this preset's bundle of options to the given builder.
+    pub(super) fn apply(self, builder: &mut ConfigBuilder) {
+        match self {
+            Preset::Llm => llm(builder),
+            Preset::Review => review(builder),
+        }
+    }
+}
+
+fn llm(builder: &mut ConfigBuilder) {
+    builder.remove_comments = Some(true);
+    builder.remove_empty_lines = Some(true);
+    builder.no_lockfiles = Some(true);
+    builder.summary = Some(true);
+    builder.stats = Some(true);
+}
+
+fn review(builder: &mut ConfigBuilder) {
+    builder.line_numbers = Some(true);
+    builder.counts = Some(true);
+    builder.toc = Some(true);
+}
@@ -6,10 +6,69 @@ pub const OUTPUT_FILE_HEADER: &str = "";
 /// The separator used before the summary section in the output.
 pub const SUMMARY_SEPARATOR: &str = "---";
 
+/// The title of the table of contents written with `--toc`.
+pub const TOC_HEADER: &str = "Table of Contents";
+
+/// The title of the front page written with `--front-page`.
+pub const FRONT_PAGE_HEADER: &str = "dircat bundle";
+
 /// The prefix for the summary header line, which is followed by the file count.
 pub const SUMMARY_HEADER_PREFIX: &str = "Processed Files";
 
+/// The prefix for the header of the largest-files section written with `--top`.
+pub const LARGEST_FILES_HEADER_PREFIX: &str = "Largest Files";
+
+/// The prefix for the header listing the files dropped by `--budget-tokens`.
+pub const OVER_BUDGET_HEADER_PREFIX: &str = "Dropped for Token Budget";
+
+/// The prefix for the header of the duplicate-files section written with `--content-hash-report`.
+pub const DUPLICATE_FILES_HEADER_PREFIX: &str = "Duplicate Files";
+
+/// The first line of the report printed to stderr with `--stats`.
+pub const STATS_HEADER: &str = "--- dircat stats ---";
+
+/// The first line of the report printed to stderr with `--report-empty-dirs`.
+pub const EMPTY_DIRS_HEADER: &str = "--- empty directories ---";
+
+/// The average number of characters per token assumed when `--stats` estimates
+/// a token count. This is a rough rule of thumb for English text and code.
+pub const CHARS_PER_TOKEN_ESTIMATE: usize = 4;
+
+/// The name of the ignore file that is always honored alongside `.gitignore`,
+/// in addition to any names given with `--ignore-file`.
+pub const DEFAULT_IGNORE_FILENAME: &str = ".dircatignore";
+
+/// The environment variable holding the file's path when running `--transform-cmd`.
+pub const TRANSFORM_PATH_ENV_VAR: &str = "DIRCAT_FILE";
+
+/// The line written in place of a removed license header with `--license-marker`.
+pub const LICENSE_HEADER_MARKER: &str = "// license header removed";
+
+/// Directory names skipped during discovery unless `--no-default-excludes` is given.
+///
+/// These are applied whether or not `.gitignore` handling is enabled. The `.git`
+/// directory is handled separately (see `discovery::walker`).
+pub const DEFAULT_EXCLUDED_DIRS: &[&str] = &["node_modules", "target", "__pycache__", ".venv"];
+
 /// The default minimum width for formatting line numbers. The actual width adjusts dynamically.
 pub const DEFAULT_LINE_NUMBER_WIDTH: usize = 5;
 
+/// The character inserted before fence-like content lines when `--markdown-safe` is set.
+/// A zero-width space keeps the line visually unchanged while preventing it from
+/// being parsed as a code fence.
+pub const FENCE_ESCAPE: char = '\u{200B}';
+
+/// Languages, as returned by `FileInfo::language`, whose content often holds its own
+/// code fences. `--ticks-policy per-lang` widens the fences of these files.
+pub const FENCE_PRONE_LANGUAGES: &[&str] = &["md", "markdown", "mdx", "rmd", "qmd"];
+
+/// The marker prefixed to continuation lines when `--wrap` breaks a long line.
+pub const WRAP_CONTINUATION_MARKER: &str = "↪ ";
+
+/// The maximum number of bytes of file content processed concurrently.
+///
+/// Files that would push the in-flight total past this limit are not dropped;
+/// they are deferred and processed one at a time after the parallel pass.
+pub const MAX_GLOBAL_MEM_USAGE: u64 = 1024 * 1024 * 1024;
+
 // Add other constants as needed, e.g., default buffer sizes, etc.
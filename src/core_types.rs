@@ -3,6 +3,7 @@
 //! These structs, `FileInfo` and `FileCounts`, are central to how file data is
 //! discovered, processed, and formatted.
 
+use crate::constants::CHARS_PER_TOKEN_ESTIMATE;
 use std::path::PathBuf;
 
 /// Represents the raw content of a file, ready for processing.
@@ -96,6 +97,220 @@ pub struct FileInfo {
     pub process_last_order: Option<usize>,
     /// A flag indicating if the file was detected as binary during processing.
     pub is_binary: bool,
+    /// A flag indicating if the file's content looks minified (very long lines
+    /// relative to its size). Only set for text files during processing; the
+    /// summary marks such files with `(minified)`.
+    pub is_minified: bool,
+    /// A flag indicating if this entry is a directory rather than a file.
+    ///
+    /// Directories are only yielded when `DiscoveryConfig::include_dirs` is set.
+    /// They have no content and are passed through the processing stage untouched.
+    pub is_dir: bool,
+    /// If this entry is a symbolic link, the path it points to, as stored in the link.
+    ///
+    /// Symlinks are only yielded when `DiscoveryConfig::show_symlinks` is set. Like
+    /// directories, they have no content; they are listed in the summary but not
+    /// given a content block.
+    pub symlink_target: Option<PathBuf>,
+    /// The zero-based position of this entry in discovery order, before `discover`
+    /// sorts normal files by path.
+    ///
+    /// A directory walk is discovered in path order. A `--treat-as-list` input is
+    /// discovered in the order of the list, with each listed directory in path
+    /// order. Files matching `--last` patterns come after all others.
+    ///
+    /// This is `None` for entries that did not come from discovery, such as those
+    /// built by `process_content`. It lets the discovery order be restored after
+    /// parallel processing (see `Config::preserve_input_order`).
+    pub discovery_index: Option<usize>,
+    /// The SHA-256 digest (lowercase hex) of the file's original content.
+    ///
+    /// Only calculated for text files, and only when the duplicate-files report
+    /// (`--content-hash-report`) is requested.
+    pub content_hash: Option<String>,
+    /// The file's original bytes, as read during processing.
+    ///
+    /// Only kept when an archive is being saved (`--save-archive`), and taken
+    /// out again once the archive is written.
+    pub raw_content: Option<Vec<u8>>,
+}
+
+/// Code fence languages for well-known files whose extension (or lack of one)
+/// doesn't identify their language. Keys are lowercase filenames.
+const FILENAME_LANGUAGES: &[(&str, &str)] = &[
+    ("dockerfile", "dockerfile"),
+    ("containerfile", "dockerfile"),
+    ("makefile", "makefile"),
+    ("gnumakefile", "makefile"),
+    ("cmakelists.txt", "cmake"),
+    ("justfile", "just"),
+    ("jenkinsfile", "groovy"),
+    ("gemfile", "ruby"),
+    ("rakefile", "ruby"),
+    ("vagrantfile", "ruby"),
+    ("podfile", "ruby"),
+    (".bashrc", "bash"),
+    (".bash_profile", "bash"),
+    (".bash_aliases", "bash"),
+    (".profile", "sh"),
+    (".zshrc", "zsh"),
+    (".zprofile", "zsh"),
+    (".gitignore", "gitignore"),
+    (".dockerignore", "gitignore"),
+    (".gitattributes", "gitattributes"),
+    (".gitconfig", "ini"),
+    (".editorconfig", "ini"),
+    (".env", "dotenv"),
+];
+
+/// Like `FILENAME_LANGUAGES`, but for filenames that only identify the language
+/// with this exact case, like Bazel's `BUILD` (as opposed to a `build` script).
+const EXACT_FILENAME_LANGUAGES: &[(&str, &str)] = &[
+    ("BUILD", "starlark"),
+    ("BUILD.bazel", "starlark"),
+    ("WORKSPACE", "starlark"),
+];
+
+/// Like `FILENAME_LANGUAGES`, but for variants such as `Dockerfile.dev`.
+const FILENAME_PREFIX_LANGUAGES: &[(&str, &str)] = &[
+    ("dockerfile.", "dockerfile"),
+    ("containerfile.", "dockerfile"),
+    ("makefile.", "makefile"),
+];
+
+/// File extensions for each language name accepted by `--lang`, the reverse of
+/// the extension hints returned by [`FileInfo::language`]. Names are lowercase.
+const LANGUAGE_EXTENSIONS: &[(&str, &[&str])] = &[
+    ("c", &["c", "h"]),
+    ("cpp", &["cpp", "cc", "cxx", "hpp", "hh", "hxx"]),
+    ("csharp", &["cs"]),
+    ("css", &["css", "scss", "sass", "less"]),
+    ("dart", &["dart"]),
+    ("elixir", &["ex", "exs"]),
+    ("go", &["go"]),
+    ("haskell", &["hs"]),
+    ("html", &["html", "htm"]),
+    ("java", &["java"]),
+    ("javascript", &["js", "jsx", "mjs", "cjs"]),
+    ("json", &["json", "jsonc"]),
+    ("kotlin", &["kt", "kts"]),
+    ("lua", &["lua"]),
+    ("markdown", &["md", "markdown", "mdx"]),
+    ("php", &["php"]),
+    ("python", &["py", "pyi", "pyw"]),
+    ("ruby", &["rb", "rake", "gemspec"]),
+    ("rust", &["rs"]),
+    ("scala", &["scala", "sc"]),
+    ("shell", &["sh", "bash", "zsh"]),
+    ("sql", &["sql"]),
+    ("swift", &["swift"]),
+    ("toml", &["toml"]),
+    ("typescript", &["ts", "tsx", "mts", "cts"]),
+    ("yaml", &["yaml", "yml"]),
+    ("zig", &["zig"]),
+];
+
+/// Returns the file extensions for a language name accepted by `--lang`.
+///
+/// The name is matched case-insensitively. Returns `None` for unknown names.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::core_types::language_extensions;
+///
+/// assert_eq!(language_extensions("Rust"), Some(&["rs"][..]));
+/// assert_eq!(language_extensions("yaml"), Some(&["yaml", "yml"][..]));
+/// assert_eq!(language_extensions("klingon"), None);
+/// ```
+pub fn language_extensions(name: &str) -> Option<&'static [&'static str]> {
+    let name = name.to_lowercase();
+    LANGUAGE_EXTENSIONS
+        .iter()
+        .find(|(lang, _)| *lang == name)
+        .map(|(_, exts)| *exts)
+}
+
+/// Returns the language names accepted by `--lang`, in alphabetical order.
+pub fn known_languages() -> impl Iterator<Item = &'static str> {
+    LANGUAGE_EXTENSIONS.iter().map(|(lang, _)| *lang)
+}
+
+impl FileInfo {
+    /// Returns `true` if this entry is a symbolic link rather than a regular file.
+    pub fn is_symlink(&self) -> bool {
+        self.symlink_target.is_some()
+    }
+
+    /// Returns a rough estimate of the tokens this file's content takes up.
+    ///
+    /// Uses the processed content if present, otherwise the size on disk (as in a
+    /// dry run). Directory and symlink entries count as zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dircat::core_types::FileInfo;
+    ///
+    /// let file = FileInfo { processed_content: Some("fn main() {}".to_string()), ..Default::default() };
+    /// assert_eq!(file.estimated_tokens(), 3);
+    /// ```
+    pub fn estimated_tokens(&self) -> usize {
+        if self.is_dir || self.is_symlink() {
+            return 0;
+        }
+        let chars = match &self.processed_content {
+            Some(content) => content.chars().count(),
+            None => self.size as usize,
+        };
+        chars.div_ceil(CHARS_PER_TOKEN_ESTIMATE)
+    }
+
+    /// Returns the language hint used for this file's code fence.
+    ///
+    /// Well-known files without a conventional extension (e.g., `Dockerfile`,
+    /// `Makefile`, `.bashrc`) are matched by filename first, case-insensitively
+    /// except for Bazel's `BUILD`, `BUILD.bazel`, and `WORKSPACE`. Otherwise, the
+    /// file's extension is returned as-is. Returns `None` if neither applies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dircat::core_types::FileInfo;
+    /// use std::path::PathBuf;
+    ///
+    /// let file = |path: &str| FileInfo { relative_path: PathBuf::from(path), ..Default::default() };
+    ///
+    /// assert_eq!(file("docker/Dockerfile").language(), Some("dockerfile"));
+    /// assert_eq!(file("Dockerfile.dev").language(), Some("dockerfile"));
+    /// assert_eq!(file(".bashrc").language(), Some("bash"));
+    /// assert_eq!(file("pkg/BUILD.bazel").language(), Some("starlark"));
+    /// assert_eq!(file("scripts/build").language(), None);
+    /// assert_eq!(file("src/main.rs").language(), Some("rs"));
+    /// assert_eq!(file("LICENSE").language(), None);
+    /// ```
+    pub fn language(&self) -> Option<&str> {
+        if let Some(name) = self.relative_path.file_name() {
+            let name = name.to_string_lossy();
+            if let Some((_, lang)) = EXACT_FILENAME_LANGUAGES
+                .iter()
+                .find(|(file, _)| *file == name)
+            {
+                return Some(lang);
+            }
+            let name = name.to_lowercase();
+            if let Some((_, lang)) = FILENAME_LANGUAGES.iter().find(|(file, _)| *file == name) {
+                return Some(lang);
+            }
+            if let Some((_, lang)) = FILENAME_PREFIX_LANGUAGES
+                .iter()
+                .find(|(prefix, _)| name.starts_with(prefix))
+            {
+                return Some(lang);
+            }
+        }
+        self.relative_path.extension().and_then(|ext| ext.to_str())
+    }
 }
 
 /// Holds line, character (byte), and word counts for a single file.
@@ -5,7 +5,8 @@ use crate::config::DiscoveryConfig;
 use crate::core_types::FileInfo;
 use crate::errors::Error;
 use crate::filtering::{
-    check_process_last, is_file_type, is_lockfile, passes_extension_filters, passes_size_filter,
+    check_process_last, is_file_type, is_likely_secret_file, is_lockfile, passes_extension_filters,
+    passes_size_filter,
 };
 #[cfg(feature = "git")]
 use crate::git;
@@ -15,13 +16,29 @@ use log::{debug, trace, warn};
 use std::path::{Path, PathBuf};
 use tracing::instrument;
 
+/// Returns `true` if `path` is one of `config.excluded_files`.
+///
+/// Only paths whose file name matches an excluded file are canonicalized, so
+/// most entries cost no extra system call.
+fn is_excluded_file(path: &Path, config: &DiscoveryConfig) -> bool {
+    config
+        .excluded_files
+        .iter()
+        .any(|excluded| excluded.file_name() == path.file_name())
+        && path
+            .canonicalize()
+            .is_ok_and(|canonical| config.excluded_files.contains(&canonical))
+}
+
 /// Processes a single directory entry from the walk.
 ///
 /// Performs filtering based on type, size, extensions, gitignore rules (handled by walker),
 /// custom ignore patterns (handled by walker), path regex, filename regex, lockfile status,
 /// and content type (text/binary).
 ///
-/// Returns `Ok(Some(FileInfo))` if the entry is a file that passes all filters.
+/// Returns `Ok(Some(FileInfo))` if the entry is a file that passes all filters, or a
+/// directory (other than the root) when `config.include_dirs` or
+/// `config.report_empty_dirs` is set.
 /// Returns `Ok(None)` if the entry is filtered out or is not a regular file.
 /// Returns `Err(Error)` for critical errors (like permission issues accessing metadata or reading file head).
 pub(crate) fn process_direntry(
@@ -56,17 +73,21 @@ pub(crate) fn process_direntry(
         };
 
         path_from_git_url.unwrap_or_else(|| {
-            // Fallback for local files or if git feature is off
-            absolute_path
-                .file_name()
-                .map(PathBuf::from)
-                .unwrap_or_else(|| {
-                    warn!(
-                        "Could not get filename for file input: {}",
-                        absolute_path.display()
-                    );
-                    absolute_path.clone()
-                })
+            // Fallback for local files or if git feature is off. A symlink given as
+            // the input is named as given, not after the file it resolves to.
+            let input = Path::new(&resolved.display);
+            let named = if input.is_symlink() {
+                input
+            } else {
+                &absolute_path
+            };
+            named.file_name().map(PathBuf::from).unwrap_or_else(|| {
+                warn!(
+                    "Could not get filename for file input: {}",
+                    absolute_path.display()
+                );
+                absolute_path.clone()
+            })
         })
     } else {
         absolute_path
@@ -106,11 +127,68 @@ pub(crate) fn process_direntry(
         }
     };
 
-    // --- 4. Filter by File Type ---
+    // --- 4. Filter by File Type (optionally yielding directories) ---
+    if (config.include_dirs || config.report_empty_dirs) && metadata.is_dir() {
+        if entry.depth() == 0 {
+            trace!("Skipping root directory entry: {}", absolute_path.display());
+            return Ok(None);
+        }
+        trace!("Yielding directory entry: {}", absolute_path.display());
+        return Ok(Some(FileInfo {
+            absolute_path,
+            relative_path,
+            is_process_last: is_last,
+            process_last_order: last_order,
+            is_dir: true,
+            ..Default::default()
+        }));
+    }
+    if config.show_symlinks && metadata.file_type().is_symlink() {
+        if !passes_extension_filters(&absolute_path, config)
+            || !passes_regex_filters(&absolute_path, &relative_path, config)?
+        {
+            debug!(
+                "Skipping symlink due to path filters: {}",
+                absolute_path.display()
+            );
+            return Ok(None);
+        }
+        let target = match std::fs::read_link(&absolute_path) {
+            Ok(target) => target,
+            Err(e) => {
+                warn!(
+                    "Skipping symlink '{}' whose target could not be read: {}",
+                    absolute_path.display(),
+                    e
+                );
+                return Ok(None);
+            }
+        };
+        trace!(
+            "Yielding symlink entry: {} -> {}",
+            absolute_path.display(),
+            target.display()
+        );
+        return Ok(Some(FileInfo {
+            absolute_path,
+            relative_path,
+            is_process_last: is_last,
+            process_last_order: last_order,
+            symlink_target: Some(target),
+            ..Default::default()
+        }));
+    }
     if !is_file_type(&metadata) {
         trace!("Skipping non-file entry: {}", absolute_path.display());
         return Ok(None);
     }
+    if is_excluded_file(&absolute_path, config) {
+        debug!(
+            "Skipping output or verified file: {}",
+            absolute_path.display()
+        );
+        return Ok(None);
+    }
     trace!("Entry is a file: {}", absolute_path.display());
 
     // --- 5. Filter by Lockfile ---
@@ -123,6 +201,15 @@ pub(crate) fn process_direntry(
     }
     trace!("File passed lockfile filter: {}", absolute_path.display());
 
+    // --- 5b. Filter by Secret Files ---
+    if config.skip_secrets && is_likely_secret_file(&absolute_path) {
+        debug!(
+            "Skipping likely secret file due to --skip-secrets flag: {}",
+            absolute_path.display()
+        );
+        return Ok(None);
+    }
+
     // --- 6. Filter by Size ---
     if !passes_size_filter(&metadata, config) {
         debug!(
@@ -164,8 +251,25 @@ pub(crate) fn process_direntry(
         is_process_last: is_last,
         process_last_order: last_order,
         is_binary: false, // Will be determined during the processing stage
+        is_minified: false,
+        is_dir: false,
+        symlink_target: None,
+        discovery_index: None, // Assigned by `discover` once all entries are collected
+        content_hash: None,    // Calculated during processing, if requested
+        raw_content: None,     // Kept during processing, if an archive is saved
     };
 
+    // --- 11. Library Callback ---
+    if let Some(callback) = &config.discover_callback {
+        if !callback.keeps(&file_info) {
+            debug!(
+                "Skipping file rejected by the discover callback: {}",
+                file_info.relative_path.display()
+            );
+            return Ok(None);
+        }
+    }
+
     debug!(
         "Entry passed metadata filters: {}",
         file_info.relative_path.display()
@@ -3,16 +3,18 @@ use crate::cancellation::CancellationToken;
 use crate::config::path_resolve::ResolvedInput;
 use crate::config::DiscoveryConfig;
 use crate::core_types::FileInfo;
-use crate::errors::{Error, Result};
+use crate::errors::{io_error_with_path, ConfigError, Error, Result};
 use crossbeam_channel::unbounded;
 use ignore::WalkState;
-use log::debug;
+use log::{debug, info, warn};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 mod entry_processor;
 mod walker;
 
 use entry_processor::process_direntry;
-use walker::build_walker;
+use walker::{build_walker, is_custom_ignored, is_ignored_input_file};
 
 /// Discovers files based on the provided configuration, applying filters.
 ///
@@ -28,9 +30,10 @@ use walker::build_walker;
 ///
 /// # Returns
 /// A `Result` containing a tuple of two vectors: `(normal_files, last_files)`.
-/// The `last_files` vector is sorted according to the order of the `--last` patterns
-/// and then alphabetically. The `normal_files` vector is explicitly **not sorted**
-/// and its order is non-deterministic due to parallel processing.
+/// The `last_files` vector is sorted according to `config.last_sort`: by default, by
+/// the order of the `--last` patterns and then alphabetically. The `normal_files` vector is explicitly **not sorted**
+/// and its order is non-deterministic due to parallel processing, unless
+/// `config.max_files_per_dir` is set, in which case it is sorted by path.
 ///
 /// # Errors
 /// Returns an `Error` if the operation is interrupted or if building the file walker fails.
@@ -79,12 +82,96 @@ pub fn discover_files(
         return Err(Error::Interrupted);
     }
 
-    let (walker, _temp_file_guard) = build_walker(config, resolved)?;
+    let found = if config.treat_input_as_list {
+        walk_path_list(config, resolved, token)?
+    } else {
+        walk(config, resolved, resolved, token)?
+    };
+
+    for file_info in found {
+        if file_info.is_process_last {
+            last_files.push(file_info);
+        } else if !config.only_last {
+            normal_files.push(file_info);
+        }
+    }
+
+    if let Some(max) = config.max_files_per_dir {
+        normal_files = cap_files_per_dir(normal_files, max);
+    }
+
+    // Sort the "last" files first by the order of the matching -z pattern (unless
+    // `last_sort` ignores it), and then alphabetically by path to ensure deterministic output.
+    // Using a tuple as a key sorts by the first element, then the second for ties.
+    last_files.sort_by_key(|fi| config.last_sort.sort_key(fi));
+
+    debug!(
+        "Discovery complete. Normal files: {}, Last files: {}",
+        normal_files.len(),
+        last_files.len()
+    );
+    Ok((normal_files, last_files))
+}
+
+/// Returns the relative paths of the directory entries in `files` that have no
+/// file (or symlink) entry beneath them, sorted.
+pub(crate) fn find_empty_dirs(files: &[FileInfo]) -> Vec<PathBuf> {
+    let occupied: HashSet<&Path> = files
+        .iter()
+        .filter(|fi| !fi.is_dir)
+        .flat_map(|fi| fi.relative_path.ancestors().skip(1))
+        .collect();
+    let mut empty: Vec<PathBuf> = files
+        .iter()
+        .filter(|fi| fi.is_dir && !occupied.contains(fi.relative_path.as_path()))
+        .map(|fi| fi.relative_path.clone())
+        .collect();
+    empty.sort();
+    empty
+}
+
+/// Walks `walk_input` and returns every entry that passes the filters, sorted by
+/// relative path so the order does not depend on the parallel walk.
+///
+/// Relative paths are computed against `relative_to`, which is the same input
+/// except when walking the entries of a path list.
+fn walk(
+    config: &DiscoveryConfig,
+    walk_input: &ResolvedInput,
+    relative_to: &ResolvedInput,
+    token: &CancellationToken,
+) -> Result<Vec<FileInfo>> {
+    // A file input is matched against -i by the path it is shown under.
+    if walk_input.is_file {
+        let shown_path = if walk_input.path == relative_to.path {
+            walk_input.path.file_name().map(Path::new)
+        } else {
+            walk_input.path.strip_prefix(&relative_to.path).ok()
+        };
+        if shown_path.is_some_and(|p| is_custom_ignored(config, p)) {
+            debug!(
+                "Skipping input file matching a custom ignore pattern: {}",
+                walk_input.path.display()
+            );
+            return Ok(Vec::new());
+        }
+        if config.use_gitignore
+            && config.ignore_explicit_input
+            && is_ignored_input_file(config, &walk_input.path)
+        {
+            debug!(
+                "Skipping input file excluded by an ignore file: {}",
+                walk_input.path.display()
+            );
+            return Ok(Vec::new());
+        }
+    }
+    let (walker, _temp_file_guard) = build_walker(config, walk_input)?;
     let (tx, rx) = unbounded();
 
     // Clone the necessary data to move into the static closure.
     let config_clone = config.clone();
-    let resolved_clone = resolved.clone();
+    let resolved_clone = relative_to.clone();
     let token_clone = token.clone();
 
     walker.run(move || {
@@ -120,23 +207,104 @@ pub fn discover_files(
         return Err(Error::Interrupted);
     }
 
-    for file_info in rx {
-        if file_info.is_process_last {
-            last_files.push(file_info);
-        } else if !config.only_last {
-            normal_files.push(file_info);
+    let mut found: Vec<FileInfo> = rx.into_iter().collect();
+    found.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    Ok(found)
+}
+
+/// Walks each path listed in the input file, for `treat_input_as_list`.
+///
+/// The list holds one path per line; blank lines and lines starting with `#`
+/// are skipped. Relative entries are resolved against the list file's
+/// directory, which is also the base for the reported relative paths. Listed
+/// directories are walked like a normal input. Missing entries are skipped
+/// with a warning, and files listed more than once are only yielded once.
+fn walk_path_list(
+    config: &DiscoveryConfig,
+    resolved: &ResolvedInput,
+    token: &CancellationToken,
+) -> Result<Vec<FileInfo>> {
+    if !resolved.is_file {
+        return Err(ConfigError::InvalidValue {
+            option: "--treat-as-list".to_string(),
+            reason: format!("'{}' is not a file", resolved.display),
         }
+        .into());
     }
+    let contents = std::fs::read_to_string(&resolved.path)
+        .map_err(|e| io_error_with_path(e, &resolved.path))?;
+    let base_dir = resolved
+        .path
+        .parent()
+        .unwrap_or(Path::new(""))
+        .to_path_buf();
+    let base = with_path(resolved, base_dir.clone(), base_dir.display().to_string());
 
-    // Sort the "last" files first by the order of the matching -z pattern,
-    // and then alphabetically by path to ensure deterministic output.
-    // Using a tuple as a key sorts by the first element, then the second for ties.
-    last_files.sort_by_key(|fi| (fi.process_last_order, fi.relative_path.clone()));
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+    let entries = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+    for entry in entries {
+        let path = match base_dir.join(entry).canonicalize() {
+            Ok(path) => path,
+            Err(e) => {
+                warn!("Skipping listed path '{}': {}", entry, e);
+                continue;
+            }
+        };
+        let listed = with_path(resolved, path, entry.to_string());
+        for file_info in walk(config, &listed, &base, token)? {
+            if seen.insert(file_info.absolute_path.clone()) {
+                found.push(file_info);
+            }
+        }
+    }
+    Ok(found)
+}
 
-    debug!(
-        "Discovery complete. Normal files: {}, Last files: {}",
-        normal_files.len(),
-        last_files.len()
-    );
-    Ok((normal_files, last_files))
+/// Returns a copy of `resolved` pointing at another local path.
+fn with_path(resolved: &ResolvedInput, path: PathBuf, display: String) -> ResolvedInput {
+    let mut input = resolved.clone();
+    input.is_file = path.is_file();
+    input.path = path;
+    input.display = display;
+    input
+}
+
+/// Keeps at most `max` files from each directory, choosing the first ones by path.
+///
+/// The choice is made in path order, but the kept files stay in the order they
+/// were given, so a `--treat-as-list` order survives. Directory entries
+/// themselves are never dropped.
+fn cap_files_per_dir(files: Vec<FileInfo>, max: usize) -> Vec<FileInfo> {
+    let mut by_path: Vec<usize> = (0..files.len()).collect();
+    by_path.sort_by(|&a, &b| files[a].relative_path.cmp(&files[b].relative_path));
+    let mut taken: HashMap<&Path, usize> = HashMap::new();
+    let mut keep = vec![true; files.len()];
+    for index in by_path {
+        let file_info = &files[index];
+        if file_info.is_dir {
+            continue;
+        }
+        let parent = file_info.relative_path.parent().unwrap_or(Path::new(""));
+        let count = taken.entry(parent).or_insert(0);
+        *count += 1;
+        keep[index] = *count <= max;
+    }
+    for (dir, count) in taken.iter().filter(|(_, count)| **count > max) {
+        info!(
+            "Directory '{}' has {} files; skipped {} beyond --max-files-per-dir {}",
+            dir.display(),
+            count,
+            count - max,
+            max
+        );
+    }
+    files
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(file_info, keep)| keep.then_some(file_info))
+        .collect()
 }
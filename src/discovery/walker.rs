@@ -1,12 +1,16 @@
 use crate::config::path_resolve::ResolvedInput;
 use crate::config::DiscoveryConfig;
+use crate::constants::DEFAULT_EXCLUDED_DIRS;
+use crate::filtering::expand_braces;
 use anyhow::{Context, Result};
 use glob::Pattern;
-use ignore::{WalkBuilder, WalkParallel};
+use ignore::gitignore::GitignoreBuilder;
+use ignore::{Match, WalkBuilder, WalkParallel};
 use log::debug; // Ensure debug is imported
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::io::Write;
+use std::path::Path;
 use tempfile::NamedTempFile;
 
 static WANTS_GIT_RE: Lazy<Regex> = Lazy::new(|| {
@@ -31,22 +35,54 @@ pub(super) fn build_walker(
         // Explicitly include hidden files (like .github, .env) by default
         walker_builder.hidden(false);
         debug!("Configuring WalkBuilder: standard_filters enabled, hidden files included.");
+        // Set parent traversal explicitly rather than relying on standard_filters.
+        // With require_git disabled below, parents are read up to the filesystem
+        // root, so rules outside the project can apply unless this is turned off.
+        walker_builder.parents(config.use_parent_ignores);
+        debug!(
+            "Configuring WalkBuilder: parent ignore files {}.",
+            if config.use_parent_ignores {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+
+        // Tool-specific ignore files (e.g., .dircatignore, .aiignore) are added
+        // first, so the --last whitelist below still takes precedence over them.
+        for name in &config.ignore_filenames {
+            walker_builder.add_custom_ignore_filename(name);
+            debug!("Added custom ignore filename: {}", name);
+        }
 
-        if let Some(last_patterns) = &config.process_last {
+        // Negated `-i` patterns (`!pattern`) re-include files that ignore files drop.
+        let reincluded: Vec<&str> = config
+            .ignore_patterns
+            .iter()
+            .flatten()
+            .filter_map(|p| p.strip_prefix('!'))
+            .collect();
+        if config.process_last.is_some() || !reincluded.is_empty() {
             // Using OverrideBuilder acts as an inclusion filter, which is not what we want.
             // Instead, we create a temporary, high-precedence ignore file with whitelist
-            // rules (`!pattern`) for the --last patterns. This correctly overrides
-            // .gitignore rules for just those patterns without filtering out other files.
-            let mut file = NamedTempFile::new()
-                .with_context(|| "Failed to create temporary override file for --last patterns")?;
-            for pattern in last_patterns {
+            // rules (`!pattern`) for the --last patterns and the negated -i patterns.
+            // This correctly overrides .gitignore rules for just those patterns without
+            // filtering out other files.
+            let mut file = NamedTempFile::new().with_context(|| {
+                "Failed to create temporary override file for whitelist patterns"
+            })?;
+            let last_patterns = config.process_last.iter().flatten();
+            for pattern in last_patterns
+                .flat_map(|p| expand_braces(p))
+                .chain(reincluded.iter().map(|p| p.to_string()))
+            {
                 // Prepend '!' to make it a whitelist pattern.
                 writeln!(file, "!{}", pattern)
                     .with_context(|| "Failed to write to temporary override file")?;
             }
             walker_builder.add_custom_ignore_filename(file.path());
             debug!(
-                "Added 'process_last' patterns as a custom, high-precedence ignore file: {:?}",
+                "Added whitelist patterns as a custom, high-precedence ignore file: {:?}",
                 file.path()
             );
             // Keep the temp file alive until the walker is built and used.
@@ -68,18 +104,19 @@ pub(super) fn build_walker(
     // Explicitly setting them again might interfere or be unnecessary for ignore 0.4+.
     // ---
 
-    if !config.recursive {
+    if let Some(depth) = config.effective_max_depth() {
         // Max depth 1 means only the immediate children of the base_path
         // If base_path is a file, walkdir handles it correctly (yields just the file)
-        walker_builder.max_depth(Some(1));
-        debug!("Recursion disabled (max depth: 1).");
+        walker_builder.max_depth(Some(depth));
+        debug!("Recursion limited (max depth: {}).", depth);
     } else {
         debug!("Recursion enabled (no max depth).");
     }
 
     // --- Determine if .git should be explicitly traversed ---
-    // Check if the root path itself contains a ".git" component
-    let mut explicitly_wants_git = resolved.path.components().any(|c| c.as_os_str() == ".git");
+    // Check if the user asked for it, or if the root path itself contains a ".git" component
+    let mut explicitly_wants_git =
+        config.include_git_dir || resolved.path.components().any(|c| c.as_os_str() == ".git");
 
     if let Some(lasts) = &config.process_last {
         if lasts.iter().any(|p| WANTS_GIT_RE.is_match(p)) {
@@ -93,37 +130,34 @@ pub(super) fn build_walker(
     }
 
     // --- Compile custom ignore glob patterns from -i ---
-    let mut custom_ignore_globs: Vec<(Pattern, Option<Pattern>)> = Vec::new();
-    if let Some(ignore_patterns) = &config.ignore_patterns {
-        custom_ignore_globs = ignore_patterns
-            .iter()
-            .filter_map(|p| match Pattern::new(p) {
-                Ok(glob) => {
-                    debug!("Compiled custom ignore glob: {}", p);
-                    let is_recursive = !p.contains('/') && !p.contains('\\');
-                    let recursive_glob = if is_recursive {
-                        Pattern::new(&format!("**/{}", p)).ok()
-                    } else {
-                        None
-                    };
-                    Some((glob, recursive_glob))
-                }
-                Err(e) => {
-                    log::warn!("Invalid ignore glob pattern '{}': {}", p, e);
-                    None
-                }
-            })
-            .collect();
-    }
+    let custom_ignore_globs = compile_ignore_globs(config);
+
+    let has_custom_ignores = custom_ignore_globs.iter().any(|g| !g.negated);
+    let use_default_excludes = config.use_default_excludes;
 
-    let has_custom_ignores = !custom_ignore_globs.is_empty();
+    // --- Follow symlinks that stay inside the input root ---
+    let follow_internal_symlinks = config.follow_internal_symlinks;
+    let canonical_root = if follow_internal_symlinks {
+        walker_builder.follow_links(true);
+        debug!("Following symlinks whose targets resolve inside the input root.");
+        resolved
+            .path
+            .canonicalize()
+            .unwrap_or_else(|_| resolved.path.clone())
+    } else {
+        resolved.path.clone()
+    };
 
     // --- Add custom filter entry ---
-    // We add the filter entry if we have custom ignores OR if we need to filter out .git
-    if has_custom_ignores || !explicitly_wants_git {
+    // We add the filter entry if we have custom ignores, default excludes, OR if we need to filter out .git
+    if has_custom_ignores
+        || use_default_excludes
+        || !explicitly_wants_git
+        || follow_internal_symlinks
+    {
         debug!(
-            "Adding custom filter_entry (has_custom_ignores: {}, explicitly_wants_git: {})",
-            has_custom_ignores, explicitly_wants_git
+            "Adding custom filter_entry (has_custom_ignores: {}, use_default_excludes: {}, explicitly_wants_git: {}, follow_internal_symlinks: {})",
+            has_custom_ignores, use_default_excludes, explicitly_wants_git, follow_internal_symlinks
         );
         let input_path_clone = resolved.path.clone();
 
@@ -136,15 +170,26 @@ pub(super) fn build_walker(
                 return false;
             }
 
-            // 2. Custom ignore patterns
+            // 2. Built-in excluded directories, independent of gitignore handling.
+            // The input path itself is never skipped, even if it has one of these names.
+            if use_default_excludes
+                && entry.depth() > 0
+                && entry.file_type().is_some_and(|t| t.is_dir())
+                && DEFAULT_EXCLUDED_DIRS
+                    .iter()
+                    .any(|name| entry.file_name() == *name)
+            {
+                debug!(
+                    "Custom filter_entry skipping default-excluded directory: {:?}",
+                    path
+                );
+                return false;
+            }
+
+            // 3. Custom ignore patterns
             if has_custom_ignores {
                 if let Ok(relative_path) = path.strip_prefix(&input_path_clone) {
-                    if custom_ignore_globs.iter().any(|(glob, rec_glob)| {
-                        glob.matches_path(relative_path)
-                            || rec_glob
-                                .as_ref()
-                                .is_some_and(|g| g.matches_path(relative_path))
-                    }) {
+                    if matches_ignore_globs(&custom_ignore_globs, relative_path) {
                         debug!(
                             "Custom filter_entry skipping {:?} matching custom ignore glob",
                             relative_path
@@ -154,13 +199,149 @@ pub(super) fn build_walker(
                 }
             }
 
+            // 4. Symlinks must resolve inside the input root
+            if follow_internal_symlinks
+                && entry.path_is_symlink()
+                && !is_inside_root(path, &canonical_root)
+            {
+                debug!(
+                    "Custom filter_entry skipping symlink {:?} resolving outside the input root",
+                    path
+                );
+                return false;
+            }
+
             true
         });
     } else {
-        debug!("No custom ignores and .git is explicitly wanted, skipping filter_entry setup.");
+        debug!("No custom ignores or default excludes and .git is explicitly wanted, skipping filter_entry setup.");
     }
 
     // Build the walker
     debug!("Building the final walker.");
     Ok((walker_builder.build_parallel(), temp_override_file))
 }
+
+/// Returns `true` if `path` canonicalizes to a location under `canonical_root`.
+///
+/// Broken links, and paths that cannot be resolved, count as outside.
+fn is_inside_root(path: &Path, canonical_root: &Path) -> bool {
+    path.canonicalize()
+        .is_ok_and(|target| target.starts_with(canonical_root))
+}
+
+/// Returns `true` if `relative_path` matches any custom ignore pattern from `-i`.
+///
+/// The walker never applies `filter_entry` to its root, so this is used to
+/// check a file given as the input itself.
+pub(super) fn is_custom_ignored(config: &DiscoveryConfig, relative_path: &Path) -> bool {
+    matches_ignore_globs(&compile_ignore_globs(config), relative_path)
+}
+
+/// Returns `true` if the ignore files around `path` exclude it, for a file given
+/// as the input itself.
+///
+/// The walker yields its root unfiltered, so this reads the ignore files the walk
+/// would have seen: those in the file's own directory and, when parent ignores are
+/// enabled, in every ancestor. The nearest directory with a matching rule decides,
+/// and within a directory `.ignore` and custom ignore files take precedence over
+/// `.gitignore`.
+pub(super) fn is_ignored_input_file(config: &DiscoveryConfig, path: &Path) -> bool {
+    let mut dirs = path.ancestors().skip(1);
+    let dirs: Vec<&Path> = if config.use_parent_ignores {
+        dirs.collect()
+    } else {
+        dirs.next().into_iter().collect()
+    };
+    let names = [".gitignore", ".ignore"]
+        .into_iter()
+        .chain(config.ignore_filenames.iter().map(String::as_str));
+    for dir in dirs {
+        let mut builder = GitignoreBuilder::new(dir);
+        for name in names.clone() {
+            let file = dir.join(name);
+            if file.is_file() {
+                if let Some(e) = builder.add(&file) {
+                    log::warn!("Invalid rule in '{}': {}", file.display(), e);
+                }
+            }
+        }
+        let matcher = match builder.build() {
+            Ok(matcher) if !matcher.is_empty() => matcher,
+            _ => continue,
+        };
+        match matcher.matched_path_or_any_parents(path, false) {
+            Match::Ignore(_) => return true,
+            Match::Whitelist(_) => return false,
+            Match::None => {}
+        }
+    }
+    false
+}
+
+/// A compiled `-i` pattern.
+struct IgnoreGlob {
+    glob: Pattern,
+    /// A `**/` variant for patterns without a separator, so they match at any depth.
+    recursive: Option<Pattern>,
+    /// Whether the pattern was written as `!pattern`, re-including matches.
+    negated: bool,
+}
+
+impl IgnoreGlob {
+    fn matches(&self, relative_path: &Path) -> bool {
+        self.glob.matches_path(relative_path)
+            || self
+                .recursive
+                .as_ref()
+                .is_some_and(|g| g.matches_path(relative_path))
+    }
+}
+
+/// Compiles the `-i` patterns, keeping their order.
+fn compile_ignore_globs(config: &DiscoveryConfig) -> Vec<IgnoreGlob> {
+    let Some(ignore_patterns) = &config.ignore_patterns else {
+        return Vec::new();
+    };
+    ignore_patterns
+        .iter()
+        .filter_map(|p| {
+            let (negated, pattern) = match p.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, p.as_str()),
+            };
+            match Pattern::new(pattern) {
+                Ok(glob) => {
+                    debug!("Compiled custom ignore glob: {}", p);
+                    let is_recursive = !pattern.contains('/') && !pattern.contains('\\');
+                    let recursive = if is_recursive {
+                        Pattern::new(&format!("**/{}", pattern)).ok()
+                    } else {
+                        None
+                    };
+                    Some(IgnoreGlob {
+                        glob,
+                        recursive,
+                        negated,
+                    })
+                }
+                Err(e) => {
+                    log::warn!("Invalid ignore glob pattern '{}': {}", p, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Returns `true` if `relative_path` is ignored by `globs`.
+///
+/// As in gitignore, the last matching pattern decides, so a later `!pattern`
+/// re-includes a path an earlier pattern ignored.
+fn matches_ignore_globs(globs: &[IgnoreGlob], relative_path: &Path) -> bool {
+    globs
+        .iter()
+        .rev()
+        .find(|g| g.matches(relative_path))
+        .is_some_and(|g| !g.negated)
+}
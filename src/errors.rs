@@ -4,6 +4,7 @@
 //! that can occur during execution, offering more context than generic I/O or
 //! `anyhow` errors.
 
+use crate::config::escape_json;
 #[cfg(feature = "git")]
 use std::path::PathBuf;
 use thiserror::Error;
@@ -301,6 +302,102 @@ pub enum Error {
     // FileProcessingError { path: String, reason: String },
 }
 
+/// How the command-line tool reports an error before exiting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorFormat {
+    /// A single `Error: message` line.
+    #[default]
+    Text,
+    /// A JSON object with the error's type, message, and context (see [`Error::to_json`]).
+    Json,
+}
+
+impl Error {
+    /// Returns a stable identifier for the error's variant.
+    ///
+    /// The identifiers are `io`, `config`, `git`, `clipboard`, `interrupted`,
+    /// `generic`, and `no_files_found`. Unlike the message, they will not change
+    /// between releases, so tools can branch on them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dircat::errors::Error;
+    ///
+    /// assert_eq!(Error::NoFilesFound.error_type(), "no_files_found");
+    /// assert_eq!(Error::Interrupted.error_type(), "interrupted");
+    /// ```
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            Error::Io { .. } => "io",
+            Error::Config(_) => "config",
+            #[cfg(feature = "git")]
+            Error::Git(_) => "git",
+            #[cfg(feature = "clipboard")]
+            Error::Clipboard(_) => "clipboard",
+            Error::Interrupted => "interrupted",
+            Error::Generic(_) => "generic",
+            Error::NoFilesFound => "no_files_found",
+        }
+    }
+
+    /// Returns the error as a single-line JSON object.
+    ///
+    /// The object always has `error_type` (see [`Error::error_type`]) and
+    /// `message`. It also has `path` for I/O errors and for git errors about a
+    /// local path, `url` for git errors about a remote, and `option` for
+    /// configuration errors that name one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dircat::errors::io_error_with_path;
+    /// use std::io;
+    ///
+    /// let err = io_error_with_path(io::Error::new(io::ErrorKind::NotFound, "gone"), "a.txt");
+    /// assert_eq!(
+    ///     err.to_json(),
+    ///     r#"{"error_type": "io", "message": "I/O error accessing path 'a.txt': gone", "path": "a.txt"}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut fields = vec![
+            ("error_type", self.error_type().to_string()),
+            ("message", self.to_string()),
+        ];
+        match self {
+            Error::Io { path, .. } => fields.push(("path", path.clone())),
+            Error::Config(
+                ConfigError::Conflict {
+                    option1: option, ..
+                }
+                | ConfigError::InvalidValue { option, .. }
+                | ConfigError::MissingDependency { option, .. },
+            ) => fields.push(("option", option.clone())),
+            #[cfg(feature = "git")]
+            Error::Git(git_error) => match git_error {
+                GitError::CloneFailed { url, .. } | GitError::ApiDownloadFailed { url, .. } => {
+                    fields.push(("url", url.clone()))
+                }
+                GitError::SubdirectoryNotFound { path, repo } => {
+                    fields.push(("path", path.clone()));
+                    fields.push(("url", repo.clone()));
+                }
+                GitError::CorruptedCache { path } => {
+                    fields.push(("path", path.display().to_string()))
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        let body: Vec<String> = fields
+            .iter()
+            .map(|(key, value)| format!("\"{}\": \"{}\"", key, escape_json(value)))
+            .collect();
+        format!("{{{}}}", body.join(", "))
+    }
+}
+
 /// Helper function to create an `Error::Io` with path context.
 ///
 /// # Arguments
@@ -335,6 +432,19 @@ mod tests {
     use super::*;
     use std::{io, path::PathBuf};
 
+    #[test]
+    fn test_error_to_json_includes_option() {
+        let err: Error = ConfigError::InvalidValue {
+            option: "--top".to_string(),
+            reason: "must be \"positive\"".to_string(),
+        }
+        .into();
+        assert_eq!(
+            err.to_json(),
+            r#"{"error_type": "config", "message": "Invalid value for option '--top': must be \"positive\"", "option": "--top"}"#
+        );
+    }
+
     #[test]
     fn test_io_error_with_path_helper() {
         // Different test name
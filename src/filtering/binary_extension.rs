@@ -0,0 +1,45 @@
+// src/filtering/binary_extension.rs
+
+use std::path::Path;
+
+// Extensions of formats that are binary by definition (all lowercase)
+#[rustfmt::skip]
+const BINARY_EXTENSIONS: &[&str] = &[
+    // --- Images ---
+    "png", "jpg", "jpeg", "gif", "bmp", "ico", "icns", "tif", "tiff", "webp", "avif", "heic", "psd",
+    // --- Audio / Video ---
+    "mp3", "wav", "flac", "ogg", "m4a", "aac", "mp4", "m4v", "mov", "avi", "mkv", "webm",
+    // --- Fonts ---
+    "ttf", "otf", "woff", "woff2", "eot",
+    // --- Archives / Compression ---
+    "zip", "tar", "gz", "tgz", "bz2", "xz", "zst", "7z", "rar", "jar", "war", "whl",
+    // --- Documents ---
+    "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "odt", "ods", "odp",
+    // --- Compiled code / Executables ---
+    "exe", "dll", "so", "dylib", "a", "lib", "o", "obj", "class", "pyc", "pyo", "wasm", "rlib", "bin",
+    // --- Databases ---
+    "db", "sqlite", "sqlite3",
+];
+
+/// Checks if a path has the extension of a well-known binary format.
+///
+/// This classifies a file by name only, without reading it, and is used by
+/// `--trust-extensions` in place of the content-based check. The check is
+/// case-insensitive. Files with any other extension, or none, are considered text.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::filtering::has_binary_extension;
+/// use std::path::Path;
+///
+/// assert!(has_binary_extension(Path::new("assets/logo.PNG")));
+/// assert!(has_binary_extension(Path::new("target/libfoo.so")));
+/// assert!(!has_binary_extension(Path::new("src/main.rs")));
+/// assert!(!has_binary_extension(Path::new("Makefile")));
+/// ```
+pub fn has_binary_extension(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| BINARY_EXTENSIONS.contains(&ext.as_str()))
+}
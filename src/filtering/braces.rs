@@ -0,0 +1,128 @@
+// src/filtering/braces.rs
+
+/// Expands shell-style brace groups in a glob pattern.
+///
+/// A group such as `{rs,toml}` produces one pattern per comma-separated
+/// alternative, in order. Groups may be nested and a pattern may contain
+/// several of them. As in shells, a group without a top-level comma, an
+/// unmatched brace, or a brace escaped with `\` is kept literally.
+///
+/// # Examples
+/// ```
+/// use dircat::filtering::expand_braces;
+///
+/// assert_eq!(
+///     expand_braces("src/**/*.{rs,toml}"),
+///     vec!["src/**/*.rs", "src/**/*.toml"]
+/// );
+/// assert_eq!(expand_braces("*.md"), vec!["*.md"]);
+/// ```
+pub fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some((open, close, commas)) = find_brace_group(pattern) else {
+        return vec![pattern.to_string()];
+    };
+
+    let prefix = &pattern[..open];
+    let suffix = &pattern[close + 1..];
+    let mut bounds = Vec::with_capacity(commas.len() + 2);
+    bounds.push(open);
+    bounds.extend(commas);
+    bounds.push(close);
+
+    // Each alternative is substituted back in and expanded again, which
+    // handles both nested groups and any further groups in the suffix.
+    bounds
+        .windows(2)
+        .flat_map(|w| {
+            let alternative = &pattern[w[0] + 1..w[1]];
+            expand_braces(&format!("{}{}{}", prefix, alternative, suffix))
+        })
+        .collect()
+}
+
+/// Finds the first brace group that has a top-level comma.
+///
+/// Returns the byte offsets of its opening and closing braces and of its
+/// top-level commas.
+fn find_brace_group(pattern: &str) -> Option<(usize, usize, Vec<usize>)> {
+    let bytes = pattern.as_bytes();
+    let mut start = 0;
+    while let Some(open) = next_unescaped(bytes, start, b'{') {
+        let mut depth = 0;
+        let mut commas = Vec::new();
+        let mut i = open + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 1,
+                b'{' => depth += 1,
+                b'}' if depth == 0 => break,
+                b'}' => depth -= 1,
+                b',' if depth == 0 => commas.push(i),
+                _ => {}
+            }
+            i += 1;
+        }
+        if i < bytes.len() && !commas.is_empty() {
+            return Some((open, i, commas));
+        }
+        // Literal or unmatched brace: look for a group after it.
+        start = open + 1;
+    }
+    None
+}
+
+/// Returns the offset of the first unescaped `target` at or after `start`.
+fn next_unescaped(bytes: &[u8], start: usize, target: u8) -> Option<usize> {
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+        if bytes[i] == target {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expands_single_group() {
+        assert_eq!(expand_braces("*.{rs,toml}"), vec!["*.rs", "*.toml"]);
+    }
+
+    #[test]
+    fn test_expands_multiple_groups() {
+        assert_eq!(
+            expand_braces("{src,tests}/*.{rs,md}"),
+            vec!["src/*.rs", "src/*.md", "tests/*.rs", "tests/*.md"]
+        );
+    }
+
+    #[test]
+    fn test_expands_nested_groups() {
+        assert_eq!(expand_braces("a{b,c{d,e}}f"), vec!["abf", "acdf", "acef"]);
+    }
+
+    #[test]
+    fn test_empty_alternative() {
+        assert_eq!(expand_braces("Cargo{,.lock}"), vec!["Cargo", "Cargo.lock"]);
+    }
+
+    #[test]
+    fn test_keeps_literal_braces() {
+        assert_eq!(expand_braces("{single}.txt"), vec!["{single}.txt"]);
+        assert_eq!(expand_braces("unmatched{a,b"), vec!["unmatched{a,b"]);
+        assert_eq!(expand_braces(r"\{a,b}"), vec![r"\{a,b}"]);
+    }
+
+    #[test]
+    fn test_group_after_literal_braces() {
+        assert_eq!(expand_braces("{x}/*.{rs,md}"), vec!["{x}/*.rs", "{x}/*.md"]);
+    }
+}
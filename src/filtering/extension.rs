@@ -1,12 +1,15 @@
 // src/filtering/extension.rs
 
-use crate::config::DiscoveryConfig;
+use crate::config::{DiscoveryConfig, Extensionless};
 use std::path::Path;
 
 /// Checks if a path passes the include/exclude extension filters defined in `Config`.
 ///
 /// The filtering logic follows these rules in order:
 ///
+/// 0.  **Extensionless Files:** If `config.extensionless` is `Exclude`, files without an
+///     extension return `false`; if it is `Only`, files with one return `false`. Names
+///     like `.bashrc` count as having no extension.
 /// 1.  **Exclusion Precedence:** If `config.exclude_extensions` is `Some` and contains the
 ///     file's extension, the function returns `false`. This check is performed first
 ///     and takes priority over the inclusion filter.
@@ -54,8 +57,14 @@ use std::path::Path;
 pub fn passes_extension_filters(path: &Path, config: &DiscoveryConfig) -> bool {
     let extension = path
         .extension()
-        .and_then(|os_str| os_str.to_str())
-        .map(|s| s.to_lowercase()); // Compare case-insensitively
+        // Lossy conversion so non-UTF-8 extensions are still compared rather than ignored.
+        .map(|os_str| os_str.to_string_lossy().to_lowercase()); // Compare case-insensitively
+
+    // 0. Check the extensionless setting
+    match (config.extensionless, &extension) {
+        (Extensionless::Exclude, None) | (Extensionless::Only, Some(_)) => return false,
+        _ => {}
+    }
 
     // 1. Check exclude extensions first
     if let Some(ref exclude_exts) = config.exclude_extensions {
@@ -132,4 +141,20 @@ mod tests {
         assert!(!passes_extension_filters(Path::new("file.bak"), &config)); // Excluded
         assert!(!passes_extension_filters(Path::new("file.rs"), &config)); // Not included
     }
+
+    #[test]
+    fn test_ext_extensionless() {
+        let mut config = create_test_config(None, None);
+        config.extensionless = Extensionless::Exclude;
+        assert!(passes_extension_filters(Path::new("file.txt"), &config));
+        assert!(!passes_extension_filters(Path::new("Makefile"), &config));
+        assert!(!passes_extension_filters(Path::new(".bashrc"), &config)); // Dotfile has no extension
+
+        config.extensionless = Extensionless::Only;
+        assert!(!passes_extension_filters(Path::new("file.txt"), &config));
+        assert!(passes_extension_filters(
+            Path::new("scripts/deploy"),
+            &config
+        ));
+    }
 }
@@ -0,0 +1,82 @@
+// src/filtering/generated.rs
+
+/// How many lines from the top of a file are searched for a generated-code marker.
+const GENERATED_MARKER_LINES: usize = 5;
+
+/// Banners that code generators put at the top of their output.
+const GENERATED_MARKERS: &[&str] = &[
+    "@generated",
+    "Code generated by",
+    "DO NOT EDIT",
+    "<auto-generated",
+    "This file was automatically generated",
+    "Generated by the protocol buffer compiler",
+];
+
+/// Returns the marker that identifies `content` as generated code, if any.
+///
+/// Only the first few lines are searched, since generators put their banner at
+/// the top of the file; a marker mentioned further down (e.g., in a comment about
+/// generated code) does not count. Matching is case-sensitive.
+///
+/// # Examples
+/// ```
+/// use dircat::filtering::generated_marker;
+///
+/// let generated = "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage pb\n";
+/// assert_eq!(generated_marker(generated), Some("Code generated by"));
+///
+/// assert_eq!(generated_marker("fn main() {}\n"), None);
+/// ```
+pub fn generated_marker(content: &str) -> Option<&'static str> {
+    content
+        .lines()
+        .take(GENERATED_MARKER_LINES)
+        .find_map(|line| {
+            GENERATED_MARKERS
+                .iter()
+                .find(|marker| line.contains(*marker))
+                .copied()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_common_banners() {
+        assert_eq!(
+            generated_marker("// @generated by build.rs\nfn a() {}"),
+            Some("@generated")
+        );
+        assert_eq!(
+            generated_marker("# Code generated by tool; DO NOT EDIT.\nx = 1"),
+            Some("Code generated by")
+        );
+        assert_eq!(
+            generated_marker("/* DO NOT EDIT */\nint x;"),
+            Some("DO NOT EDIT")
+        );
+        assert_eq!(
+            generated_marker("// <auto-generated />\nclass A {}"),
+            Some("<auto-generated")
+        );
+    }
+
+    #[test]
+    fn test_marker_after_header_lines_is_ignored() {
+        let content = format!("{}// @generated\n", "line\n".repeat(GENERATED_MARKER_LINES));
+        assert_eq!(generated_marker(&content), None);
+    }
+
+    #[test]
+    fn test_marker_is_case_sensitive() {
+        assert_eq!(generated_marker("// do not edit lightly\n"), None);
+    }
+
+    #[test]
+    fn test_empty_content_is_not_generated() {
+        assert_eq!(generated_marker(""), None);
+    }
+}
@@ -0,0 +1,67 @@
+// src/filtering/minified.rs
+
+/// Content smaller than this is never considered minified.
+const MIN_MINIFIED_SIZE: usize = 512;
+
+/// The average number of bytes per line above which content is considered minified.
+const MIN_AVERAGE_LINE_LENGTH: usize = 200;
+
+/// Checks if text content is likely minified (e.g., bundled JavaScript or CSS).
+///
+/// The heuristic flags content that is reasonably large but has very few
+/// newlines relative to its size, i.e., its average line length exceeds a
+/// threshold. This catches minified files even when their name doesn't reveal it.
+///
+/// # Examples
+/// ```
+/// use dircat::filtering::is_likely_minified;
+///
+/// let minified = "var a=1;".repeat(200);
+/// assert!(is_likely_minified(&minified));
+///
+/// let normal = "let a = 1;\n".repeat(200);
+/// assert!(!is_likely_minified(&normal));
+/// ```
+pub fn is_likely_minified(content: &str) -> bool {
+    if content.len() < MIN_MINIFIED_SIZE {
+        return false;
+    }
+    let line_count = content.lines().count().max(1);
+    content.len() / line_count > MIN_AVERAGE_LINE_LENGTH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_content_is_not_minified() {
+        // A single long line below the size threshold is not flagged.
+        let content = "x".repeat(MIN_MINIFIED_SIZE - 1);
+        assert!(!is_likely_minified(&content));
+    }
+
+    #[test]
+    fn test_single_long_line_is_minified() {
+        let content = "function a(){return 1}".repeat(100);
+        assert!(is_likely_minified(&content));
+    }
+
+    #[test]
+    fn test_few_long_lines_are_minified() {
+        let line = format!("{}\n", ".a{color:red}".repeat(40));
+        let content = line.repeat(5);
+        assert!(is_likely_minified(&content));
+    }
+
+    #[test]
+    fn test_regular_source_is_not_minified() {
+        let content = "fn main() {\n    println!(\"Hello, world!\");\n}\n".repeat(50);
+        assert!(!is_likely_minified(&content));
+    }
+
+    #[test]
+    fn test_empty_content_is_not_minified() {
+        assert!(!is_likely_minified(""));
+    }
+}
@@ -7,18 +7,31 @@
 //! to allow for their use in other contexts.
 
 // Declare the sub-modules within the filtering module
+mod binary_extension;
+mod braces;
 mod extension;
 mod file_type;
+mod generated;
 mod lockfile;
+mod minified;
 mod process_last;
+mod secrets;
 mod size;
 mod text_detection;
 
 // Re-export the functions needed by other parts of the crate (like discovery)
 // Use pub to make them accessible within the crate and as part of the public library API.
+pub use binary_extension::has_binary_extension;
+pub use braces::expand_braces;
 pub use extension::passes_extension_filters;
 pub use file_type::is_file_type;
+pub use generated::generated_marker;
 pub use lockfile::is_lockfile;
+pub use minified::is_likely_minified;
 pub use process_last::check_process_last;
+pub use secrets::is_likely_secret_file;
 pub use size::passes_size_filter;
-pub use text_detection::{is_likely_text, is_likely_text_from_buffer};
+pub(crate) use text_detection::{classify_head, HeadVerdict, READ_BUFFER_SIZE};
+pub use text_detection::{
+    classify_text, is_likely_text, is_likely_text_from_buffer, TextClassification,
+};
@@ -1,5 +1,6 @@
 // src/filtering/process_last.rs
 
+use super::braces::expand_braces;
 use crate::config::DiscoveryConfig;
 use glob::Pattern;
 use log::warn; // Import warn
@@ -37,7 +38,14 @@ use std::path::Path;
 /// ```
 pub fn check_process_last(relative_path: &Path, config: &DiscoveryConfig) -> (bool, Option<usize>) {
     if let Some(ref last_patterns) = config.process_last {
-        for (index, pattern_str) in last_patterns.iter().enumerate() {
+        // A pattern with brace groups matches if any of its expansions does,
+        // and keeps its original position in the order.
+        let expanded = last_patterns
+            .iter()
+            .enumerate()
+            .flat_map(|(index, p)| expand_braces(p).into_iter().map(move |e| (index, e)));
+        for (index, pattern_str) in expanded {
+            let pattern_str = pattern_str.as_str();
             // Check if the pattern should be treated as recursive (mimicking gitignore behavior).
             // If a pattern has no path separators (e.g. "*.rs"), gitignore applies it recursively.
             // Standard glob does not, so we manually check `**/pattern` as well in that case.
@@ -104,6 +112,23 @@ mod tests {
         assert_eq!(check_process_last(rel_path_root, &config), (true, Some(1)));
     }
 
+    #[test]
+    fn test_match_brace_glob_keeps_pattern_index() {
+        let config = create_test_config(Some(vec!["*.md", "*.{rs,toml}"]));
+        assert_eq!(
+            check_process_last(Path::new("src/main.rs"), &config),
+            (true, Some(1))
+        );
+        assert_eq!(
+            check_process_last(Path::new("Cargo.toml"), &config),
+            (true, Some(1))
+        );
+        assert_eq!(
+            check_process_last(Path::new("README.md"), &config),
+            (true, Some(0))
+        );
+    }
+
     #[test]
     fn test_match_wildcard_filename_glob() {
         let config = create_test_config(Some(vec!["*.txt", "*.rs"]));
@@ -0,0 +1,90 @@
+// src/filtering/secrets.rs
+
+use std::path::Path;
+
+// File names that commonly hold credentials (all lowercase for efficient comparison)
+const SECRET_FILE_NAMES: &[&str] = &[
+    // --- Environment files ---
+    ".env",
+    // --- SSH private keys ---
+    "id_rsa",
+    "id_dsa",
+    "id_ecdsa",
+    "id_ed25519",
+    // --- Credential stores ---
+    "credentials",
+    "credentials.json",
+    ".netrc",
+    ".npmrc",
+    ".pypirc",
+    ".htpasswd",
+    ".git-credentials",
+];
+
+// Extensions of key and certificate stores (all lowercase)
+const SECRET_EXTENSIONS: &[&str] = &["pem", "key", "p12", "pfx", "jks", "keystore"];
+
+// Suffixes marking `.env.*` files as templates that are safe to share
+const ENV_TEMPLATE_SUFFIXES: &[&str] = &[".example", ".sample", ".template", ".dist"];
+
+/// Checks if a path looks like a file that contains secrets.
+///
+/// This matches `.env` and `.env.*` files (except templates such as `.env.example`),
+/// SSH private keys, common credential stores, and key/certificate files
+/// (e.g., `*.pem`, `*.key`). The check is case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::filtering::is_likely_secret_file;
+/// use std::path::Path;
+///
+/// assert!(is_likely_secret_file(Path::new("app/.env")));
+/// assert!(is_likely_secret_file(Path::new(".env.production")));
+/// assert!(is_likely_secret_file(Path::new("certs/server.PEM")));
+/// assert!(!is_likely_secret_file(Path::new(".env.example")));
+/// assert!(!is_likely_secret_file(Path::new("src/main.rs")));
+/// ```
+pub fn is_likely_secret_file(path: &Path) -> bool {
+    let Some(name) = path.file_name() else {
+        return false;
+    };
+    let name = name.to_string_lossy().to_lowercase();
+
+    if SECRET_FILE_NAMES.contains(&name.as_str()) {
+        return true;
+    }
+    if name.starts_with(".env.") {
+        return !ENV_TEMPLATE_SUFFIXES
+            .iter()
+            .any(|suffix| name.ends_with(suffix));
+    }
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| SECRET_EXTENSIONS.contains(&ext.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_is_likely_secret_file_matches() {
+        assert!(is_likely_secret_file(&PathBuf::from(".env")));
+        assert!(is_likely_secret_file(&PathBuf::from("deploy/.env.local")));
+        assert!(is_likely_secret_file(&PathBuf::from("home/.ssh/id_rsa")));
+        assert!(is_likely_secret_file(&PathBuf::from(".aws/credentials")));
+        assert!(is_likely_secret_file(&PathBuf::from("tls/cert.pem")));
+        assert!(is_likely_secret_file(&PathBuf::from("store.JKS")));
+    }
+
+    #[test]
+    fn test_is_likely_secret_file_no_match() {
+        assert!(!is_likely_secret_file(&PathBuf::from(".env.example")));
+        assert!(!is_likely_secret_file(&PathBuf::from(".env.sample")));
+        assert!(!is_likely_secret_file(&PathBuf::from("id_rsa.pub")));
+        assert!(!is_likely_secret_file(&PathBuf::from("environment.rs")));
+        assert!(!is_likely_secret_file(&PathBuf::from("keys.md")));
+    }
+}
@@ -1,19 +1,125 @@
 // src/filtering/text_detection.rs
 
 use content_inspector::ContentType;
-use std::{fs::File, io::Read, path::Path, str};
+use std::{fmt, fs::File, io::Read, path::Path, str};
 
-// Define a reasonable buffer size for content type detection
-const READ_BUFFER_SIZE: usize = 1024;
+// Define a reasonable buffer size for content type detection. This matches the
+// number of bytes `content_inspector` scans for null bytes.
+pub(crate) const READ_BUFFER_SIZE: usize = 1024;
 
-/// Checks if a byte buffer is likely text-based.
+/// The verdict on a file from only its first `READ_BUFFER_SIZE` bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HeadVerdict {
+    /// Text regardless of the rest of the file (it starts with a UTF-8 BOM).
+    Text,
+    /// Binary regardless of the rest of the file.
+    Binary,
+    /// Text only if the whole file is valid UTF-8.
+    TextIfValidUtf8,
+}
+
+/// The result of classifying a buffer as text or binary, with the reason for a binary verdict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextClassification {
+    /// The buffer is valid UTF-8 text (with or without a BOM).
+    Text,
+    /// The buffer contains a null byte at the given offset.
+    NullByte {
+        /// The byte offset of the first null byte.
+        offset: usize,
+    },
+    /// The buffer starts with the signature of a known binary format (e.g., PDF, PNG).
+    BinarySignature,
+    /// The buffer is text in an encoding other than UTF-8 (e.g., UTF-16).
+    UnsupportedEncoding,
+    /// The buffer looks like UTF-8 but contains an invalid sequence at the given offset.
+    InvalidUtf8 {
+        /// The byte offset at which the invalid sequence starts.
+        offset: usize,
+    },
+}
+
+impl TextClassification {
+    /// Returns `true` if the buffer was classified as text.
+    pub fn is_text(&self) -> bool {
+        matches!(self, TextClassification::Text)
+    }
+}
+
+impl fmt::Display for TextClassification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextClassification::Text => write!(f, "text"),
+            TextClassification::NullByte { offset } => {
+                write!(f, "null byte at offset {}", offset)
+            }
+            TextClassification::BinarySignature => write!(f, "binary file signature"),
+            TextClassification::UnsupportedEncoding => write!(f, "non-UTF-8 text encoding"),
+            TextClassification::InvalidUtf8 { offset } => {
+                write!(f, "invalid UTF-8 at offset {}", offset)
+            }
+        }
+    }
+}
+
+/// Classifies a byte buffer as text or binary, reporting why it is considered binary.
 ///
-/// This is the core logic used by both `is_likely_text` and the main processing loop.
 /// It uses `content_inspector` to perform a heuristic check and then verifies
 /// UTF-8 validity if the content type is ambiguous.
 ///
 /// # Examples
 /// ```
+/// use dircat::filtering::{classify_text, TextClassification};
+///
+/// assert_eq!(classify_text(b"plain text"), TextClassification::Text);
+/// assert_eq!(classify_text(b"ab\0cd"), TextClassification::NullByte { offset: 2 });
+///
+/// let verdict = classify_text(&[0x48, 0x65, 0x80]);
+/// assert_eq!(verdict, TextClassification::InvalidUtf8 { offset: 2 });
+/// assert_eq!(verdict.to_string(), "invalid UTF-8 at offset 2");
+/// ```
+pub fn classify_text(buffer_slice: &[u8]) -> TextClassification {
+    // Consider it text ONLY if explicitly detected as UTF_8_BOM,
+    // OR if detected as UTF_8 AND the buffer slice is actually valid UTF-8.
+    // All other types (BINARY, etc.) are treated as non-text.
+    match content_inspector::inspect(buffer_slice) {
+        ContentType::UTF_8_BOM => TextClassification::Text,
+        ContentType::UTF_8 => match str::from_utf8(buffer_slice) {
+            Ok(_) => TextClassification::Text,
+            Err(e) => TextClassification::InvalidUtf8 {
+                offset: e.valid_up_to(),
+            },
+        },
+        ContentType::BINARY => match buffer_slice.iter().position(|&b| b == 0) {
+            Some(offset) => TextClassification::NullByte { offset },
+            None => TextClassification::BinarySignature,
+        },
+        // UTF-16/32 and any future types are treated conservatively as non-text
+        _ => TextClassification::UnsupportedEncoding,
+    }
+}
+
+/// Classifies a file from its head, for callers that stream the rest of it.
+///
+/// Past the first `READ_BUFFER_SIZE` bytes, [`classify_text`] only checks that
+/// the content is valid UTF-8, so this verdict combined with a streaming UTF-8
+/// check agrees with `classify_text` on the whole file.
+pub(crate) fn classify_head(head: &[u8]) -> HeadVerdict {
+    match content_inspector::inspect(head) {
+        ContentType::UTF_8_BOM => HeadVerdict::Text,
+        ContentType::UTF_8 => HeadVerdict::TextIfValidUtf8,
+        _ => HeadVerdict::Binary,
+    }
+}
+
+/// Checks if a byte buffer is likely text-based.
+///
+/// This is the core logic used by both `is_likely_text` and the main processing loop.
+/// It delegates to [`classify_text`]; use that directly to learn why a buffer was
+/// considered binary.
+///
+/// # Examples
+/// ```
 /// use dircat::filtering::is_likely_text_from_buffer;
 ///
 /// let text_buffer = b"This is valid UTF-8 text.";
@@ -26,19 +132,7 @@ const READ_BUFFER_SIZE: usize = 1024;
 /// assert!(!is_likely_text_from_buffer(invalid_utf8_buffer));
 /// ```
 pub fn is_likely_text_from_buffer(buffer_slice: &[u8]) -> bool {
-    // Inspect the bytes read
-    let content_type = content_inspector::inspect(buffer_slice);
-
-    // Consider it text ONLY if explicitly detected as UTF_8_BOM,
-    // OR if detected as UTF_8 AND the buffer slice is actually valid UTF-8.
-    // All other types (BINARY, etc.) are treated as non-text.
-    match content_type {
-        ContentType::UTF_8_BOM => true,
-        ContentType::UTF_8 => str::from_utf8(buffer_slice).is_ok(), // Verify UTF-8 validity
-        ContentType::BINARY => false,
-        // Handle other potential future types conservatively as non-text
-        _ => false,
-    }
+    classify_text(buffer_slice).is_text()
 }
 
 /// Checks if the file content is likely text-based by reading its head.
@@ -111,6 +205,34 @@ mod tests {
         assert!(!is_likely_text_from_buffer(buffer));
     }
 
+    // --- Tests for classify_text ---
+    #[test]
+    fn test_classify_reasons() {
+        assert_eq!(classify_text(b"text"), TextClassification::Text);
+        assert_eq!(
+            classify_text(b"abc\0def"),
+            TextClassification::NullByte { offset: 3 }
+        );
+        assert_eq!(
+            classify_text(&[0x48, 0x65, 0x6c, 0x6c, 0x80, 0x6f]),
+            TextClassification::InvalidUtf8 { offset: 4 }
+        );
+        // UTF-16LE with BOM
+        assert_eq!(
+            classify_text(&[0xFF, 0xFE, b'h', 0x00, b'i', 0x00]),
+            TextClassification::UnsupportedEncoding
+        );
+    }
+
+    #[test]
+    fn test_classify_display() {
+        assert_eq!(
+            TextClassification::NullByte { offset: 7 }.to_string(),
+            "null byte at offset 7"
+        );
+        assert!(!TextClassification::BinarySignature.is_text());
+    }
+
     // --- Tests for is_likely_text (file-based) ---
     #[test]
     fn test_detect_utf8_text() -> std::io::Result<()> {
@@ -69,6 +69,40 @@ pub fn download_directory_via_api(
     url_parts: &ParsedGitUrl,
     branch_override: &Option<String>,
     target_dir: Option<&Path>,
+) -> Result<PathBuf> {
+    download_directory_via_api_with_max_depth(url_parts, branch_override, target_dir, None)
+}
+
+/// Like [`download_directory_via_api`], but descends at most `max_depth` levels
+/// below the requested directory.
+///
+/// With `Some(1)`, only the directory's own files are listed and downloaded.
+/// Deeper directories are never requested from the API, which bounds the number
+/// of requests on large trees; a warning reports how many were skipped.
+///
+/// # Errors
+/// Returns an error if API requests fail, the directory is not found, or file I/O fails.
+///
+/// # Examples
+/// ```no_run
+/// use dircat::git::{parse_github_folder_url, download_directory_via_api_with_max_depth};
+/// # use anyhow::Result;
+///
+/// # fn main() -> Result<()> {
+/// let url = "https://github.com/rust-lang/cargo/tree/master/src/cargo";
+/// if let Some(parsed_url) = parse_github_folder_url(url) {
+///     // Download 'src/cargo' and its direct subdirectories only.
+///     let path = download_directory_via_api_with_max_depth(&parsed_url, &None, None, Some(2))?;
+///     println!("Downloaded to: {}", path.display());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn download_directory_via_api_with_max_depth(
+    url_parts: &ParsedGitUrl,
+    branch_override: &Option<String>,
+    target_dir: Option<&Path>,
+    max_depth: Option<usize>,
 ) -> Result<PathBuf> {
     // 1. Setup
     let (base_dir, _temp_dir_guard) = if let Some(dir) = target_dir {
@@ -100,7 +134,7 @@ pub fn download_directory_via_api(
 
     // 3. List all files
     let files_to_download =
-        list_all_files_recursively(&client, &owner, &repo, &branch_to_use, url_parts)?;
+        list_all_files_recursively(&client, &owner, &repo, &branch_to_use, url_parts, max_depth)?;
 
     if files_to_download.is_empty() {
         // Leak the TempDir to prevent it from being deleted, and return its path.
@@ -149,18 +183,24 @@ fn fetch_default_branch(owner: &str, repo: &str, client: &Client) -> Result<Stri
 }
 
 /// Recursively lists all files in a given GitHub directory path using a queue.
+///
+/// Each queued directory carries its depth below the requested one (which is
+/// at depth 0). Directories whose contents would lie deeper than `max_depth`
+/// are skipped.
 fn list_all_files_recursively(
     client: &Client,
     owner: &str,
     repo: &str,
     branch: &str,
     url_parts: &ParsedGitUrl,
+    max_depth: Option<usize>,
 ) -> Result<Vec<ContentItem>> {
     let mut files = Vec::new();
-    let mut queue: VecDeque<String> = VecDeque::new();
-    queue.push_back(url_parts.subdirectory.clone());
+    let mut skipped_dirs = 0;
+    let mut queue: VecDeque<(String, usize)> = VecDeque::new();
+    queue.push_back((url_parts.subdirectory.clone(), 0));
 
-    while let Some(path) = queue.pop_front() {
+    while let Some((path, depth)) = queue.pop_front() {
         let path_segment = if path.is_empty() {
             String::new()
         } else {
@@ -194,10 +234,22 @@ fn list_all_files_recursively(
                     log::warn!("Skipping file with no download_url: {}", item.path);
                 }
             } else if item.item_type == "dir" {
-                queue.push_back(item.path);
+                if max_depth.is_some_and(|max| depth + 1 >= max) {
+                    log::debug!("Skipping directory beyond max depth: {}", item.path);
+                    skipped_dirs += 1;
+                } else {
+                    queue.push_back((item.path, depth + 1));
+                }
             }
         }
     }
+    if skipped_dirs > 0 {
+        log::warn!(
+            "Skipped {} directories deeper than the max depth of {} in the GitHub API listing.",
+            skipped_dirs,
+            max_depth.unwrap_or_default()
+        );
+    }
     Ok(files)
 }
 
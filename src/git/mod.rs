@@ -30,7 +30,7 @@ mod url;
 /// # Ok(())
 /// # }
 /// ```
-pub use api::download_directory_via_api;
+pub use api::{download_directory_via_api, download_directory_via_api_with_max_depth};
 /// Clones or updates a git repository into a local cache directory.
 ///
 /// # Examples
@@ -45,6 +45,7 @@ pub use api::download_directory_via_api;
 /// # }
 /// ```
 pub use clone::{get_repo, get_repo_cache_path};
+pub(crate) use ops::find_repo_root;
 /// Functions and types for parsing git and GitHub URLs.
 pub use ops::update_repo;
 pub use url::{
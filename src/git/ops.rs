@@ -5,8 +5,20 @@ use crate::errors::GitError;
 use crate::progress::ProgressReporter;
 use anyhow::{anyhow, Context, Result};
 use git2::{Cred, FetchOptions, RemoteCallbacks, Repository, ResetType};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Finds the root of the git working tree containing `path`.
+///
+/// Returns `None` if `path` is not inside a working tree (including bare
+/// repositories, which have none).
+pub(crate) fn find_repo_root(path: &Path) -> Option<PathBuf> {
+    let repo = Repository::discover(path).ok()?;
+    let workdir = repo.workdir()?;
+    // Canonicalize so the root can be stripped from canonical discovered paths.
+    workdir.canonicalize().ok()
+}
+
 /// Sets up remote callbacks for authentication and progress reporting.
 pub(super) fn create_remote_callbacks(
     progress: Option<Arc<dyn ProgressReporter>>,
@@ -87,7 +87,10 @@
 //! ```
 
 // Make modules public if they contain public types used in the API
+#[cfg(feature = "archive")]
+pub mod archive;
 pub mod cancellation;
+pub mod capabilities;
 pub mod cli;
 pub mod config;
 pub mod constants;
@@ -107,33 +110,47 @@ pub mod prelude;
 // Re-export key public types for easier use as a library
 pub use cancellation::CancellationToken;
 pub use config::{
-    Config, ConfigBuilder, DiscoveryConfig, OutputConfig, OutputDestination, ProcessingConfig,
+    BinaryEncoding, Config, ConfigBuilder, ContentStyle, DiscoverCallback, DiscoveryConfig,
+    Extensionless, IfExists, LastSort, Newline, OutputConfig, OutputDestination, OutputFormat,
+    PostProcessCallback, Preset, ProcessingConfig, RelativeBase, TicksPolicy,
 };
 pub use core_types::{FileCounts, FileInfo};
 pub use discovery::discover_files;
-pub use processing::{process_content, process_files, ProcessingOptions};
+pub use processing::{
+    process_content, process_files, process_files_with_progress, ProcessingOptions,
+};
 
 /// Standalone functions for file filtering and text detection.
 pub use filtering::{
-    check_process_last, is_file_type, is_likely_text, is_likely_text_from_buffer, is_lockfile,
-    passes_extension_filters, passes_size_filter,
+    check_process_last, classify_text, generated_marker, has_binary_extension, is_file_type,
+    is_likely_minified, is_likely_secret_file, is_likely_text, is_likely_text_from_buffer,
+    is_lockfile, passes_extension_filters, passes_size_filter, TextClassification,
 };
+pub use output::DelimitedFormatter;
+pub use output::IndexFormatter;
+pub use output::JsonFormatter;
+pub use output::ListingFormatter;
 pub use output::MarkdownFormatter;
 pub use output::OutputFormatter;
+pub use output::RoundtripFormatter;
+pub use output::XmlFormatter;
 /// Standalone functions and traits for content processing.
 pub use processing::{
     calculate_counts,
     filters::{
-        remove_comments, remove_empty_lines, ContentFilter, RemoveCommentsFilter,
+        remove_comments, remove_comments_collapsing_spaces, remove_comments_with_placeholder,
+        remove_empty_lines, CompactRemoveCommentsFilter, ContentFilter, RemoveCommentsFilter,
         RemoveEmptyLinesFilter,
     },
+    StreamingCounter,
 };
 
 // Re-export key git utility functions for library users
 #[cfg(feature = "git")]
 pub use git::{
-    download_directory_via_api, get_repo, is_git_url, parse_clone_url, parse_github_folder_url,
-    parse_github_folder_url_with_hint, parse_github_repo_url, ParsedGitUrl,
+    download_directory_via_api, download_directory_via_api_with_max_depth, get_repo, is_git_url,
+    parse_clone_url, parse_github_folder_url, parse_github_folder_url_with_hint,
+    parse_github_repo_url, ParsedGitUrl,
 };
 
 use crate::errors::{Error, Result};
@@ -170,7 +187,11 @@ use std::sync::Arc; // Import Write trait
 /// # Ok(())
 /// # }
 /// ```
+///
+/// The struct is `#[non_exhaustive]`, so fields can be added without breaking
+/// callers; outside this crate, build one with [`DircatResult::new`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct DircatResult {
     /// A vector of `FileInfo` structs, processed according to the `Config` and
     /// sorted in a deterministic order.
@@ -179,14 +200,54 @@ pub struct DircatResult {
     pub files: Vec<FileInfo>,
     /// The resolved, absolute path to the directory or file that was processed.
     pub resolved_path: std::path::PathBuf,
+    /// Directories with no included file beneath them after filtering, relative to
+    /// the input and sorted. Only filled when `report_empty_dirs` is set.
+    pub empty_dirs: Vec<std::path::PathBuf>,
+    /// How long reading and processing the discovered files took, excluding path
+    /// resolution and discovery. Used for the throughput line of `--stats`.
+    pub processing_time: std::time::Duration,
+    /// Files dropped to fit `token_budget`, relative to the input, in the order they
+    /// would have been output.
+    pub over_budget: Vec<std::path::PathBuf>,
 }
 
 impl DircatResult {
+    /// Creates a result holding `files`, processed from `resolved_path`.
+    ///
+    /// The remaining fields start empty: no empty directories, no files over a
+    /// token budget, and a zero processing time. Set them directly if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dircat::DircatResult;
+    /// use dircat::core_types::FileInfo;
+    /// use std::path::PathBuf;
+    ///
+    /// let file = FileInfo { relative_path: PathBuf::from("a.txt"), ..Default::default() };
+    /// let mut result = DircatResult::new(vec![file], PathBuf::from("/abs"));
+    /// result.over_budget = vec![PathBuf::from("b.txt")];
+    ///
+    /// assert_eq!(result.files.len(), 1);
+    /// assert!(result.empty_dirs.is_empty());
+    /// ```
+    pub fn new(files: Vec<FileInfo>, resolved_path: std::path::PathBuf) -> Self {
+        Self {
+            files,
+            resolved_path,
+            empty_dirs: Vec::new(),
+            processing_time: std::time::Duration::ZERO,
+            over_budget: Vec::new(),
+        }
+    }
+
     /// Formats the result using a custom output formatter.
     ///
     /// This method allows library users to provide their own implementation of the
     /// `OutputFormatter` trait to generate output in different formats (e.g., JSON, XML)
-    /// without needing to reimplement the discovery and processing logic.
+    /// without needing to reimplement the discovery and processing logic. The files in
+    /// `over_budget` are passed to
+    /// [`OutputFormatter::format_with_over_budget`](crate::output::OutputFormatter::format_with_over_budget).
     ///
     /// # Arguments
     /// * `formatter` - An instance of a type that implements `OutputFormatter`.
@@ -207,14 +268,16 @@ impl DircatResult {
     ///     size: 4,
     ///     processed_content: Some("test".to_string()),
     ///     counts: None, is_process_last: false, process_last_order: None, is_binary: false,
+    ///     is_minified: false, is_dir: false, symlink_target: None,
+    ///     discovery_index: None, content_hash: None, raw_content: None,
     /// };
-    /// let result = DircatResult { files: vec![file], resolved_path: PathBuf::from("/abs") };
+    /// let result = DircatResult::new(vec![file], PathBuf::from("/abs"));
     ///
     /// // 2. Choose a formatter and configure output options.
     /// let formatter = MarkdownFormatter;
     /// let opts = OutputConfig {
     ///     filename_only_header: false, line_numbers: false, backticks: false,
-    ///     num_ticks: 3, summary: false, counts: false
+    ///     num_ticks: 3, summary: false, counts: false, ..Default::default()
     /// };
     ///
     /// // 3. Format the result into a buffer.
@@ -227,13 +290,13 @@ impl DircatResult {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn format_with<F: OutputFormatter>(
+    pub fn format_with<F: OutputFormatter + ?Sized>(
         &self,
         formatter: &F,
         opts: &OutputConfig,
         writer: &mut dyn Write,
     ) -> Result<()> {
-        Ok(formatter.format(&self.files, opts, writer)?)
+        Ok(formatter.format_with_over_budget(&self.files, &self.over_budget, opts, writer)?)
     }
 
     /// Formats the result for a dry run using a custom output formatter.
@@ -264,14 +327,16 @@ impl DircatResult {
     ///     relative_path: PathBuf::from("a.txt"),
     ///     size: 4, processed_content: None, counts: None,
     ///     is_process_last: false, process_last_order: None, is_binary: false,
+    ///     is_minified: false, is_dir: false, symlink_target: None,
+    ///     discovery_index: None, content_hash: None, raw_content: None,
     /// };
-    /// let result = DircatResult { files: vec![file], resolved_path: PathBuf::from("/abs") };
+    /// let result = DircatResult::new(vec![file], PathBuf::from("/abs"));
     ///
     /// // 2. Choose a formatter and configure output options.
     /// let formatter = MarkdownFormatter;
     /// let opts = OutputConfig {
     ///     filename_only_header: false, line_numbers: false, backticks: false,
-    ///     num_ticks: 3, summary: false, counts: false
+    ///     num_ticks: 3, summary: false, counts: false, ..Default::default()
     /// };
     /// // 3. Format the dry run result into a buffer.
     /// let mut buffer = Vec::new();
@@ -283,7 +348,7 @@ impl DircatResult {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn format_dry_run_with<F: OutputFormatter>(
+    pub fn format_dry_run_with<F: OutputFormatter + ?Sized>(
         &self,
         formatter: &F,
         opts: &OutputConfig,
@@ -337,12 +402,22 @@ pub fn discover(
     resolved: &config::path_resolve::ResolvedInput,
     token: &CancellationToken,
 ) -> Result<impl Iterator<Item = FileInfo>> {
-    let (mut normal_files, last_files) = discovery::discover_files(config, resolved, token)?;
+    let (mut normal_files, mut last_files) = discovery::discover_files(config, resolved, token)?;
+
+    // Record the discovery order (e.g., the order of a `--treat-as-list` file)
+    // before sorting, so `preserve_input_order` can restore it. "last_files" are
+    // already sorted correctly by the discovery module and keep coming last.
+    let last_offset = normal_files.len();
+    for (index, file_info) in normal_files.iter_mut().enumerate() {
+        file_info.discovery_index = Some(index);
+    }
+    for (index, file_info) in last_files.iter_mut().enumerate() {
+        file_info.discovery_index = Some(last_offset + index);
+    }
 
     // Sort normal files alphabetically by relative path
     normal_files.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
 
-    // "last_files" are already sorted correctly by the discovery module.
     Ok(normal_files.into_iter().chain(last_files))
 }
 
@@ -446,12 +521,15 @@ pub fn process<'a>(
 /// This function orchestrates the discovery and parallel processing stages, and
 /// ensures the final list of files is deterministically sorted before returning.
 /// The sorting order is: normal files alphabetically by relative path, followed by
-/// files matching `--last` patterns in the order they were specified.
+/// files matching `--last` patterns in the order they were specified. If
+/// `config.preserve_input_order` is set, files are instead returned in discovery
+/// order (see `FileInfo::discovery_index`).
 ///
 /// # Arguments
 /// * `config` - The configuration for the entire run.
 /// * `token` - A `CancellationToken` that can be used to gracefully interrupt the process.
 /// * `progress` - An optional progress reporter for long operations like cloning.
+///   It is also reset after discovery and advanced as each file is processed.
 ///
 /// # Examples
 ///
@@ -478,68 +556,62 @@ pub fn execute(
     token: &CancellationToken,
     progress: Option<Arc<dyn progress::ProgressReporter>>,
 ) -> Result<DircatResult> {
+    // A saved archive replaces discovery entirely; the input path is not used.
+    #[cfg(feature = "archive")]
+    if let Some(archive_path) = &config.from_archive {
+        return execute_from_archive(config, archive_path, token);
+    }
+
     // --- Path Resolution (I/O heavy part) ---
-    let resolved_input = {
-        #[cfg(feature = "git")]
-        {
-            config::resolve_input(
-                &config.input_path,
-                &config.git_branch,
-                config.git_depth,
-                &config.git_cache_path,
-                &config.git_download_path,
-                config.git_download,
-                progress,
-            )?
-        }
-        #[cfg(not(feature = "git"))]
-        {
-            config::resolve_input(
-                &config.input_path,
-                &None,
-                None,
-                &None,
-                &None,
-                false,
-                progress,
-            )?
-        }
-    };
+    let resolved_input = config.resolve_input(progress.clone())?;
 
     #[cfg(feature = "git")]
     if config.show_download_path {
-        return Ok(DircatResult {
-            files: vec![],
-            resolved_path: resolved_input.path,
-        });
+        return Ok(DircatResult::new(vec![], resolved_input.path));
     }
 
     // Discover files based on config
-    let discovered_iter = discover(&config.discovery, &resolved_input, token)?;
+    #[cfg_attr(not(feature = "git"), allow(unused_mut))]
+    let mut discovered: Vec<_> = discover(&config.discovery, &resolved_input, token)?.collect();
+
+    #[cfg(feature = "git")]
+    if config.relative_base == config::RelativeBase::GitRoot {
+        rebase_on_git_root(&mut discovered, &resolved_input.path);
+    }
 
     if token.is_cancelled() {
         return Err(Error::Interrupted);
     }
 
+    let processing_started = std::time::Instant::now();
     let mut final_files = if config.dry_run {
         // For a dry run, we just need to filter out binaries from the discovered files.
         // The content isn't processed, but we still need to read the file head to check for binary content.
-        discovered_iter
-            .par_bridge()
+        discovered
+            .into_par_iter()
             .filter_map(|fi| {
                 if token.is_cancelled() {
                     return None;
                 }
-                if config.processing.include_binary {
+                if fi.is_dir || fi.is_symlink() {
+                    return Some(fi);
+                }
+                let only_binary = config.processing.only_binary;
+                if config.processing.include_binary && !only_binary {
                     return Some(fi);
                 }
-                // Check if the file is likely text. If it is, keep it.
+                // Check if the file is likely text. Keep text files normally, or
+                // only the binary ones when `only_binary` is set.
+                if config.processing.trust_extensions {
+                    let is_text = !filtering::has_binary_extension(&fi.relative_path);
+                    return (is_text != only_binary).then_some(fi);
+                }
                 match filtering::is_likely_text(&fi.absolute_path) {
                     Ok(is_text) => {
-                        if is_text {
+                        if is_text != only_binary {
                             Some(fi)
                         } else {
-                            // It's binary and we're not including binaries, so filter it out.
+                            // The file's type doesn't match what we're keeping, so filter it out.
                             None
                         }
                     }
@@ -554,33 +626,309 @@ pub fn execute(
                     }
                 }
             })
+            .map(|mut fi| {
+                if config.dry_run_counts && !fi.is_dir && !fi.is_symlink() {
+                    if let Err(e) = processing::count_file(&mut fi) {
+                        log::warn!(
+                            "Dry run: Could not count '{}': {}",
+                            fi.absolute_path.display(),
+                            e
+                        );
+                    }
+                }
+                if config.processing.hash_content && !fi.is_dir && !fi.is_symlink() {
+                    if let Err(e) = processing::hash_file(&mut fi) {
+                        log::warn!(
+                            "Dry run: Could not hash '{}': {}",
+                            fi.absolute_path.display(),
+                            e
+                        );
+                    }
+                }
+                fi
+            })
             .collect()
     } else {
-        // For a normal run, process the files.
-        let processed_iter = process(discovered_iter, &config.processing, token);
-        processed_iter.collect::<Result<Vec<_>>>()?
+        // For a normal run, process the files, reporting progress over the discovered total.
+        let total = discovered.len() as u64;
+        if let Some(p) = &progress {
+            p.reset();
+            p.set_length(total);
+            p.set_message("Processing files...".to_string());
+        }
+        let processed_iter = processing::process_files_with_progress(
+            discovered.into_iter(),
+            &config.processing,
+            token,
+            progress.as_deref(),
+        );
+        let processed = processed_iter.collect::<Result<Vec<_>>>()?;
+        if let Some(p) = &progress {
+            p.finish_with_message(format!("Processed {} files.", total));
+        }
+        processed
     };
+    let processing_time = processing_started.elapsed();
 
     if token.is_cancelled() {
         return Err(Error::Interrupted);
     }
 
-    // Re-sort the files after parallel processing, which does not preserve order.
-    // The sorting criteria are:
-    // 1. Normal files before "process_last" files.
-    // 2. "process_last" files are sorted by the order of the glob pattern they matched.
-    // 3. All other files are sorted alphabetically by relative path.
-    final_files.sort_by_key(|fi| {
-        (
-            fi.is_process_last,
-            fi.process_last_order,
-            fi.relative_path.clone(),
-        )
-    });
+    let empty_dirs = settle_dir_entries(&config.discovery, &mut final_files);
+    sort_final_files(config, &mut final_files);
+
+    // The archive holds the content read during processing, so a dry run has none to save.
+    #[cfg(feature = "archive")]
+    if let (Some(archive_path), false) = (&config.save_archive, config.dry_run) {
+        archive::save_archive(&archive::take_file_contents(&mut final_files), archive_path)?;
+    }
+
+    take_shard(config, &mut final_files);
+    let over_budget = take_token_budget(config, &mut final_files);
 
     Ok(DircatResult {
         files: final_files,
         resolved_path: resolved_input.path,
+        empty_dirs,
+        processing_time,
+        over_budget,
+    })
+}
+
+/// Rewrites each file's `relative_path` to be relative to the root of the git
+/// working tree containing `input_path`.
+///
+/// Files are left unchanged if `input_path` is not inside a working tree.
+#[cfg(feature = "git")]
+fn rebase_on_git_root(files: &mut [FileInfo], input_path: &std::path::Path) {
+    let Some(root) = git::find_repo_root(input_path) else {
+        log::warn!(
+            "'{}' is not inside a git working tree; paths stay relative to the input.",
+            input_path.display()
+        );
+        return;
+    };
+    for file_info in files {
+        if let Ok(rebased) = file_info.absolute_path.strip_prefix(&root) {
+            file_info.relative_path = rebased.to_path_buf();
+        }
+    }
+}
+
+/// Re-sorts files after parallel processing, which does not preserve order.
+///
+/// The sorting criteria are:
+/// 1. Normal files before "process_last" files.
+/// 2. "process_last" files are sorted by the order of the glob pattern they matched,
+///    unless `last_sort` orders them purely by path.
+/// 3. All other files are sorted alphabetically by relative path.
+///
+/// With `preserve_input_order`, the discovery order recorded by `discover` is restored instead.
+fn sort_final_files(config: &Config, files: &mut [FileInfo]) {
+    if config.preserve_input_order {
+        files.sort_by_key(|fi| fi.discovery_index);
+    } else {
+        let last_sort = config.discovery.last_sort;
+        files.sort_by_key(|fi| (fi.is_process_last, last_sort.sort_key(fi)));
+    }
+}
+
+/// Keeps only the slice of the sorted `files` selected by `config.shard`.
+///
+/// Shard `i` of `n` covers `[(i - 1) * len / n, i * len / n)`, so the shards are
+/// contiguous, differ in size by at most one, and together cover every file.
+fn take_shard(config: &Config, files: &mut Vec<FileInfo>) {
+    if let Some((index, count)) = config.shard {
+        let len = files.len();
+        let (start, end) = ((index - 1) * len / count, index * len / count);
+        files.truncate(end);
+        files.drain(..start);
+    }
+}
+
+/// Formats `result` with the formatter `run` selects for `config`.
+fn write_result(config: &Config, result: &DircatResult, writer: &mut dyn Write) -> Result<()> {
+    let output_config = OutputConfig::from(config);
+    let formatter = output::formatter_for(config);
+
+    if config.dry_run {
+        // Handle Dry Run formatting
+        result.format_dry_run_with(formatter.as_ref(), &output_config, writer)?;
+    } else {
+        // Handle Normal Run formatting
+        result.format_with(formatter.as_ref(), &output_config, writer)?;
+    }
+
+    // JSON and XML documents are complete on their own, so plain-text sections are not appended.
+    if output_config.summary
+        && !result.over_budget.is_empty()
+        && config.format == OutputFormat::Markdown
+    {
+        output::summary::write_over_budget(writer, &result.over_budget, &output_config)?;
+    }
+    Ok(())
+}
+
+/// Renders `result` to the exact bytes `run` would write to a new output file.
+///
+/// This applies the same formatter as `run`, then the `output_newline` conversion
+/// and, with `write_bom`, the leading byte order mark. It is used by `--verify`
+/// to check that a saved output file is up to date.
+///
+/// # Errors
+/// Returns an error if formatting fails.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::{execute, render_output, ConfigBuilder, CancellationToken};
+/// use std::fs;
+/// use tempfile::tempdir;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let temp = tempdir()?;
+/// fs::write(temp.path().join("a.txt"), "A")?;
+/// let config = ConfigBuilder::new()
+///     .input_path(temp.path().to_str().unwrap())
+///     .build()?;
+///
+/// let result = execute(&config, &CancellationToken::new(), None)?;
+/// let rendered = render_output(&config, &result)?;
+/// assert_eq!(String::from_utf8(rendered)?, "## File: a.txt\n```txt\nA\n```\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn render_output(config: &Config, result: &DircatResult) -> Result<Vec<u8>> {
+    let mut rendered = Vec::new();
+    if config.output.write_bom {
+        rendered.extend_from_slice(output::writer::UTF8_BOM);
+    }
+    match config.output.output_newline {
+        Some(newline) => {
+            let mut writer = output::newline::NewlineWriter::new(&mut rendered, newline);
+            write_result(config, result, &mut writer)?;
+            writer.flush().map_err(anyhow::Error::from)?;
+        }
+        None => write_result(config, result, &mut rendered)?,
+    }
+    Ok(rendered)
+}
+
+/// Keeps the leading files of the sorted `files` that fit within
+/// `config.token_budget`, and returns the relative paths of the rest.
+///
+/// Selection stops at the first file that would exceed the budget, so the
+/// output is always a prefix of the full, sorted output. If the first file alone
+/// exceeds the budget, it is kept anyway with a warning, rather than dropping
+/// every file.
+fn take_token_budget(config: &Config, files: &mut Vec<FileInfo>) -> Vec<std::path::PathBuf> {
+    let Some(budget) = config.token_budget else {
+        return Vec::new();
+    };
+    let mut used = 0;
+    let mut fits = files
+        .iter()
+        .take_while(|fi| {
+            used += fi.estimated_tokens();
+            used <= budget
+        })
+        .count();
+    if fits == 0 {
+        if let Some(first) = files.first() {
+            log::warn!(
+                "'{}' alone is estimated at {} tokens, over the budget of {}; keeping it anyway.",
+                first.relative_path.display(),
+                first.estimated_tokens(),
+                budget
+            );
+            fits = 1;
+        }
+    }
+    let dropped: Vec<_> = files.drain(fits..).map(|fi| fi.relative_path).collect();
+    if !dropped.is_empty() {
+        log::info!(
+            "Dropped {} files to fit the budget of {} tokens.",
+            dropped.len(),
+            budget
+        );
+    }
+    dropped
+}
+
+/// Finds the directories left empty by filtering, then drops the directory
+/// entries that were not asked for.
+///
+/// Directory entries are yielded for `report_empty_dirs` even without
+/// `include_dirs`; those are all removed here, and `exclude_empty_dirs` removes
+/// the empty ones. Returns the empty directories when `report_empty_dirs` is set.
+fn settle_dir_entries(
+    discovery: &config::DiscoveryConfig,
+    files: &mut Vec<FileInfo>,
+) -> Vec<std::path::PathBuf> {
+    if !discovery.report_empty_dirs && !discovery.exclude_empty_dirs {
+        return Vec::new();
+    }
+    let empty_dirs = discovery::find_empty_dirs(files);
+    if !discovery.include_dirs {
+        files.retain(|fi| !fi.is_dir);
+    } else if discovery.exclude_empty_dirs {
+        files.retain(|fi| !fi.is_dir || empty_dirs.binary_search(&fi.relative_path).is_err());
+    }
+    if discovery.report_empty_dirs {
+        empty_dirs
+    } else {
+        Vec::new()
+    }
+}
+
+/// Runs the processing stage over the files stored in a saved archive.
+///
+/// The archive takes the place of path resolution and discovery. For a dry run,
+/// the stored files are listed without being processed.
+#[cfg(feature = "archive")]
+fn execute_from_archive(
+    config: &Config,
+    archive_path: &std::path::Path,
+    token: &CancellationToken,
+) -> Result<DircatResult> {
+    let processing_started = std::time::Instant::now();
+    let contents = archive::load_archive(archive_path)?;
+    let mut files = if config.dry_run {
+        contents
+            .into_iter()
+            .map(|fc| FileInfo {
+                absolute_path: fc.relative_path.clone(),
+                relative_path: fc.relative_path,
+                size: fc.content.len() as u64,
+                is_process_last: fc.is_process_last,
+                process_last_order: fc.process_last_order,
+                ..Default::default()
+            })
+            .collect()
+    } else {
+        processing::process_content_with(
+            contents.into_iter(),
+            ProcessingOptions::from(config),
+            Some(&config.processing),
+            token,
+        )
+        .collect::<Result<Vec<_>>>()?
+    };
+    let processing_time = processing_started.elapsed();
+
+    if token.is_cancelled() {
+        return Err(Error::Interrupted);
+    }
+
+    sort_final_files(config, &mut files);
+    take_shard(config, &mut files);
+    let over_budget = take_token_budget(config, &mut files);
+    Ok(DircatResult {
+        files,
+        resolved_path: archive_path.to_path_buf(),
+        empty_dirs: vec![],
+        processing_time,
+        over_budget,
     })
 }
 
@@ -626,9 +974,14 @@ pub fn run(
     token: &CancellationToken,
     progress: Option<Arc<dyn progress::ProgressReporter>>,
 ) -> Result<()> {
+    let started = std::time::Instant::now();
     // Execute the core logic to get the processed files.
     let result = execute(config, token, progress)?;
 
+    if config.discovery.report_empty_dirs {
+        output::stats::write_empty_dirs(&mut std::io::stderr().lock(), &result.empty_dirs)?;
+    }
+
     #[cfg(feature = "git")]
     if config.show_download_path {
         println!("{}", result.resolved_path.display());
@@ -643,21 +996,18 @@ pub fn run(
     // Set up the output writer (stdout, file, or clipboard buffer)
     let writer_setup = output::writer::setup_output_writer(config)?;
     let mut writer: Box<dyn Write + Send> = writer_setup.writer;
-    let output_config = OutputConfig::from(config);
-    let formatter = MarkdownFormatter;
-
-    if config.dry_run {
-        // Handle Dry Run formatting
-        result.format_dry_run_with(&formatter, &output_config, &mut writer)?;
-    } else {
-        // Handle Normal Run formatting
-        result.format_with(&formatter, &output_config, &mut writer)?;
-    }
+    write_result(config, &result, &mut writer)?;
 
     // Finalize output (e.g., copy to clipboard)
-    Ok(output::writer::finalize_output(
-        writer,
-        writer_setup.clipboard_buffer,
-        config,
-    )?)
+    output::writer::finalize_output(writer, writer_setup.clipboard_buffer, config)?;
+
+    if config.stats {
+        output::stats::write_stats(
+            &mut std::io::stderr().lock(),
+            &result.files,
+            started.elapsed(),
+            result.processing_time,
+        )?;
+    }
+    Ok(())
 }
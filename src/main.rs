@@ -2,14 +2,18 @@
 
 use anyhow::Result;
 use clap::Parser;
+use dircat::capabilities::capabilities_json;
 use dircat::cli::Cli;
-use dircat::config::ConfigBuilder;
-use dircat::errors::Error;
+use dircat::config::{Config, ConfigBuilder};
+use dircat::errors::{io_error_with_path, Error, ErrorFormat};
+use dircat::output::unpack::unpack_bundle;
+use dircat::output::writer::{finalize_output, setup_output_writer};
 #[cfg(feature = "progress")]
 use dircat::progress::IndicatifProgress;
 use dircat::progress::ProgressReporter;
-use dircat::run;
 use dircat::signal::setup_signal_handler;
+use dircat::{execute, render_output, run};
+use std::path::Path;
 use std::sync::Arc;
 
 fn main() -> Result<()> {
@@ -34,29 +38,155 @@ fn main() -> Result<()> {
         }
     };
 
+    let error_format = cli_args.error_format;
+
+    if cli_args.capabilities {
+        println!("{}", capabilities_json());
+        return Ok(());
+    }
+
+    // --- Unpacking ---
+    if let Some(packed_file) = &cli_args.unpack {
+        let written = check(
+            std::fs::read(packed_file)
+                .map_err(|e| io_error_with_path(e, Path::new(packed_file)))
+                .and_then(|packed| unpack_bundle(&packed, Path::new(&cli_args.input_path))),
+            error_format,
+        )?;
+        eprintln!(
+            "dircat: Unpacked {} files into {}",
+            written.len(),
+            cli_args.input_path
+        );
+        return Ok(());
+    }
+
     // --- Configuration & Execution ---
-    let config = ConfigBuilder::from_cli(cli_args).build()?;
+    let print_config = cli_args.print_config;
+    let (fail_if_empty, allow_empty) = (cli_args.fail_if_empty, cli_args.allow_empty);
+    let verify = cli_args.verify.clone();
+    let mut builder = ConfigBuilder::from_cli(cli_args);
+    if let Some(expected_file) = &verify {
+        // The saved output usually sits inside the input it was generated from.
+        builder = builder.exclude_file(expected_file);
+    }
+    let config = check(builder.build(), error_format)?;
+
+    if let Some(format) = print_config {
+        let resolved = check(config.resolve_input(progress_reporter), error_format)?;
+        print!("{}", config.describe_with(format, Some(&resolved.path)));
+        return Ok(());
+    }
+
     let token = setup_signal_handler()?;
 
+    // --- Verification ---
+    if let Some(expected_file) = verify {
+        let rendered = check(
+            execute(&config, &token, progress_reporter)
+                .and_then(|result| render_output(&config, &result)),
+            error_format,
+        )?;
+        let expected = check(
+            std::fs::read(&expected_file)
+                .map_err(|e| io_error_with_path(e, Path::new(&expected_file))),
+            error_format,
+        )?;
+        if let Some(line) = first_difference(&expected, &rendered) {
+            eprintln!(
+                "dircat: {} is out of date: first difference at line {} ({} lines in the file, {} lines generated)",
+                expected_file,
+                line,
+                line_count(&expected),
+                line_count(&rendered)
+            );
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let result = run(&config, &token, progress_reporter);
 
     // --- Error Handling ---
     if let Err(e) = result {
         match e {
             Error::Interrupted => {
-                eprintln!("\nOperation cancelled.");
+                match error_format {
+                    ErrorFormat::Text => eprintln!("\nOperation cancelled."),
+                    ErrorFormat::Json => eprintln!("{}", e.to_json()),
+                }
                 std::process::exit(130);
             }
             Error::NoFilesFound => {
-                eprintln!("dircat: No files found matching the specified criteria.");
+                if allow_empty {
+                    // Still write the destination, so an output file never keeps stale content.
+                    if let Err(e) = write_empty_output(&config) {
+                        exit_with_error(&e, error_format);
+                    }
+                } else {
+                    match error_format {
+                        ErrorFormat::Text => {
+                            eprintln!("dircat: No files found matching the specified criteria.")
+                        }
+                        ErrorFormat::Json => eprintln!("{}", e.to_json()),
+                    }
+                }
+                if fail_if_empty {
+                    std::process::exit(1);
+                }
                 return Ok(());
             }
-            _ => {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            }
+            _ => exit_with_error(&e, error_format),
         }
     }
 
     Ok(())
 }
+
+/// Creates or truncates the output destination without writing any files to it.
+///
+/// Only the byte order mark is written, if one was requested for file output.
+fn write_empty_output(config: &Config) -> dircat::errors::Result<()> {
+    let setup = setup_output_writer(config)?;
+    finalize_output(setup.writer, setup.clipboard_buffer, config)?;
+    Ok(())
+}
+
+/// Returns the 1-based number of the first line where `expected` and `actual`
+/// differ, or `None` if they are identical.
+fn first_difference(expected: &[u8], actual: &[u8]) -> Option<usize> {
+    if expected == actual {
+        return None;
+    }
+    let mut expected_lines = expected.split(|b| *b == b'\n');
+    let mut actual_lines = actual.split(|b| *b == b'\n');
+    let mut line = 1;
+    while expected_lines.next() == actual_lines.next() {
+        line += 1;
+    }
+    Some(line)
+}
+
+/// Counts the lines in `bytes`, including a last line without a newline.
+fn line_count(bytes: &[u8]) -> usize {
+    let newlines = bytes.iter().filter(|b| **b == b'\n').count();
+    newlines + usize::from(!bytes.is_empty() && !bytes.ends_with(b"\n"))
+}
+
+/// Passes an error on to `main`'s default reporting, or with `--error-format json`
+/// prints it as JSON and exits.
+fn check<T>(result: dircat::errors::Result<T>, format: ErrorFormat) -> Result<T> {
+    result.or_else(|e| match format {
+        ErrorFormat::Text => Err(e.into()),
+        ErrorFormat::Json => exit_with_error(&e, format),
+    })
+}
+
+/// Prints `error` to stderr in the requested format and exits with status 1.
+fn exit_with_error(error: &Error, format: ErrorFormat) -> ! {
+    match format {
+        ErrorFormat::Text => eprintln!("Error: {}", error),
+        ErrorFormat::Json => eprintln!("{}", error.to_json()),
+    }
+    std::process::exit(1);
+}
@@ -0,0 +1,180 @@
+// src/output/delimited.rs
+
+//! A formatter that separates files with a custom delimiter line instead of Markdown.
+
+use crate::core_types::FileInfo;
+use crate::output::formatter::format_path_for_display;
+use crate::output::{dry_run, summary, OutputConfig, OutputFormatter};
+use anyhow::Result;
+use log::debug;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// The placeholder in a delimiter pattern that is replaced with the file's path.
+pub const PATH_PLACEHOLDER: &str = "{path}";
+
+/// A formatter that writes each file's raw content preceded by a delimiter line.
+///
+/// The delimiter is a pattern in which every occurrence of `{path}` is replaced
+/// with the file's display path (e.g., `===== FILE: {path} =====`). No headers or
+/// code fences are written, which keeps the output compact for tools that expect
+/// a fixed delimiter convention.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::{DelimitedFormatter, OutputFormatter};
+/// use dircat::OutputConfig;
+/// use dircat::core_types::FileInfo;
+/// use std::path::PathBuf;
+/// # fn main() -> anyhow::Result<()> {
+///
+/// let file = FileInfo {
+///     absolute_path: PathBuf::from("/abs/a.txt"),
+///     relative_path: PathBuf::from("a.txt"),
+///     processed_content: Some("test".to_string()),
+///     ..Default::default()
+/// };
+/// let opts = OutputConfig {
+///     filename_only_header: false, line_numbers: false, backticks: false,
+///     num_ticks: 3, summary: false, counts: false, ..Default::default()
+/// };
+///
+/// let formatter = DelimitedFormatter::new("<<<{path}>>>");
+/// let mut buffer = Vec::new();
+/// formatter.format(&[file], &opts, &mut buffer)?;
+///
+/// assert_eq!(String::from_utf8(buffer)?, "<<<a.txt>>>\ntest\n");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DelimitedFormatter {
+    delimiter: String,
+}
+
+impl DelimitedFormatter {
+    /// Creates a new formatter using the given delimiter pattern.
+    pub fn new(delimiter: impl Into<String>) -> Self {
+        Self {
+            delimiter: delimiter.into(),
+        }
+    }
+
+    /// Renders the delimiter line for a single file.
+    fn delimiter_for(&self, file_info: &FileInfo, opts: &OutputConfig) -> String {
+        let path = if opts.filename_only_header {
+            file_info
+                .relative_path
+                .file_name()
+                .map(PathBuf::from)
+                .unwrap_or_else(|| file_info.relative_path.clone())
+        } else {
+            file_info.relative_path.clone()
+        };
+        self.delimiter
+            .replace(PATH_PLACEHOLDER, &format_path_for_display(&path, opts))
+    }
+}
+
+impl OutputFormatter for DelimitedFormatter {
+    fn format(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        debug!("Starting delimited output generation...");
+
+        for file_info in files.iter().filter(|fi| !fi.is_dir && !fi.is_symlink()) {
+            writeln!(writer, "{}", self.delimiter_for(file_info, opts))?;
+            match &file_info.processed_content {
+                Some(content) if content.is_empty() => {}
+                Some(content) => {
+                    write!(writer, "{}", content)?;
+                    if !content.ends_with('\n') {
+                        writeln!(writer)?;
+                    }
+                }
+                None => {
+                    log::warn!(
+                        "Content not available for file: {}",
+                        file_info.absolute_path.display()
+                    );
+                }
+            }
+            if opts.flush_per_file {
+                writer.flush()?;
+            }
+        }
+
+        if opts.summary && !files.is_empty() {
+            let all_processed_files: Vec<&FileInfo> =
+                files.iter().filter(|fi| !fi.is_dir).collect();
+            summary::write_summary(writer, &all_processed_files, opts)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn format_dry_run(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        let file_refs: Vec<&FileInfo> = files.iter().collect();
+        dry_run::write_dry_run_output(writer, &file_refs, opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{create_mock_file_info, create_mock_output_config};
+
+    #[test]
+    fn test_delimited_formatter_multiple_files() -> Result<()> {
+        let opts = create_mock_output_config(false, false, false, false);
+        let mut a = create_mock_file_info("src/a.rs", 10);
+        a.processed_content = Some("fn a() {}\n".to_string());
+        let mut b = create_mock_file_info("b.txt", 5);
+        b.processed_content = Some("B".to_string());
+
+        let mut output = Vec::new();
+        DelimitedFormatter::new("===== FILE: {path} =====").format(&[a, b], &opts, &mut output)?;
+
+        let expected = "===== FILE: src/a.rs =====\nfn a() {}\n===== FILE: b.txt =====\nB\n";
+        assert_eq!(String::from_utf8(output)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_delimited_formatter_respects_path_options() -> Result<()> {
+        let opts = create_mock_output_config(true, true, false, false);
+        let mut a = create_mock_file_info("src/a.rs", 10);
+        a.processed_content = Some(String::new());
+
+        let mut output = Vec::new();
+        DelimitedFormatter::new("<<<{path}>>>").format(&[a], &opts, &mut output)?;
+
+        assert_eq!(String::from_utf8(output)?, "<<<`a.rs`>>>\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_delimited_formatter_with_summary() -> Result<()> {
+        let opts = create_mock_output_config(false, false, false, true);
+        let mut a = create_mock_file_info("a.txt", 1);
+        a.processed_content = Some("A".to_string());
+
+        let mut output = Vec::new();
+        DelimitedFormatter::new("<<<{path}>>>").format(&[a], &opts, &mut output)?;
+
+        let output = String::from_utf8(output)?;
+        assert!(output.starts_with("<<<a.txt>>>\nA\n---\n"));
+        assert!(output.contains("Processed Files: (1)"));
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 // src/output/dry_run.rs
 
 use crate::core_types::FileInfo;
-use crate::output::formatter::format_path_for_display;
+use crate::output::formatter::{format_path_for_display, format_symlink_for_display};
 use crate::output::OutputConfig;
 use anyhow::Result;
 use log::debug;
@@ -11,6 +11,8 @@ use std::io::Write;
 ///
 /// This function lists the relative paths of files that would be processed.
 /// It iterates over the provided files slice in order, without re-sorting.
+/// Files that carry counts (from `--dry-run-count`) are listed with them, in
+/// the same format as the summary.
 /// The caller is responsible for ensuring the files are in the desired display order.
 #[doc(hidden)] // This is a public helper but not intended for direct library use.
 pub(crate) fn write_dry_run_output(
@@ -23,8 +25,21 @@ pub(crate) fn write_dry_run_output(
 
     // Iterate directly over the provided slice to preserve order.
     for file_info in files {
-        let path_str = format_path_for_display(&file_info.relative_path, opts);
-        writeln!(writer, "- {}", path_str)?;
+        let path_str = match &file_info.symlink_target {
+            Some(target) => format_symlink_for_display(&file_info.relative_path, target, opts),
+            None => format_path_for_display(&file_info.relative_path, opts),
+        };
+        match file_info.counts {
+            Some(counts) if file_info.is_binary => {
+                writeln!(writer, "- {} (Binary C:{})", path_str, counts.characters)?
+            }
+            Some(counts) => writeln!(
+                writer,
+                "- {} (L:{} C:{} W:{})",
+                path_str, counts.lines, counts.characters, counts.words
+            )?,
+            None => writeln!(writer, "- {}", path_str)?,
+        }
     }
 
     writeln!(writer, "--- End Dry Run ---")?;
@@ -35,7 +50,7 @@ pub(crate) fn write_dry_run_output(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core_types::FileInfo;
+    use crate::core_types::{FileCounts, FileInfo};
     use std::io::Cursor;
     use std::path::PathBuf;
 
@@ -89,6 +104,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_dry_run_output_with_counts() -> Result<()> {
+        let opts = create_test_opts(false);
+        let mut text = create_file_info("a.rs");
+        text.counts = Some(FileCounts {
+            lines: 2,
+            characters: 20,
+            words: 4,
+        });
+        let mut binary = create_file_info("logo.png");
+        binary.is_binary = true;
+        binary.counts = Some(FileCounts {
+            lines: 0,
+            characters: 100,
+            words: 0,
+        });
+        let plain = create_file_info("b.txt");
+        let mut writer = Cursor::new(Vec::new());
+        write_dry_run_output(&mut writer, &[&text, &binary, &plain], &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        let expected = "\n--- Dry Run: Files that would be processed ---\n- a.rs (L:2 C:20 W:4)\n- logo.png (Binary C:100)\n- b.txt\n--- End Dry Run ---\n";
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
     #[test]
     fn test_dry_run_output_with_backticks() -> Result<()> {
         let opts = create_test_opts(true); // Enable backticks
@@ -1,18 +1,24 @@
 //! Handles the formatting of a single file's content into a Markdown block.
 
-use crate::constants::DEFAULT_LINE_NUMBER_WIDTH;
+use crate::config::{BinaryEncoding, ContentStyle, TicksPolicy};
+use crate::constants::{
+    DEFAULT_LINE_NUMBER_WIDTH, FENCE_ESCAPE, FENCE_PRONE_LANGUAGES, WRAP_CONTINUATION_MARKER,
+};
 use crate::core_types::FileInfo;
 use crate::output::formatter::format_path_for_display;
 use crate::output::OutputConfig;
 use anyhow::Result;
 use log::debug; // Import debug
+use std::borrow::Cow;
 use std::io::Write;
 use std::path::PathBuf;
 
 /// Writes a single file's header and content block to the writer.
 ///
-/// This function generates a Markdown header (`## File: ...`) followed by a
-/// fenced code block containing the file's processed content.
+/// This function generates a Markdown header (`## File: ...`) followed by the
+/// file's processed content. By default the content is placed in a fenced code
+/// block; `content_style` can instead render it as a blockquote, an indented
+/// code block, or raw text directly under the header.
 ///
 /// # Arguments
 /// * `writer` - The `Write` trait object to write the output to.
@@ -29,7 +35,7 @@ use std::path::PathBuf;
 /// use dircat::core_types::FileInfo;
 /// use std::path::PathBuf;
 ///
-/// let opts = OutputConfig { backticks: false, filename_only_header: false, line_numbers: true, num_ticks: 3, summary: false, counts: false };
+/// let opts = OutputConfig { backticks: false, filename_only_header: false, line_numbers: true, num_ticks: 3, summary: false, counts: false, ..Default::default() };
 ///
 /// let file_info = FileInfo {
 ///     absolute_path: PathBuf::from("/tmp/test.rs"),
@@ -52,15 +58,19 @@ pub fn write_file_block(
     file_info: &FileInfo,
     opts: &OutputConfig,
 ) -> Result<()> {
-    let path_to_display = if opts.filename_only_header {
-        file_info
-            .relative_path
-            .file_name()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| file_info.relative_path.clone()) // Fallback if no filename
-    } else {
-        file_info.relative_path.clone()
-    };
+    write_indexed_file_block(writer, file_info, None, opts)
+}
+
+/// Writes a file block whose header is numbered with `index`, if given.
+///
+/// `index` is the file's 1-based position in the output, used for `index_headers`.
+pub(crate) fn write_indexed_file_block(
+    writer: &mut dyn Write,
+    file_info: &FileInfo,
+    index: Option<usize>,
+    opts: &OutputConfig,
+) -> Result<()> {
+    let path_to_display = header_path(file_info, opts);
     // Persistent Debug Log: Log the path being used for the header
     debug!(
         "Path used for header display: '{}'",
@@ -68,18 +78,42 @@ pub fn write_file_block(
     );
     let header_path_str = format_path_for_display(&path_to_display, opts);
 
+    // --- Anchor Comment ---
+    if opts.anchor_comments {
+        writeln!(writer, "{}", anchor_comment(file_info))?;
+    }
+
+    // --- Open Collapsible Wrapper ---
+    if opts.collapsible {
+        let summary_path = path_to_display.to_string_lossy().replace('\\', "/");
+        writeln!(writer, "<details>")?;
+        writeln!(writer, "<summary>{}</summary>", escape_html(&summary_path))?;
+        writeln!(writer)?; // Markdown inside HTML blocks needs a blank line
+    }
+
     // --- Write File Header ---
-    writeln!(writer, "## File: {}", header_path_str)?;
+    writeln!(
+        writer,
+        "## {}",
+        header_text(file_info, &header_path_str, index, opts)
+    )?;
 
     // --- Write Code Block ---
-    let extension_hint = file_info
-        .relative_path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .unwrap_or(""); // Default to empty hint if no extension
-
-    let fence = "`".repeat(opts.num_ticks as usize);
-    writeln!(writer, "{}{}", fence, extension_hint)?;
+    let fence = match opts.content_style {
+        ContentStyle::CodeFence => {
+            let language_hint = fence_language(file_info, opts);
+            let fence = "`".repeat(fence_width(file_info, opts));
+            writeln!(writer, "{}{}", fence, language_hint)?;
+            Some(fence)
+        }
+        // Blank line so the block is not read as part of a preceding paragraph.
+        ContentStyle::Blockquote | ContentStyle::Indented => {
+            writeln!(writer)?;
+            None
+        }
+        // Raw content starts right under the header, for tools that do their own highlighting.
+        ContentStyle::Raw => None,
+    };
 
     // Write content line by line, adding line numbers if requested
     if let Some(content) = &file_info.processed_content {
@@ -90,18 +124,11 @@ pub fn write_file_block(
             // Handle empty file: write nothing between ``` blocks
         } else if lines.is_empty() && !content.is_empty() {
             // Handle file with content but no newline (single line)
-            if opts.line_numbers {
-                write!(writer, "{:>width$} | ", 1, width = num_width)?;
-            }
-            writeln!(writer, "{}", content)?;
+            write_content_line(writer, 1, content, num_width, opts)?;
         } else {
             // Handle multiple lines (or single line ending in newline)
             for (i, line) in lines.iter().enumerate() {
-                if opts.line_numbers {
-                    // Format with dynamic padding: "{line_num:>width$} | {line_content}"
-                    write!(writer, "{:>width$} | ", i + 1, width = num_width)?;
-                }
-                writeln!(writer, "{}", line)?;
+                write_content_line(writer, i + 1, line, num_width, opts)?;
             }
         }
     } else {
@@ -110,14 +137,245 @@ pub fn write_file_block(
             "Content not available for file: {}",
             file_info.absolute_path.display()
         );
-        writeln!(writer, "// Content not available")?;
+        writeln!(
+            writer,
+            "{}// Content not available",
+            content_line_prefix(opts.content_style)
+        )?;
+    }
+
+    match fence {
+        Some(fence) => writeln!(writer, "{}", fence)?,
+        None if opts.content_style == ContentStyle::Raw => {}
+        None => writeln!(writer)?,
     }
 
-    writeln!(writer, "{}", fence)?;
+    // --- Close Collapsible Wrapper ---
+    if opts.collapsible {
+        writeln!(writer)?;
+        writeln!(writer, "</details>")?;
+    }
 
     Ok(())
 }
 
+/// Returns the path shown in a file's header: its relative path, or just the
+/// filename if `filename_only_header` is set.
+pub(crate) fn header_path(file_info: &FileInfo, opts: &OutputConfig) -> PathBuf {
+    if opts.filename_only_header {
+        file_info
+            .relative_path
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| file_info.relative_path.clone()) // Fallback if no filename
+    } else {
+        file_info.relative_path.clone()
+    }
+}
+
+/// Returns the text of a file's `##` header for an already formatted path,
+/// numbered with `index` if given.
+///
+/// A base64-encoded binary file notes its original size as `(base64, 300 bytes)`.
+/// With `counts_in_header`, the file's counts follow the path as
+/// `(L:10 W:50 C:300)`, or `(Binary C:300)` for binary files.
+pub(crate) fn header_text(
+    file_info: &FileInfo,
+    formatted_path: &str,
+    index: Option<usize>,
+    opts: &OutputConfig,
+) -> String {
+    let mut text = match index {
+        Some(index) => format!("File {}: {}", index, formatted_path),
+        None => format!("File: {}", formatted_path),
+    };
+    if is_base64(file_info, opts) {
+        text.push_str(&format!(" (base64, {} bytes)", file_info.size));
+    }
+    if let Some(counts) = file_info.counts.filter(|_| opts.counts_in_header) {
+        if file_info.is_binary {
+            text.push_str(&format!(" (Binary C:{})", counts.characters));
+        } else {
+            text.push_str(&format!(
+                " (L:{} W:{} C:{})",
+                counts.lines, counts.words, counts.characters
+            ));
+        }
+    }
+    text
+}
+
+/// Writes one content line, with its line-number gutter if enabled.
+///
+/// If `wrap_width` is set and the line is too long, it is split into several
+/// output lines. Continuation lines get a blank gutter and start with
+/// `WRAP_CONTINUATION_MARKER`. Every output line starts with the prefix for
+/// the configured `content_style`.
+fn write_content_line(
+    writer: &mut dyn Write,
+    line_number: usize,
+    line: &str,
+    num_width: usize,
+    opts: &OutputConfig,
+) -> Result<()> {
+    let line = if opts.markdown_safe {
+        escape_fence_like(line)
+    } else {
+        Cow::Borrowed(line)
+    };
+    let segments = match opts.wrap_width {
+        Some(width) => wrap_line(&line, width),
+        None => vec![line.as_ref()],
+    };
+    let prefix = content_line_prefix(opts.content_style);
+    for (i, segment) in segments.iter().enumerate() {
+        write!(writer, "{}", prefix)?;
+        if opts.line_numbers {
+            if i == 0 {
+                // Format with dynamic padding: "{line_num:>width$} | {line_content}"
+                write!(writer, "{:>width$} | ", line_number, width = num_width)?;
+            } else {
+                write!(writer, "{:>width$} | ", "", width = num_width)?;
+            }
+        }
+        if i > 0 {
+            write!(writer, "{}", WRAP_CONTINUATION_MARKER)?;
+        }
+        writeln!(writer, "{}", segment)?;
+    }
+    Ok(())
+}
+
+/// Returns the text that starts every content line for a content style.
+fn content_line_prefix(style: ContentStyle) -> &'static str {
+    match style {
+        ContentStyle::CodeFence | ContentStyle::Raw => "",
+        ContentStyle::Blockquote => "> ",
+        ContentStyle::Indented => "    ",
+    }
+}
+
+/// Returns the number of backticks to use for a file's code fence.
+///
+/// With `markdown_safe`, or when `ticks_policy` calls for it, the fence is made
+/// longer than any backtick run in the content, so no content line can close it.
+fn fence_width(file_info: &FileInfo, opts: &OutputConfig) -> usize {
+    let configured = opts.num_ticks as usize;
+    let widen = opts.markdown_safe
+        || match opts.ticks_policy {
+            TicksPolicy::Fixed => false,
+            TicksPolicy::Auto => true,
+            TicksPolicy::PerLang => file_info.language().is_some_and(|lang| {
+                FENCE_PRONE_LANGUAGES
+                    .iter()
+                    .any(|prone| lang.eq_ignore_ascii_case(prone))
+            }),
+        };
+    if !widen {
+        return configured;
+    }
+    let longest_run = file_info
+        .processed_content
+        .as_deref()
+        .unwrap_or("")
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    configured.max(longest_run + 1)
+}
+
+/// Escapes a line that a Markdown renderer could mistake for a code fence.
+///
+/// A line is fence-like if, after leading whitespace, it starts with three or
+/// more backticks or tildes. `FENCE_ESCAPE` is inserted before the run, which
+/// stops lenient renderers from treating it as a fence regardless of its length.
+fn escape_fence_like(line: &str) -> Cow<'_, str> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+        let indent = &line[..line.len() - trimmed.len()];
+        Cow::Owned(format!("{}{}{}", indent, FENCE_ESCAPE, trimmed))
+    } else {
+        Cow::Borrowed(line)
+    }
+}
+
+/// Splits a line into segments that fit within `width` characters.
+///
+/// Continuation segments leave room for `WRAP_CONTINUATION_MARKER`. Each break
+/// is placed after the last whitespace within the limit; if there is none
+/// (or only leading indentation), the line is broken mid-word.
+fn wrap_line(line: &str, width: usize) -> Vec<&str> {
+    let continuation_width = width
+        .saturating_sub(WRAP_CONTINUATION_MARKER.chars().count())
+        .max(1);
+    let mut segments = Vec::new();
+    let mut rest = line;
+    let mut limit = width.max(1);
+    while let Some((hard_break, _)) = rest.char_indices().nth(limit) {
+        let split = rest[..hard_break]
+            .char_indices()
+            .rev()
+            .find(|&(i, c)| c.is_whitespace() && !rest[..i].trim().is_empty())
+            .map_or(hard_break, |(i, c)| i + c.len_utf8());
+        segments.push(&rest[..split]);
+        rest = &rest[split..];
+        limit = continuation_width;
+    }
+    segments.push(rest);
+    segments
+}
+
+/// Escapes the characters that are significant in HTML text content.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Formats the `<!-- dircat:file ... -->` comment written before a file block.
+///
+/// The path is always the full relative path with `/` separators, regardless
+/// of header options. Escaping `>` and `"` keeps it from ending the comment
+/// or the attribute early.
+fn anchor_comment(file_info: &FileInfo) -> String {
+    let path = file_info.relative_path.to_string_lossy().replace('\\', "/");
+    format!(
+        "<!-- dircat:file path=\"{}\" size=\"{}\" -->",
+        escape_html(&path).replace('"', "&quot;"),
+        file_info.size
+    )
+}
+
+/// Returns `true` if the file is binary and its content was encoded as base64.
+fn is_base64(file_info: &FileInfo, opts: &OutputConfig) -> bool {
+    file_info.is_binary && opts.binary_encoding == BinaryEncoding::Base64
+}
+
+/// Determines the code fence language for a file.
+///
+/// Base64-encoded binary files are always labeled `base64`. Otherwise, user
+/// overrides (keyed by lowercase filename, then lowercase extension) take
+/// precedence over `FileInfo::language`. Returns an empty string if nothing matches.
+fn fence_language<'a>(file_info: &'a FileInfo, opts: &'a OutputConfig) -> &'a str {
+    if is_base64(file_info, opts) {
+        return "base64";
+    }
+    if !opts.language_overrides.is_empty() {
+        let keys = [
+            file_info.relative_path.file_name(),
+            file_info.relative_path.extension(),
+        ];
+        for key in keys.into_iter().flatten() {
+            let key = key.to_string_lossy().to_lowercase();
+            if let Some(lang) = opts.language_overrides.get(&key) {
+                return lang;
+            }
+        }
+    }
+    file_info.language().unwrap_or("")
+}
+
 /// Calculates the required width for line numbers based on the total number of lines.
 fn calculate_line_number_width(line_count: usize, opts: &OutputConfig) -> usize {
     if !opts.line_numbers {
@@ -196,19 +454,74 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_file_block_anchor_comment() -> Result<()> {
+        let mut opts = create_test_opts(false, true, false); // Filename-only header
+        opts.anchor_comments = true;
+        let mut file_info = create_file_info("src/a\"b>.rs", Some("x"));
+        file_info.size = 1;
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(&mut writer, &file_info, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert!(output.starts_with(
+            "<!-- dircat:file path=\"src/a&quot;b&gt;.rs\" size=\"1\" -->\n## File: a\"b>.rs\n"
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_write_file_block_no_extension() -> Result<()> {
+        let opts = create_test_opts(false, false, false);
+        let file_info = create_file_info("LICENSE", Some("MIT"));
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(&mut writer, &file_info, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        let expected = "## File: LICENSE\n```\nMIT\n```\n"; // No language hint
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_file_block_well_known_filename() -> Result<()> {
         let opts = create_test_opts(false, false, false);
         let file_info = create_file_info("Makefile", Some("all: build"));
         let mut writer = Cursor::new(Vec::new());
         write_file_block(&mut writer, &file_info, &opts)?;
 
         let output = String::from_utf8(writer.into_inner())?;
-        let expected = "## File: Makefile\n```\nall: build\n```\n"; // No language hint
+        let expected = "## File: Makefile\n```makefile\nall: build\n```\n";
         assert_eq!(output, expected);
         Ok(())
     }
 
+    #[test]
+    fn test_write_file_block_language_overrides() -> Result<()> {
+        let mut opts = create_test_opts(false, false, false);
+        opts.language_overrides
+            .insert("rs".to_string(), "rust".to_string());
+        opts.language_overrides
+            .insert("makefile".to_string(), "make".to_string());
+
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(
+            &mut writer,
+            &create_file_info("src/Main.RS", Some("fn main() {}")),
+            &opts,
+        )?;
+        write_file_block(
+            &mut writer,
+            &create_file_info("Makefile", Some("all:")),
+            &opts,
+        )?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert!(output.contains("```rust\nfn main() {}"));
+        assert!(output.contains("```make\nall:"));
+        Ok(())
+    }
+
     #[test]
     fn test_write_file_block_line_numbers() -> Result<()> {
         let opts = create_test_opts(true, false, false); // Line numbers ON
@@ -250,6 +563,112 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_wrap_line() {
+        assert_eq!(wrap_line("short", 10), vec!["short"]);
+        assert_eq!(wrap_line("exactly10!", 10), vec!["exactly10!"]);
+        // Breaks after whitespace; continuation segments leave room for the marker.
+        assert_eq!(
+            wrap_line("alpha beta gamma delta", 10),
+            vec!["alpha ", "beta ", "gamma ", "delta"]
+        );
+        // No whitespace: hard break.
+        assert_eq!(
+            wrap_line("abcdefghijklmnop", 8),
+            vec!["abcdefgh", "ijklmn", "op"]
+        );
+        // Leading indentation alone is not a break point.
+        assert_eq!(wrap_line("    abcdefgh", 6), vec!["    ab", "cdef", "gh"]);
+        // Multi-byte characters are counted as single characters.
+        assert_eq!(wrap_line("ééééé", 3), vec!["ééé", "é", "é"]);
+    }
+
+    #[test]
+    fn test_write_file_block_wrap_with_line_numbers() -> Result<()> {
+        let mut opts = create_test_opts(true, false, false);
+        opts.wrap_width = Some(12);
+        let file_info = create_file_info(
+            "notes.txt",
+            Some(
+                "one two three four
+ok",
+            ),
+        );
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(&mut writer, &file_info, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        let expected =
+            "## File: notes.txt\n```txt\n    1 | one two \n      | ↪ three four\n    2 | ok\n```\n";
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fence_width_markdown_safe() {
+        let width = |content: Option<&str>, opts: &OutputConfig| {
+            fence_width(&create_file_info("a.txt", content), opts)
+        };
+        let mut opts = create_test_opts(false, false, false);
+        assert_eq!(width(Some("`````"), &opts), 3); // Not widened unless requested
+        opts.markdown_safe = true;
+        assert_eq!(width(None, &opts), 3);
+        assert_eq!(width(Some("no ticks"), &opts), 3);
+        assert_eq!(width(Some("a ``b`` c"), &opts), 3);
+        assert_eq!(width(Some("```\ncode\n```"), &opts), 4);
+        assert_eq!(width(Some("x\ntrailing``````"), &opts), 7);
+        opts.num_ticks = 8;
+        assert_eq!(width(Some("````"), &opts), 8);
+    }
+
+    #[test]
+    fn test_fence_width_ticks_policy() {
+        let nested = Some("```\ncode\n```");
+        let readme = create_file_info("README.md", nested);
+        let script = create_file_info("gen.py", nested);
+        let mut opts = create_test_opts(false, false, false);
+
+        opts.ticks_policy = TicksPolicy::Auto;
+        assert_eq!(fence_width(&readme, &opts), 4);
+        assert_eq!(fence_width(&script, &opts), 4);
+
+        opts.ticks_policy = TicksPolicy::PerLang;
+        assert_eq!(fence_width(&readme, &opts), 4);
+        assert_eq!(fence_width(&script, &opts), 3);
+        assert_eq!(
+            fence_width(&create_file_info("GUIDE.MDX", nested), &opts),
+            4
+        );
+    }
+
+    #[test]
+    fn test_escape_fence_like() {
+        assert_eq!(escape_fence_like("plain"), "plain");
+        assert_eq!(escape_fence_like("let s = \"```\";"), "let s = \"```\";");
+        assert_eq!(escape_fence_like("``not a fence"), "``not a fence");
+        assert_eq!(escape_fence_like("```rust"), "\u{200B}```rust");
+        assert_eq!(escape_fence_like("  ~~~~"), "  \u{200B}~~~~");
+    }
+
+    #[test]
+    fn test_write_file_block_markdown_safe_nested_fences() -> Result<()> {
+        let mut opts = create_test_opts(false, false, false);
+        opts.markdown_safe = true;
+        let content = "# Doc\n````md\n```rust\nfn main() {}\n```\n````\nend```";
+        let file_info = create_file_info("README.md", Some(content));
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(&mut writer, &file_info, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        let expected = "## File: README.md\n`````md\n# Doc\n\u{200B}````md\n\u{200B}```rust\nfn main() {}\n\u{200B}```\n\u{200B}````\nend```\n`````\n";
+        assert_eq!(output, expected);
+        // Every content line that starts with backticks is shorter than the fence
+        // and escaped, so only the opening and closing lines can act as fences.
+        let fence_lines: Vec<_> = output.lines().filter(|l| l.starts_with("```")).collect();
+        assert_eq!(fence_lines, vec!["`````md", "`````"]);
+        Ok(())
+    }
+
     #[test]
     fn test_write_file_block_filename_only() -> Result<()> {
         let opts = create_test_opts(false, true, false); // Filename only ON
@@ -303,4 +722,77 @@ mod tests {
         assert_eq!(output, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_write_file_block_blockquote_style() -> Result<()> {
+        let mut opts = create_test_opts(false, false, false);
+        opts.content_style = ContentStyle::Blockquote;
+        let file_info = create_file_info("notes.md", Some("First line\n\nThird line\n"));
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(&mut writer, &file_info, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        let expected = "## File: notes.md\n\n> First line\n> \n> Third line\n\n";
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_file_block_counts_in_header() -> Result<()> {
+        let mut opts = create_test_opts(false, false, false);
+        opts.counts_in_header = true;
+        let mut file_info = create_file_info("a.txt", Some("one two\nthree\n"));
+        file_info.counts = Some(crate::core_types::FileCounts {
+            lines: 2,
+            characters: 14,
+            words: 3,
+        });
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(&mut writer, &file_info, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert!(output.starts_with("## File: a.txt (L:2 W:3 C:14)\n```txt\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_file_block_raw_style() -> Result<()> {
+        let mut opts = create_test_opts(false, false, false);
+        opts.content_style = ContentStyle::Raw;
+        let file_info = create_file_info("main.rs", Some("fn main() {\n}\n"));
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(&mut writer, &file_info, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert_eq!(output, "## File: main.rs\nfn main() {\n}\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_file_block_indented_style_with_line_numbers() -> Result<()> {
+        let mut opts = create_test_opts(true, false, false);
+        opts.content_style = ContentStyle::Indented;
+        let file_info = create_file_info("main.rs", Some("fn main() {\n}"));
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(&mut writer, &file_info, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        let expected = "## File: main.rs\n\n        1 | fn main() {\n        2 | }\n\n";
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_file_block_collapsible() -> Result<()> {
+        let mut opts = create_test_opts(false, false, true);
+        opts.collapsible = true;
+        let file_info = create_file_info("src/a<b>.rs", Some("fn a() {}"));
+        let mut writer = Cursor::new(Vec::new());
+        write_file_block(&mut writer, &file_info, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        let expected = "<details>\n<summary>src/a&lt;b&gt;.rs</summary>\n\n## File: `src/a<b>.rs`\n```rs\nfn a() {}\n```\n\n</details>\n";
+        assert_eq!(output, expected);
+        Ok(())
+    }
 }
@@ -16,8 +16,8 @@ use std::path::Path;
 /// use dircat::OutputConfig;
 /// use std::path::Path;
 ///
-/// let opts_no_ticks = OutputConfig { backticks: false, filename_only_header: false, line_numbers: false, num_ticks: 3, summary: false, counts: false };
-/// let opts_with_ticks = OutputConfig { backticks: true, filename_only_header: false, line_numbers: false, num_ticks: 3, summary: false, counts: false };
+/// let opts_no_ticks = OutputConfig { backticks: false, filename_only_header: false, line_numbers: false, num_ticks: 3, summary: false, counts: false, ..Default::default() };
+/// let opts_with_ticks = OutputConfig { backticks: true, filename_only_header: false, line_numbers: false, num_ticks: 3, summary: false, counts: false, ..Default::default() };
 ///
 /// let path = Path::new("src/main.rs");
 ///
@@ -34,6 +34,22 @@ pub fn format_path_for_display(path: &Path, opts: &OutputConfig) -> String {
     }
 }
 
+/// Formats a symlink entry for display in the summary or a listing.
+///
+/// The link's own path is formatted like any other path; the target is shown
+/// as stored in the link, followed by a `(symlink)` marker.
+pub(crate) fn format_symlink_for_display(
+    path: &Path,
+    target: &Path,
+    opts: &OutputConfig,
+) -> String {
+    format!(
+        "{} -> {} (symlink)",
+        format_path_for_display(path, opts),
+        format_path_for_display(target, opts)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,6 +61,20 @@ mod tests {
         opts
     }
 
+    #[test]
+    fn test_format_symlink_for_display() {
+        let link = Path::new("docs/latest");
+        let target = Path::new("../v2");
+        assert_eq!(
+            format_symlink_for_display(link, target, &create_test_opts(false)),
+            "docs/latest -> ../v2 (symlink)"
+        );
+        assert_eq!(
+            format_symlink_for_display(link, target, &create_test_opts(true)),
+            "`docs/latest` -> `../v2` (symlink)"
+        );
+    }
+
     #[test]
     fn test_format_no_backticks() {
         let opts = create_test_opts(false);
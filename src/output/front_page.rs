@@ -0,0 +1,59 @@
+// src/output/front_page.rs
+
+//! Renders the optional front page that opens a Markdown bundle.
+
+use crate::config::Config;
+use crate::constants::FRONT_PAGE_HEADER;
+use crate::output::writer::current_date;
+use std::fmt::Write;
+
+/// Renders a front page describing how the bundle was produced.
+///
+/// The page lists the tool version, the input, and the generation date, followed
+/// by the effective configuration from [`Config::describe`]. The date is left out
+/// when `config.deterministic` is set, so repeated runs produce identical output.
+pub(crate) fn render_front_page(config: &Config) -> String {
+    let mut page = String::new();
+    // Writing to a String cannot fail.
+    let _ = writeln!(page, "# {}", FRONT_PAGE_HEADER);
+    let _ = writeln!(page);
+    let _ = writeln!(page, "- Version: dircat {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(page, "- Input: `{}`", config.input_path);
+    if !config.deterministic {
+        let _ = writeln!(page, "- Date: {}", current_date());
+    }
+    let _ = writeln!(page);
+    let _ = writeln!(page, "```text");
+    page.push_str(&config.describe());
+    if !page.ends_with('\n') {
+        page.push('\n');
+    }
+    let _ = writeln!(page, "```");
+    let _ = writeln!(page);
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigBuilder;
+
+    #[test]
+    fn test_front_page_lists_version_and_config() {
+        let config = ConfigBuilder::new()
+            .input_path("src")
+            .deterministic(true)
+            .build()
+            .unwrap();
+        let page = render_front_page(&config);
+        assert!(page.starts_with("# dircat bundle\n\n"));
+        assert!(page.contains(&format!(
+            "- Version: dircat {}\n",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(page.contains("- Input: `src`\n"));
+        assert!(!page.contains("- Date:"));
+        assert!(page.contains("```text\ninput_path = src\n"));
+        assert!(page.ends_with("```\n\n"));
+    }
+}
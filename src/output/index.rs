@@ -0,0 +1,120 @@
+// src/output/index.rs
+
+//! A formatter that writes a compact, one-line-per-file index without content.
+
+use crate::core_types::FileInfo;
+use crate::output::formatter::{format_path_for_display, format_symlink_for_display};
+use crate::output::{OutputConfig, OutputFormatter};
+use anyhow::Result;
+use log::debug;
+use std::io::Write;
+
+/// A formatter that writes one line per file with its path, size, and language.
+///
+/// This is used by `--index` for a quick overview of which files a run would
+/// include. The language is the code fence hint from
+/// [`FileInfo::language`], and is left out when there is none. Directory
+/// entries are skipped and symlinks are listed with their targets. A dry run
+/// is written the same way, since the index never needs file content.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::{IndexFormatter, OutputFormatter};
+/// use dircat::OutputConfig;
+/// use dircat::core_types::FileInfo;
+/// use std::path::PathBuf;
+/// # fn main() -> anyhow::Result<()> {
+///
+/// let file = |path: &str, size: u64| FileInfo {
+///     absolute_path: PathBuf::from("/abs").join(path),
+///     relative_path: PathBuf::from(path),
+///     size,
+///     ..Default::default()
+/// };
+/// let opts = OutputConfig {
+///     filename_only_header: false, line_numbers: false, backticks: false,
+///     num_ticks: 3, summary: false, counts: false, ..Default::default()
+/// };
+///
+/// let mut buffer = Vec::new();
+/// IndexFormatter.format(&[file("src/main.rs", 120), file("LICENSE", 1070)], &opts, &mut buffer)?;
+///
+/// assert_eq!(
+///     String::from_utf8(buffer)?,
+///     "src/main.rs (120 bytes, rs)\nLICENSE (1070 bytes)\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IndexFormatter;
+
+impl OutputFormatter for IndexFormatter {
+    fn format(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        debug!("Writing index for {} files...", files.len());
+        for file_info in files.iter().filter(|fi| !fi.is_dir) {
+            if let Some(target) = &file_info.symlink_target {
+                let entry = format_symlink_for_display(&file_info.relative_path, target, opts);
+                writeln!(writer, "{}", entry)?;
+                continue;
+            }
+            let path_str = format_path_for_display(&file_info.relative_path, opts);
+            match file_info.language() {
+                Some(language) => writeln!(
+                    writer,
+                    "{} ({} bytes, {})",
+                    path_str, file_info.size, language
+                )?,
+                None => writeln!(writer, "{} ({} bytes)", path_str, file_info.size)?,
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn format_dry_run(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        self.format(files, opts, writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{create_mock_file_info, create_mock_output_config};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_index_formatter_skips_dirs_and_lists_symlinks() -> Result<()> {
+        let opts = create_mock_output_config(false, false, false, false);
+        let mut dir = create_mock_file_info("src", 0);
+        dir.is_dir = true;
+        let mut link = create_mock_file_info("latest", 0);
+        link.symlink_target = Some(PathBuf::from("releases/v2"));
+        let files = [
+            dir,
+            create_mock_file_info("src/lib.rs", 42),
+            link,
+            create_mock_file_info("Makefile", 7),
+        ];
+
+        let mut output = Vec::new();
+        IndexFormatter.format_dry_run(&files, &opts, &mut output)?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            "src/lib.rs (42 bytes, rs)\nlatest -> releases/v2 (symlink)\nMakefile (7 bytes, makefile)\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,295 @@
+// src/output/json.rs
+
+//! A formatter that writes the processed files as a JSON document.
+
+use crate::config::escape_json;
+use crate::core_types::{FileCounts, FileInfo};
+use crate::output::{OutputConfig, OutputFormatter};
+use anyhow::Result;
+use log::debug;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// A formatter that writes the processed files as a structured JSON document.
+///
+/// The document is an object with a `files` array holding one object per file,
+/// with its `relative_path`, `size`, `is_binary`, and `processed_content`, plus
+/// a `counts` object when counts were computed. If `OutputConfig::summary` is
+/// set, a top-level `summary` object lists every file path along with the total
+/// file count and size (and total counts, with `OutputConfig::counts`). Files
+/// dropped to fit a token budget are listed in its `over_budget` array.
+///
+/// Paths always use `/` separators and are never wrapped in backticks. A dry
+/// run writes just a JSON array of the paths.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::{JsonFormatter, OutputFormatter};
+/// use dircat::OutputConfig;
+/// use dircat::core_types::FileInfo;
+/// use std::path::PathBuf;
+/// # fn main() -> anyhow::Result<()> {
+///
+/// let file = FileInfo {
+///     absolute_path: PathBuf::from("/abs/a.txt"),
+///     relative_path: PathBuf::from("a.txt"),
+///     size: 5,
+///     processed_content: Some("test\n".to_string()),
+///     ..Default::default()
+/// };
+/// let opts = OutputConfig {
+///     filename_only_header: false, line_numbers: false, backticks: false,
+///     num_ticks: 3, summary: false, counts: false, ..Default::default()
+/// };
+///
+/// let mut buffer = Vec::new();
+/// JsonFormatter.format(&[file], &opts, &mut buffer)?;
+///
+/// assert_eq!(
+///     String::from_utf8(buffer)?,
+///     "{\n  \"files\": [\n    {\"relative_path\": \"a.txt\", \"size\": 5, \"is_binary\": false, \
+///      \"processed_content\": \"test\\n\"}\n  ]\n}\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormatter;
+
+impl OutputFormatter for JsonFormatter {
+    fn format(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        self.format_with_over_budget(files, &[], opts, writer)
+    }
+
+    fn format_with_over_budget(
+        &self,
+        files: &[FileInfo],
+        over_budget: &[PathBuf],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        debug!("Writing JSON output for {} files...", files.len());
+        let entries: Vec<String> = files
+            .iter()
+            .filter(|fi| !fi.is_dir && !fi.is_symlink())
+            .map(file_object)
+            .collect();
+        writeln!(writer, "{{")?;
+        write!(writer, "  \"files\": {}", array(&entries, "    "))?;
+        if opts.summary {
+            let listed: Vec<&FileInfo> = files.iter().filter(|fi| !fi.is_dir).collect();
+            write!(
+                writer,
+                ",\n  \"summary\": {}",
+                summary_object(&listed, over_budget, opts)
+            )?;
+        }
+        writeln!(writer, "\n}}")?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn format_dry_run(
+        &self,
+        files: &[FileInfo],
+        _opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        let paths: Vec<String> = files
+            .iter()
+            .filter(|fi| !fi.is_dir)
+            .map(|fi| path_string(&fi.relative_path))
+            .collect();
+        writeln!(writer, "{}", array(&paths, "  "))?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Quotes and escapes a string as a JSON string literal.
+fn string(value: &str) -> String {
+    format!("\"{}\"", escape_json(value))
+}
+
+/// Renders a path as a JSON string with `/` separators.
+fn path_string(path: &Path) -> String {
+    string(&path.to_string_lossy().replace('\\', "/"))
+}
+
+/// Renders a JSON array with one item per line, each prefixed by `indent`.
+fn array(items: &[String], indent: &str) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+    let closing = &indent[..indent.len().saturating_sub(2)];
+    let body: Vec<String> = items
+        .iter()
+        .map(|item| format!("{}{}", indent, item))
+        .collect();
+    format!("[\n{}\n{}]", body.join(",\n"), closing)
+}
+
+fn counts_object(counts: &FileCounts) -> String {
+    format!(
+        "{{\"lines\": {}, \"characters\": {}, \"words\": {}}}",
+        counts.lines, counts.characters, counts.words
+    )
+}
+
+fn file_object(file_info: &FileInfo) -> String {
+    let content = match &file_info.processed_content {
+        Some(content) => string(content),
+        None => "null".to_string(),
+    };
+    let mut fields = vec![
+        format!(
+            "\"relative_path\": {}",
+            path_string(&file_info.relative_path)
+        ),
+        format!("\"size\": {}", file_info.size),
+        format!("\"is_binary\": {}", file_info.is_binary),
+        format!("\"processed_content\": {}", content),
+    ];
+    if let Some(counts) = &file_info.counts {
+        fields.push(format!("\"counts\": {}", counts_object(counts)));
+    }
+    format!("{{{}}}", fields.join(", "))
+}
+
+fn summary_object(files: &[&FileInfo], over_budget: &[PathBuf], opts: &OutputConfig) -> String {
+    let paths: Vec<String> = files
+        .iter()
+        .map(|fi| path_string(&fi.relative_path))
+        .collect();
+    let mut fields = vec![
+        format!("\"files\": [{}]", paths.join(", ")),
+        format!("\"total_files\": {}", files.len()),
+        format!(
+            "\"total_size\": {}",
+            files.iter().map(|fi| fi.size).sum::<u64>()
+        ),
+    ];
+    if opts.counts {
+        let mut total = FileCounts::default();
+        for counts in files.iter().filter_map(|fi| fi.counts.as_ref()) {
+            total.lines += counts.lines;
+            total.characters += counts.characters;
+            total.words += counts.words;
+        }
+        fields.push(format!("\"counts\": {}", counts_object(&total)));
+    }
+    if !over_budget.is_empty() {
+        let dropped: Vec<String> = over_budget.iter().map(|path| path_string(path)).collect();
+        fields.push(format!("\"over_budget\": [{}]", dropped.join(", ")));
+    }
+    format!("{{{}}}", fields.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{create_mock_file_info, create_mock_output_config};
+
+    #[test]
+    fn test_json_formatter_escapes_content_and_skips_dirs() -> Result<()> {
+        let opts = create_mock_output_config(true, false, false, false);
+        let mut dir = create_mock_file_info("src", 0);
+        dir.is_dir = true;
+        let mut a = create_mock_file_info("src/a.rs", 10);
+        a.processed_content = Some("let s = \"x\";\n".to_string());
+        let mut bin = create_mock_file_info("img.bin", 3);
+        bin.is_binary = true;
+
+        let mut output = Vec::new();
+        JsonFormatter.format(&[dir, a, bin], &opts, &mut output)?;
+
+        let expected = concat!(
+            "{\n  \"files\": [\n",
+            "    {\"relative_path\": \"src/a.rs\", \"size\": 10, \"is_binary\": false, ",
+            "\"processed_content\": \"let s = \\\"x\\\";\\n\"},\n",
+            "    {\"relative_path\": \"img.bin\", \"size\": 3, \"is_binary\": true, ",
+            "\"processed_content\": null}\n",
+            "  ]\n}\n"
+        );
+        assert_eq!(String::from_utf8(output)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_formatter_with_summary_and_counts() -> Result<()> {
+        let mut opts = create_mock_output_config(false, false, false, true);
+        opts.counts = true;
+        let mut a = create_mock_file_info("a.txt", 4);
+        a.processed_content = Some("A B\n".to_string());
+        a.counts = Some(FileCounts {
+            lines: 1,
+            characters: 4,
+            words: 2,
+        });
+        let mut b = create_mock_file_info("b.txt", 2);
+        b.processed_content = Some("C\n".to_string());
+        b.counts = Some(FileCounts {
+            lines: 1,
+            characters: 2,
+            words: 1,
+        });
+
+        let mut output = Vec::new();
+        JsonFormatter.format(&[a, b], &opts, &mut output)?;
+
+        let output = String::from_utf8(output)?;
+        assert!(output.contains(
+            "\"processed_content\": \"A B\\n\", \"counts\": {\"lines\": 1, \"characters\": 4, \"words\": 2}}"
+        ));
+        assert!(output.ends_with(concat!(
+            "  ],\n  \"summary\": {\"files\": [\"a.txt\", \"b.txt\"], \"total_files\": 2, ",
+            "\"total_size\": 6, \"counts\": {\"lines\": 2, \"characters\": 6, \"words\": 3}}\n}\n"
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_formatter_lists_over_budget_files_in_summary() -> Result<()> {
+        let opts = create_mock_output_config(false, false, false, true);
+        let mut a = create_mock_file_info("a.txt", 2);
+        a.processed_content = Some("A\n".to_string());
+        let dropped = [PathBuf::from("b.txt"), PathBuf::from("sub/c.txt")];
+
+        let mut output = Vec::new();
+        JsonFormatter.format_with_over_budget(&[a], &dropped, &opts, &mut output)?;
+
+        let output = String::from_utf8(output)?;
+        assert!(output.ends_with(concat!(
+            "\"summary\": {\"files\": [\"a.txt\"], \"total_files\": 1, \"total_size\": 2, ",
+            "\"over_budget\": [\"b.txt\", \"sub/c.txt\"]}\n}\n"
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn test_json_formatter_dry_run_lists_paths() -> Result<()> {
+        let opts = create_mock_output_config(false, false, false, false);
+        let files = [
+            create_mock_file_info("src/lib.rs", 42),
+            create_mock_file_info("README.md", 7),
+        ];
+
+        let mut output = Vec::new();
+        JsonFormatter.format_dry_run(&files, &opts, &mut output)?;
+        assert_eq!(
+            String::from_utf8(output)?,
+            "[\n  \"src/lib.rs\",\n  \"README.md\"\n]\n"
+        );
+
+        let mut output = Vec::new();
+        JsonFormatter.format_dry_run(&[], &opts, &mut output)?;
+        assert_eq!(String::from_utf8(output)?, "[]\n");
+        Ok(())
+    }
+}
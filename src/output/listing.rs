@@ -0,0 +1,104 @@
+// src/output/listing.rs
+
+//! A formatter that lists files with their sizes instead of emitting content.
+
+use crate::core_types::FileInfo;
+use crate::output::formatter::{format_path_for_display, format_symlink_for_display};
+use crate::output::{dry_run, OutputConfig, OutputFormatter};
+use anyhow::Result;
+use log::debug;
+use std::io::Write;
+
+/// A formatter that writes one line per file with its path and size in bytes.
+///
+/// This is used by `--only-binary` to audit non-text assets, where emitting the
+/// content itself would be meaningless.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::{ListingFormatter, OutputFormatter};
+/// use dircat::OutputConfig;
+/// use dircat::core_types::FileInfo;
+/// use std::path::PathBuf;
+/// # fn main() -> anyhow::Result<()> {
+///
+/// let file = FileInfo {
+///     absolute_path: PathBuf::from("/abs/logo.png"),
+///     relative_path: PathBuf::from("assets/logo.png"),
+///     size: 2048,
+///     is_binary: true,
+///     ..Default::default()
+/// };
+/// let opts = OutputConfig {
+///     filename_only_header: false, line_numbers: false, backticks: false,
+///     num_ticks: 3, summary: false, counts: false, ..Default::default()
+/// };
+///
+/// let mut buffer = Vec::new();
+/// ListingFormatter.format(&[file], &opts, &mut buffer)?;
+///
+/// assert_eq!(String::from_utf8(buffer)?, "- assets/logo.png (2048 bytes)\n");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListingFormatter;
+
+impl OutputFormatter for ListingFormatter {
+    fn format(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        debug!("Writing listing for {} files...", files.len());
+        for file_info in files {
+            if let Some(target) = &file_info.symlink_target {
+                let entry = format_symlink_for_display(&file_info.relative_path, target, opts);
+                writeln!(writer, "- {}", entry)?;
+                continue;
+            }
+            let path_str = format_path_for_display(&file_info.relative_path, opts);
+            writeln!(writer, "- {} ({} bytes)", path_str, file_info.size)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn format_dry_run(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        let file_refs: Vec<&FileInfo> = files.iter().collect();
+        dry_run::write_dry_run_output(writer, &file_refs, opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{create_mock_file_info, create_mock_output_config};
+
+    #[test]
+    fn test_listing_formatter_lists_paths_and_sizes() -> Result<()> {
+        let opts = create_mock_output_config(true, false, false, false);
+        let mut file = create_mock_file_info("img/a.png", 1234);
+        file.processed_content = Some("ignored".to_string());
+
+        let mut output = Vec::new();
+        ListingFormatter.format(
+            &[file, create_mock_file_info("b.bin", 0)],
+            &opts,
+            &mut output,
+        )?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            "- `img/a.png` (1234 bytes)\n- `b.bin` (0 bytes)\n"
+        );
+        Ok(())
+    }
+}
@@ -4,18 +4,39 @@
 //! a default `MarkdownFormatter` implementation, and helpers for writing to
 //! different destinations like stdout, files, or the clipboard.
 
-use crate::config::{Config, OutputConfig};
+use crate::config::{Config, OutputConfig, OutputFormat};
 use crate::core_types::FileInfo;
 use anyhow::Result;
 use log::debug;
 use std::io::Write;
+use std::path::PathBuf;
 
+pub mod delimited;
 pub mod dry_run;
 pub mod file_block;
 pub mod formatter;
+pub(crate) mod front_page;
 pub mod header;
+pub mod index;
+pub mod json;
+pub mod listing;
+pub mod newline;
+pub mod roundtrip;
+pub(crate) mod stats;
 pub mod summary;
+mod tee;
+mod toc;
+pub mod unpack;
 pub mod writer;
+pub mod xml;
+
+pub use delimited::DelimitedFormatter;
+pub use index::IndexFormatter;
+pub use json::JsonFormatter;
+pub use listing::ListingFormatter;
+pub use roundtrip::RoundtripFormatter;
+pub use tee::format_to_many;
+pub use xml::XmlFormatter;
 
 /// Creates an `OutputConfig` from a reference to the main `Config`.
 ///
@@ -39,7 +60,35 @@ pub mod writer;
 /// ```
 impl From<&Config> for OutputConfig {
     fn from(config: &Config) -> Self {
-        config.output
+        config.output.clone()
+    }
+}
+
+/// Selects the formatter used by `run` for the given configuration.
+///
+/// Returns an `IndexFormatter` for `--index`, a `JsonFormatter` or
+/// `XmlFormatter` for `--format json` or `xml`, a `ListingFormatter` for
+/// `--only-binary`, a `RoundtripFormatter` for `--roundtrip`, a
+/// `DelimitedFormatter` if a delimiter was configured, and otherwise the
+/// default `MarkdownFormatter`.
+pub(crate) fn formatter_for(config: &Config) -> Box<dyn OutputFormatter> {
+    if config.index {
+        return Box::new(IndexFormatter);
+    }
+    match config.format {
+        OutputFormat::Json => return Box::new(JsonFormatter),
+        OutputFormat::Xml => return Box::new(XmlFormatter),
+        OutputFormat::Markdown => {}
+    }
+    if config.processing.only_binary {
+        return Box::new(ListingFormatter);
+    }
+    if config.roundtrip {
+        return Box::new(RoundtripFormatter);
+    }
+    match &config.delimiter {
+        Some(delimiter) => Box::new(DelimitedFormatter::new(delimiter.clone())),
+        None => Box::new(MarkdownFormatter),
     }
 }
 
@@ -81,7 +130,7 @@ impl From<&Config> for OutputConfig {
 /// let files = vec![file];
 /// let opts = OutputConfig {
 ///     filename_only_header: false, line_numbers: false, backticks: false,
-///     num_ticks: 3, summary: false, counts: false
+///     num_ticks: 3, summary: false, counts: false, ..Default::default()
 /// };
 /// let mut buffer = Vec::new();
 ///
@@ -102,6 +151,28 @@ pub trait OutputFormatter: Send + Sync {
     fn format(&self, files: &[FileInfo], opts: &OutputConfig, writer: &mut dyn Write)
         -> Result<()>;
 
+    /// Formats the processed files along with the files dropped to fit a token
+    /// budget (see `DircatResult::over_budget`).
+    ///
+    /// The default implementation ignores `over_budget` and calls
+    /// [`format`](Self::format). Formatters that write a single document, like
+    /// JSON and XML, override it to list the dropped files in their summary.
+    ///
+    /// # Arguments
+    /// * `files` - A slice of `FileInfo` structs, sorted in the desired output order.
+    /// * `over_budget` - The relative paths of the files dropped to fit the budget.
+    /// * `opts` - Configuration options for formatting the output.
+    /// * `writer` - A mutable reference to a type that implements `std::io::Write`.
+    fn format_with_over_budget(
+        &self,
+        files: &[FileInfo],
+        _over_budget: &[PathBuf],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        self.format(files, opts, writer)
+    }
+
     /// Formats the discovered files for a dry run.
     ///
     /// # Arguments
@@ -138,14 +209,14 @@ pub trait OutputFormatter: Send + Sync {
 ///     processed_content: Some("test".to_string()),
 ///     ..Default::default()
 /// };
-/// let result = DircatResult { files: vec![file], resolved_path: PathBuf::from("/abs") };
+/// let result = DircatResult::new(vec![file], PathBuf::from("/abs"));
 ///
 /// // 2. Instantiate the formatter and configure output options.
 /// use dircat::OutputFormatter; // Import the trait to use its methods
 /// let formatter = MarkdownFormatter;
 /// let opts = OutputConfig {
 ///     filename_only_header: false, line_numbers: false, backticks: false,
-///     num_ticks: 3, summary: false, counts: false
+///     num_ticks: 3, summary: false, counts: false, ..Default::default()
 /// };
 ///
 /// // 3. Format the result into a buffer.
@@ -175,17 +246,40 @@ impl OutputFormatter for MarkdownFormatter {
             return Ok(());
         }
 
+        if opts.summary_only_paths {
+            let all_processed_files: Vec<&FileInfo> =
+                files.iter().filter(|fi| !fi.is_dir).collect();
+            summary::write_path_list(writer, &all_processed_files, opts)?;
+            writer.flush()?;
+            return Ok(());
+        }
+
         header::write_global_header(writer)?;
+        if let Some(front_page) = &opts.front_page {
+            writer.write_all(front_page.as_bytes())?;
+        }
 
-        let all_files_iter = files.iter();
+        // Directory and symlink entries have no content, so they are not given a block.
+        let block_files: Vec<&FileInfo> = files
+            .iter()
+            .filter(|fi| !fi.is_dir && !fi.is_symlink())
+            .collect();
+
+        if opts.toc && !block_files.is_empty() {
+            toc::write_toc(writer, &block_files, opts)?;
+        }
 
         let mut first_block = true;
-        for file_info in all_files_iter {
+        for (i, file_info) in block_files.into_iter().enumerate() {
             if !first_block {
-                // Add a blank line separator between file blocks
-                writeln!(writer)?;
+                // Separate file blocks, with a blank line by default
+                write!(writer, "{}", opts.block_separator)?;
+            }
+            let index = opts.index_headers.then_some(i + 1);
+            file_block::write_indexed_file_block(writer, file_info, index, opts)?;
+            if opts.flush_per_file {
+                writer.flush()?;
             }
-            file_block::write_file_block(writer, file_info, opts)?;
             first_block = false;
         }
 
@@ -193,7 +287,8 @@ impl OutputFormatter for MarkdownFormatter {
             if !first_block {
                 writeln!(writer)?;
             }
-            let all_processed_files: Vec<&FileInfo> = files.iter().collect();
+            let all_processed_files: Vec<&FileInfo> =
+                files.iter().filter(|fi| !fi.is_dir).collect();
             summary::write_summary(writer, &all_processed_files, opts)?;
         }
 
@@ -218,6 +313,7 @@ impl OutputFormatter for MarkdownFormatter {
 pub(crate) mod tests {
     // Make module public within the crate for use by siblings
     use super::*;
+    use crate::config::{ContentStyle, IfExists, TicksPolicy};
     use crate::core_types::{FileCounts, FileInfo};
     use std::path::PathBuf;
 
@@ -233,7 +329,27 @@ pub(crate) mod tests {
             line_numbers,
             summary,
             counts: false, // Default to false for these tests unless specified
+            counts_in_header: false,
+            summary_only_paths: false,
+            top_files: None,
+            content_hash_report: false,
+            front_page: None,
             num_ticks: 3,
+            markdown_safe: false,
+            content_style: ContentStyle::default(),
+            ticks_policy: TicksPolicy::default(),
+            toc: false,
+            index_headers: false,
+            block_separator: "\n".to_string(),
+            flush_per_file: false,
+            collapsible: false,
+            binary_encoding: crate::config::BinaryEncoding::Lossy,
+            anchor_comments: false,
+            wrap_width: None,
+            write_bom: false,
+            output_newline: None,
+            if_exists: IfExists::default(),
+            language_overrides: Default::default(),
         }
     }
 
@@ -431,4 +547,47 @@ pub(crate) mod tests {
         assert_eq!(output_str, expected);
         Ok(())
     }
+
+    // A writer that records how many times it was flushed.
+    #[derive(Default)]
+    struct FlushCountingWriter {
+        buffer: Vec<u8>,
+        flushes: usize,
+    }
+
+    impl Write for FlushCountingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.buffer.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_markdown_formatter_flush_per_file() -> Result<()> {
+        let mut file1 = create_mock_file_info("a.txt", 1);
+        file1.processed_content = Some("A".to_string());
+        let mut file2 = create_mock_file_info("b.txt", 1);
+        file2.processed_content = Some("B".to_string());
+        let files = vec![file1, file2];
+
+        // Default: only the final flush.
+        let opts = create_mock_output_config(false, false, false, false);
+        let mut writer = FlushCountingWriter::default();
+        MarkdownFormatter.format(&files, &opts, &mut writer)?;
+        assert_eq!(writer.flushes, 1);
+
+        // Per-file: one flush per block plus the final flush.
+        let opts = OutputConfig {
+            flush_per_file: true,
+            ..create_mock_output_config(false, false, false, false)
+        };
+        let mut writer = FlushCountingWriter::default();
+        MarkdownFormatter.format(&files, &opts, &mut writer)?;
+        assert_eq!(writer.flushes, 3);
+        Ok(())
+    }
 }
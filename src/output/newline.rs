@@ -0,0 +1,119 @@
+// src/output/newline.rs
+
+//! A writer that rewrites every line ending in the output to one style.
+
+use crate::config::Newline;
+use std::io::{self, Write};
+
+/// Wraps a writer and converts `\r\n`, `\r`, and `\n` to a single line ending.
+///
+/// The conversion is streaming: a `\r` at the end of one write is held back
+/// until the next byte shows whether it starts a `\r\n` pair. A held-back `\r`
+/// is written out as a line ending on `flush`.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::config::Newline;
+/// use dircat::output::newline::NewlineWriter;
+/// use std::io::Write;
+///
+/// let mut writer = NewlineWriter::new(Vec::new(), Newline::Crlf);
+/// writer.write_all(b"a\nb\r\nc\r").unwrap();
+/// writer.flush().unwrap();
+/// assert_eq!(writer.into_inner(), b"a\r\nb\r\nc\r\n");
+/// ```
+pub struct NewlineWriter<W: Write> {
+    inner: W,
+    newline: &'static [u8],
+    pending_cr: bool,
+}
+
+impl<W: Write> NewlineWriter<W> {
+    /// Creates a writer that writes line endings in the given style to `inner`.
+    pub fn new(inner: W, newline: Newline) -> Self {
+        let newline: &'static [u8] = match newline.resolve() {
+            Newline::Crlf => b"\r\n",
+            _ => b"\n",
+        };
+        Self {
+            inner,
+            newline,
+            pending_cr: false,
+        }
+    }
+
+    /// Returns the wrapped writer. A held-back `\r` that was not flushed is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for NewlineWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut converted = Vec::with_capacity(buf.len() + buf.len() / 16);
+        let mut bytes = buf.iter();
+        if self.pending_cr {
+            self.pending_cr = false;
+            converted.extend_from_slice(self.newline);
+            // A '\n' right after the held-back '\r' completes the same line ending.
+            if buf.first() == Some(&b'\n') {
+                bytes.next();
+            }
+        }
+        let mut bytes = bytes.peekable();
+        while let Some(&b) = bytes.next() {
+            match b {
+                b'\r' if bytes.peek().is_none() => self.pending_cr = true,
+                b'\r' => {
+                    converted.extend_from_slice(self.newline);
+                    bytes.next_if_eq(&&b'\n');
+                }
+                b'\n' => converted.extend_from_slice(self.newline),
+                b => converted.push(b),
+            }
+        }
+        self.inner.write_all(&converted)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.pending_cr {
+            self.pending_cr = false;
+            self.inner.write_all(self.newline)?;
+        }
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(chunks: &[&[u8]], newline: Newline) -> Vec<u8> {
+        let mut writer = NewlineWriter::new(Vec::new(), newline);
+        for chunk in chunks {
+            writer.write_all(chunk).unwrap();
+        }
+        writer.flush().unwrap();
+        writer.into_inner()
+    }
+
+    #[test]
+    fn test_mixed_endings_to_lf() {
+        assert_eq!(convert(&[b"a\r\nb\rc\nd"], Newline::Lf), b"a\nb\nc\nd");
+    }
+
+    #[test]
+    fn test_crlf_split_across_writes() {
+        assert_eq!(
+            convert(&[b"a\r", b"\nb\r", b"c"], Newline::Crlf),
+            b"a\r\nb\r\nc"
+        );
+    }
+
+    #[test]
+    fn test_crlf_is_not_doubled() {
+        assert_eq!(convert(&[b"a\r\n\r\n"], Newline::Crlf), b"a\r\n\r\n");
+    }
+}
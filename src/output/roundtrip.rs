@@ -0,0 +1,271 @@
+// src/output/roundtrip.rs
+
+//! A reversible output format that can be split back into the original files.
+//!
+//! Each file is written as:
+//!
+//! ```text
+//! >>>>> DIRCAT FILE path=src/main.rs size=12
+//! fn main() {}
+//! <<<<< END path=src/main.rs
+//! ```
+//!
+//! `size` is the exact number of content bytes that follow the header line, so
+//! content containing marker-like lines is still read back correctly. A newline
+//! is always written between the content and the end line and is not part of
+//! the content. Text outside file blocks, such as a summary, is ignored by
+//! [`unpack`].
+
+use crate::core_types::FileInfo;
+use crate::errors::{Error, Result};
+use crate::output::unpack::write_unpacked_file;
+use crate::output::{dry_run, OutputConfig, OutputFormatter};
+use anyhow::anyhow;
+use log::debug;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The start of the line that opens a file block.
+pub const FILE_MARKER: &str = ">>>>> DIRCAT FILE";
+/// The start of the line that closes a file block.
+pub const END_MARKER: &str = "<<<<< END";
+
+/// A formatter that writes each file's processed content between machine-readable
+/// markers, so the output can be split back apart with [`unpack`].
+///
+/// Headers, line numbers, and the summary are not written, since they would
+/// not survive a round trip.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::{OutputFormatter, RoundtripFormatter};
+/// use dircat::OutputConfig;
+/// use dircat::core_types::FileInfo;
+/// use std::path::PathBuf;
+/// # fn main() -> anyhow::Result<()> {
+///
+/// let file = FileInfo {
+///     absolute_path: PathBuf::from("/abs/a.txt"),
+///     relative_path: PathBuf::from("a.txt"),
+///     processed_content: Some("test".to_string()),
+///     ..Default::default()
+/// };
+/// let mut buffer = Vec::new();
+/// RoundtripFormatter.format(&[file], &OutputConfig::default(), &mut buffer)?;
+///
+/// assert_eq!(
+///     String::from_utf8(buffer)?,
+///     ">>>>> DIRCAT FILE path=a.txt size=4\ntest\n<<<<< END path=a.txt\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RoundtripFormatter;
+
+impl OutputFormatter for RoundtripFormatter {
+    fn format(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> anyhow::Result<()> {
+        debug!("Starting roundtrip output generation...");
+        for file_info in files.iter().filter(|fi| !fi.is_dir && !fi.is_symlink()) {
+            let Some(content) = &file_info.processed_content else {
+                log::warn!(
+                    "Content not available for file: {}",
+                    file_info.absolute_path.display()
+                );
+                continue;
+            };
+            let path = file_info.relative_path.to_string_lossy().replace('\\', "/");
+            writeln!(
+                writer,
+                "{} path={} size={}",
+                FILE_MARKER,
+                path,
+                content.len()
+            )?;
+            writer.write_all(content.as_bytes())?;
+            writeln!(writer)?;
+            writeln!(writer, "{} path={}", END_MARKER, path)?;
+            if opts.flush_per_file {
+                writer.flush()?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn format_dry_run(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> anyhow::Result<()> {
+        let file_refs: Vec<&FileInfo> = files.iter().collect();
+        dry_run::write_dry_run_output(writer, &file_refs, opts)
+    }
+}
+
+/// Recreates the files in `packed`, output written by [`RoundtripFormatter`],
+/// under `dest`.
+///
+/// Missing directories are created and existing files are overwritten. Returns
+/// the relative paths of the files written, in order.
+///
+/// # Errors
+/// Returns an error if a file block is malformed or truncated, if a path is
+/// absolute or contains `..`, or if a file cannot be written. Files unpacked
+/// before the error are left in place.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::roundtrip::unpack;
+///
+/// # fn main() -> dircat::errors::Result<()> {
+/// let dir = tempfile::tempdir().unwrap();
+/// let packed = b">>>>> DIRCAT FILE path=src/a.rs size=5\nfn a\n\n<<<<< END path=src/a.rs\n";
+///
+/// let written = unpack(packed, dir.path())?;
+/// assert_eq!(written, vec![std::path::PathBuf::from("src/a.rs")]);
+/// assert_eq!(std::fs::read(dir.path().join("src/a.rs")).unwrap(), b"fn a\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn unpack(packed: &[u8], dest: &Path) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = find_file_marker(packed, pos) {
+        let header_end =
+            find_newline(packed, start).ok_or_else(|| malformed("unterminated file header"))?;
+        let header = String::from_utf8_lossy(&packed[start..header_end]);
+        let (path, size) = parse_header(&header)?;
+
+        let content_start = header_end + 1;
+        let content_end = content_start
+            .checked_add(size)
+            .filter(|&end| end <= packed.len())
+            .ok_or_else(|| malformed(&format!("content of '{}' is truncated", path)))?;
+
+        let end_line = format!("\n{} path={}", END_MARKER, path);
+        if !packed[content_end..].starts_with(end_line.as_bytes()) {
+            return Err(malformed(&format!("missing end line for '{}'", path)));
+        }
+
+        written.push(write_unpacked_file(
+            dest,
+            path,
+            &packed[content_start..content_end],
+        )?);
+
+        pos = content_end + end_line.len();
+    }
+    Ok(written)
+}
+
+/// Returns the offset of the next line, at or after `pos`, that starts a file block.
+fn find_file_marker(packed: &[u8], mut pos: usize) -> Option<usize> {
+    while pos < packed.len() {
+        if packed[pos..].starts_with(FILE_MARKER.as_bytes()) {
+            return Some(pos);
+        }
+        pos = find_newline(packed, pos)? + 1;
+    }
+    None
+}
+
+fn find_newline(packed: &[u8], from: usize) -> Option<usize> {
+    packed[from..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map(|offset| from + offset)
+}
+
+/// Splits a header line into its path and content size.
+fn parse_header(header: &str) -> Result<(&str, usize)> {
+    let fields = header
+        .strip_prefix(FILE_MARKER)
+        .and_then(|rest| rest.strip_prefix(" path="))
+        .ok_or_else(|| malformed(&format!("invalid file header '{}'", header)))?;
+    // The size is the last field, so a path containing " size=" is still parsed correctly.
+    let (path, size) = fields
+        .rsplit_once(" size=")
+        .ok_or_else(|| malformed(&format!("file header without size '{}'", header)))?;
+    let size = size
+        .parse()
+        .map_err(|_| malformed(&format!("invalid size in file header '{}'", header)))?;
+    Ok((path, size))
+}
+
+fn malformed(reason: &str) -> Error {
+    Error::Generic(anyhow!("Invalid roundtrip input: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{create_mock_file_info, create_mock_output_config};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn pack(files: &[FileInfo]) -> Vec<u8> {
+        let opts = create_mock_output_config(false, false, false, false);
+        let mut buffer = Vec::new();
+        RoundtripFormatter
+            .format(files, &opts, &mut buffer)
+            .unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_round_trip_preserves_content_exactly() -> Result<()> {
+        let mut a = create_mock_file_info("src/a.rs", 0);
+        a.processed_content = Some("no trailing newline".to_string());
+        let mut b = create_mock_file_info("docs/b.md", 0);
+        // Content that looks like the markers must not end the block early.
+        b.processed_content = Some(format!("{} path=x size=1\n{}\n\n", END_MARKER, FILE_MARKER));
+        let mut empty = create_mock_file_info("empty.txt", 0);
+        empty.processed_content = Some(String::new());
+
+        let mut packed = b"leading text is ignored\n".to_vec();
+        packed.extend(pack(&[a, b.clone(), empty]));
+        packed.extend(b"\n---\nProcessed Files: (3)\n");
+
+        let dir = tempdir().unwrap();
+        let written = unpack(&packed, dir.path())?;
+        assert_eq!(written.len(), 3);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("src/a.rs")).unwrap(),
+            "no trailing newline"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("docs/b.md")).unwrap(),
+            b.processed_content.unwrap()
+        );
+        assert_eq!(fs::read(dir.path().join("empty.txt")).unwrap(), b"");
+        Ok(())
+    }
+
+    #[test]
+    fn test_unpack_rejects_path_traversal() {
+        let dir = tempdir().unwrap();
+        let packed = b">>>>> DIRCAT FILE path=../evil size=1\nx\n<<<<< END path=../evil\n";
+        let err = unpack(packed, &dir.path().join("out")).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Refusing to unpack unsafe path '../evil'"));
+        assert!(!dir.path().join("evil").exists());
+    }
+
+    #[test]
+    fn test_unpack_rejects_truncated_content() {
+        let dir = tempdir().unwrap();
+        let packed = b">>>>> DIRCAT FILE path=a.txt size=100\nshort\n";
+        let err = unpack(packed, dir.path()).unwrap_err();
+        assert!(err.to_string().contains("truncated"));
+    }
+}
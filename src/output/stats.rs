@@ -0,0 +1,182 @@
+// src/output/stats.rs
+
+//! Writes the human-readable end-of-run reports enabled by `--stats` and
+//! `--report-empty-dirs`.
+
+use crate::constants::{CHARS_PER_TOKEN_ESTIMATE, EMPTY_DIRS_HEADER, STATS_HEADER};
+use crate::core_types::FileInfo;
+use anyhow::Result;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Totals over the files included in a run.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct RunStats {
+    pub files: usize,
+    pub bytes: usize,
+    /// The size on disk of the included files, i.e. the bytes read to produce them.
+    pub bytes_read: u64,
+    pub lines: usize,
+    pub estimated_tokens: usize,
+}
+
+impl RunStats {
+    /// Sums the processed content of `files`.
+    ///
+    /// Directory and symlink entries are not counted. Files without processed
+    /// content (as in a dry run) contribute their size on disk but no lines or
+    /// tokens.
+    pub(crate) fn from_files(files: &[FileInfo]) -> Self {
+        let mut stats = Self::default();
+        let mut chars = 0;
+        for file_info in files.iter().filter(|fi| !fi.is_dir && !fi.is_symlink()) {
+            stats.files += 1;
+            stats.bytes_read += file_info.size;
+            match &file_info.processed_content {
+                Some(content) => {
+                    stats.bytes += content.len();
+                    stats.lines += content.lines().count();
+                    chars += content.chars().count();
+                }
+                None => stats.bytes += file_info.size as usize,
+            }
+        }
+        stats.estimated_tokens = chars.div_ceil(CHARS_PER_TOKEN_ESTIMATE);
+        stats
+    }
+}
+
+/// Writes the stats report for `files`, which took `elapsed` to produce, of
+/// which `processing_time` was spent reading and processing them.
+pub(crate) fn write_stats(
+    writer: &mut dyn Write,
+    files: &[FileInfo],
+    elapsed: Duration,
+    processing_time: Duration,
+) -> Result<()> {
+    let stats = RunStats::from_files(files);
+    writeln!(writer, "{}", STATS_HEADER)?;
+    writeln!(writer, "Files included:   {}", stats.files)?;
+    writeln!(writer, "Total bytes:      {}", stats.bytes)?;
+    writeln!(writer, "Total lines:      {}", stats.lines)?;
+    writeln!(writer, "Estimated tokens: ~{}", stats.estimated_tokens)?;
+    writeln!(writer, "Elapsed:          {:.2}s", elapsed.as_secs_f64())?;
+    writeln!(
+        writer,
+        "Throughput:       {}",
+        format_throughput(stats.bytes_read, processing_time)
+    )?;
+    Ok(())
+}
+
+/// Formats `bytes` read over `elapsed` as `Processed 1.2GB in 3.4s (352.9MB/s)`.
+///
+/// The rate is left out when no measurable time passed.
+fn format_throughput(bytes: u64, elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    let mut line = format!("Processed {} in {:.1}s", format_bytes(bytes as f64), secs);
+    if secs > 0.0 {
+        line.push_str(&format!(" ({}/s)", format_bytes(bytes as f64 / secs)));
+    }
+    line
+}
+
+/// Formats a byte count with a decimal unit and one decimal place, e.g. `1.2GB`.
+fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1000.0 {
+        return format!("{:.0}B", bytes);
+    }
+    let mut value = bytes / 1000.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if value < 1000.0 {
+            break;
+        }
+        value /= 1000.0;
+        unit = next;
+    }
+    format!("{:.1}{}", value, unit)
+}
+
+/// Writes the list of directories that contain no included files.
+pub(crate) fn write_empty_dirs(writer: &mut dyn Write, empty_dirs: &[PathBuf]) -> Result<()> {
+    writeln!(writer, "{}", EMPTY_DIRS_HEADER)?;
+    if empty_dirs.is_empty() {
+        writeln!(writer, "(none)")?;
+    }
+    for dir in empty_dirs {
+        writeln!(writer, "{}/", dir.display())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::create_mock_file_info;
+
+    #[test]
+    fn test_run_stats_from_files() {
+        let mut file1 = create_mock_file_info("a.rs", 100);
+        file1.processed_content = Some("fn a() {}\nfn b() {}\n".to_string());
+        let mut file2 = create_mock_file_info("b.txt", 100);
+        file2.processed_content = Some("abc".to_string());
+        let mut dir = create_mock_file_info("src", 0);
+        dir.is_dir = true;
+
+        let stats = RunStats::from_files(&[file1, file2, dir]);
+        assert_eq!(
+            stats,
+            RunStats {
+                files: 2,
+                bytes: 23,
+                bytes_read: 200,
+                lines: 3,
+                estimated_tokens: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn test_run_stats_uses_size_without_content() {
+        let mut file = create_mock_file_info("a.rs", 42);
+        file.processed_content = None;
+        let stats = RunStats::from_files(&[file]);
+        assert_eq!(stats.bytes, 42);
+        assert_eq!(stats.lines, 0);
+    }
+
+    #[test]
+    fn test_write_stats() -> Result<()> {
+        let mut file = create_mock_file_info("a.rs", 4);
+        file.processed_content = Some("abcd".to_string());
+        let mut buffer = Vec::new();
+        write_stats(
+            &mut buffer,
+            &[file],
+            Duration::from_millis(1500),
+            Duration::from_millis(500),
+        )?;
+
+        let output = String::from_utf8(buffer)?;
+        assert_eq!(
+            output,
+            "--- dircat stats ---\nFiles included:   1\nTotal bytes:      4\nTotal lines:      1\nEstimated tokens: ~1\nElapsed:          1.50s\nThroughput:       Processed 4B in 0.5s (8B/s)\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_throughput() {
+        assert_eq!(
+            format_throughput(1_200_000_000, Duration::from_millis(3400)),
+            "Processed 1.2GB in 3.4s (352.9MB/s)"
+        );
+        assert_eq!(
+            format_throughput(999, Duration::ZERO),
+            "Processed 999B in 0.0s"
+        );
+    }
+}
@@ -2,17 +2,21 @@
 
 use crate::constants;
 use crate::core_types::FileInfo;
-use crate::output::formatter::format_path_for_display;
+use crate::output::formatter::{format_path_for_display, format_symlink_for_display};
 use crate::output::OutputConfig;
 use anyhow::Result;
 use log::debug;
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use std::io::Write;
+use std::path::PathBuf;
 
 /// Writes the summary section (list of processed files, optionally with counts)
 /// to the output writer.
 ///
 /// The list of files is printed in the order they are provided (which matches
-/// the order they appear in the main document).
+/// the order they appear in the main document). Files whose content looks
+/// minified end with a `(minified)` marker.
 pub(crate) fn write_summary(
     writer: &mut dyn Write,
     files: &[&FileInfo], // Takes refs to avoid cloning
@@ -29,7 +33,16 @@ pub(crate) fn write_summary(
 
     // The files slice is already sorted in the correct processing order.
     for file_info in files {
+        if file_info.is_symlink() {
+            writeln!(writer, "- {}", format_entry(file_info, opts))?;
+            continue;
+        }
         let path_str = format_path_for_display(&file_info.relative_path, opts);
+        let marker = if file_info.is_minified {
+            " (minified)"
+        } else {
+            ""
+        };
         if opts.counts {
             if let Some(counts) = file_info.counts {
                 if file_info.is_binary {
@@ -38,23 +51,163 @@ pub(crate) fn write_summary(
                 } else {
                     writeln!(
                         writer,
-                        "- {} (L:{} C:{} W:{})",
-                        path_str, counts.lines, counts.characters, counts.words
+                        "- {} (L:{} C:{} W:{}){}",
+                        path_str, counts.lines, counts.characters, counts.words, marker
                     )?;
                 }
             } else {
                 // Should not happen if config.counts is true and processing succeeded,
                 // but handle defensively.
                 log::warn!("Counts requested but not available for: {}", path_str);
-                writeln!(writer, "- {} (Counts not available)", path_str)?;
+                writeln!(writer, "- {} (Counts not available){}", path_str, marker)?;
             }
         } else {
-            writeln!(writer, "- {}", path_str)?;
+            writeln!(writer, "- {}{}", path_str, marker)?;
         }
     }
+
+    if let Some(n) = opts.top_files {
+        write_largest_files(writer, files, n, opts)?;
+    }
+    if opts.content_hash_report {
+        write_duplicate_files(writer, files, opts)?;
+    }
+    Ok(())
+}
+
+/// Writes the files dropped to fit the token budget, after a blank line.
+///
+/// `run` appends this after the formatter's own summary, since the dropped
+/// files are not part of the formatted result.
+pub(crate) fn write_over_budget(
+    writer: &mut dyn Write,
+    dropped: &[PathBuf],
+    opts: &OutputConfig,
+) -> Result<()> {
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{}: ({})",
+        constants::OVER_BUDGET_HEADER_PREFIX,
+        dropped.len()
+    )?;
+    for path in dropped {
+        writeln!(writer, "- {}", format_path_for_display(path, opts))?;
+    }
+    Ok(())
+}
+
+/// Writes the groups of files with identical content, after a blank line.
+///
+/// Groups are ordered by the bytes their extra copies take up, largest first,
+/// and each group lists its files in output order. Files without a content
+/// hash (binary files, directories, symlinks) are not grouped.
+fn write_duplicate_files(
+    writer: &mut dyn Write,
+    files: &[&FileInfo],
+    opts: &OutputConfig,
+) -> Result<()> {
+    let mut groups: Vec<Vec<&FileInfo>> = Vec::new();
+    let mut group_of_hash: HashMap<&str, usize> = HashMap::new();
+    for file_info in files {
+        let Some(hash) = file_info.content_hash.as_deref() else {
+            continue;
+        };
+        match group_of_hash.get(hash) {
+            Some(&group) => groups[group].push(file_info),
+            None => {
+                group_of_hash.insert(hash, groups.len());
+                groups.push(vec![file_info]);
+            }
+        }
+    }
+    groups.retain(|group| group.len() > 1);
+    groups.sort_by_key(|group| Reverse(group[0].size * (group.len() as u64 - 1)));
+
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{}: ({})",
+        constants::DUPLICATE_FILES_HEADER_PREFIX,
+        groups.len()
+    )?;
+    for group in groups {
+        let paths: Vec<String> = group
+            .iter()
+            .map(|fi| format_path_for_display(&fi.relative_path, opts))
+            .collect();
+        writeln!(
+            writer,
+            "- {} copies of {} bytes: {}",
+            group.len(),
+            group[0].size,
+            paths.join(", ")
+        )?;
+    }
+    Ok(())
+}
+
+/// Writes the `n` largest files by size, largest first, after a blank line.
+///
+/// Files of equal size keep their output order. Line counts are shown when
+/// counts were calculated.
+fn write_largest_files(
+    writer: &mut dyn Write,
+    files: &[&FileInfo],
+    n: usize,
+    opts: &OutputConfig,
+) -> Result<()> {
+    let mut largest: Vec<&FileInfo> = files
+        .iter()
+        .copied()
+        .filter(|fi| !fi.is_symlink())
+        .collect();
+    largest.sort_by_key(|fi| Reverse(fi.size));
+    largest.truncate(n);
+
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{}: ({})",
+        constants::LARGEST_FILES_HEADER_PREFIX,
+        largest.len()
+    )?;
+    for file_info in largest {
+        let path_str = format_path_for_display(&file_info.relative_path, opts);
+        match file_info.counts {
+            Some(counts) if opts.counts && !file_info.is_binary => writeln!(
+                writer,
+                "- {} ({} bytes, {} lines)",
+                path_str, file_info.size, counts.lines
+            )?,
+            _ => writeln!(writer, "- {} ({} bytes)", path_str, file_info.size)?,
+        }
+    }
+    Ok(())
+}
+
+/// Writes only the summary's list of paths, one `- path` line per file,
+/// without the separator, header, or counts.
+pub(crate) fn write_path_list(
+    writer: &mut dyn Write,
+    files: &[&FileInfo],
+    opts: &OutputConfig,
+) -> Result<()> {
+    debug!("Writing path list for {} files...", files.len());
+    for file_info in files {
+        writeln!(writer, "- {}", format_entry(file_info, opts))?;
+    }
     Ok(())
 }
 
+/// Formats a summary entry's path, including the target for symlinks.
+fn format_entry(file_info: &FileInfo, opts: &OutputConfig) -> String {
+    match &file_info.symlink_target {
+        Some(target) => format_symlink_for_display(&file_info.relative_path, target, opts),
+        None => format_path_for_display(&file_info.relative_path, opts),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,6 +241,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_summary_symlink() -> Result<()> {
+        let opts = create_test_opts(true, false);
+        let file = create_file_info("a.rs", Some(FileCounts::default()), false);
+        let link = FileInfo {
+            relative_path: PathBuf::from("latest"),
+            symlink_target: Some(PathBuf::from("releases/v2")),
+            ..Default::default()
+        };
+        let files = vec![&file, &link];
+        let mut writer = Cursor::new(Vec::new());
+        write_summary(&mut writer, &files, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert!(output.ends_with("- a.rs (L:0 C:0 W:0)\n- latest -> releases/v2 (symlink)\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_summary_marks_minified_files() -> Result<()> {
+        let opts = create_test_opts(false, false);
+        let mut bundle = create_file_info("app.min.js", None, false);
+        bundle.is_minified = true;
+        let file = create_file_info("a.rs", None, false);
+        let files = vec![&bundle, &file];
+        let mut writer = Cursor::new(Vec::new());
+        write_summary(&mut writer, &files, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert!(output.ends_with("- app.min.js (minified)\n- a.rs\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_path_list() -> Result<()> {
+        let opts = create_test_opts(true, true); // Counts are ignored
+        let fi1 = create_file_info("src/main.rs", Some(FileCounts::default()), false);
+        let fi2 = create_file_info("logo.png", Some(FileCounts::default()), true);
+        let files = vec![&fi1, &fi2];
+        let mut writer = Cursor::new(Vec::new());
+        write_path_list(&mut writer, &files, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert_eq!(output, "- `src/main.rs`\n- `logo.png`\n");
+        Ok(())
+    }
+
     #[test]
     fn test_summary_empty() -> Result<()> {
         let opts = create_test_opts(false, false);
@@ -222,4 +422,53 @@ mod tests {
         assert_eq!(output, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_summary_largest_files() -> Result<()> {
+        let mut opts = create_test_opts(false, false);
+        opts.top_files = Some(2);
+        let mut small = create_file_info("small.rs", None, false);
+        small.size = 10;
+        let mut big = create_file_info("big.json", None, false);
+        big.size = 5000;
+        let medium = create_file_info("medium.md", None, false); // size 100
+        let files = vec![&small, &big, &medium];
+        let mut writer = Cursor::new(Vec::new());
+        write_summary(&mut writer, &files, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert!(output.ends_with(
+            "- medium.md\n\nLargest Files: (2)\n- big.json (5000 bytes)\n- medium.md (100 bytes)\n"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_summary_duplicate_files() -> Result<()> {
+        let mut opts = create_test_opts(false, false);
+        opts.content_hash_report = true;
+        let with_hash = |path: &str, hash: &str, size: u64| FileInfo {
+            size,
+            content_hash: Some(hash.to_string()),
+            ..create_file_info(path, None, false)
+        };
+        let small_a = with_hash("a.txt", "aa", 10);
+        let big_a = with_hash("b.json", "bb", 500);
+        let small_b = with_hash("c.txt", "aa", 10);
+        let small_c = with_hash("d.txt", "aa", 10);
+        let big_b = with_hash("e.json", "bb", 500);
+        let unique = with_hash("f.md", "ff", 900);
+        let binary = create_file_info("g.bin", None, true);
+        let files = vec![
+            &small_a, &big_a, &small_b, &small_c, &big_b, &unique, &binary,
+        ];
+        let mut writer = Cursor::new(Vec::new());
+        write_summary(&mut writer, &files, &opts)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert!(output.ends_with(
+            "\n\nDuplicate Files: (2)\n- 2 copies of 500 bytes: b.json, e.json\n- 3 copies of 10 bytes: a.txt, c.txt, d.txt\n"
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,103 @@
+// src/output/tee.rs
+
+//! Writes one set of files through several formatters in a single pass.
+
+use crate::core_types::FileInfo;
+use crate::output::{OutputConfig, OutputFormatter};
+use anyhow::{Context, Result};
+use std::io::Write;
+
+/// Formats the same files with each formatter, writing to the writer paired with it.
+///
+/// [`OutputFormatter::format`] takes a single writer, so each target is a
+/// `(formatter, writer)` pair. This produces several artifacts, such as a
+/// Markdown document and a manifest, from one `execute` result without running
+/// the pipeline again. Targets are written in order; the first failure stops
+/// the run and reports which target failed.
+///
+/// # Errors
+/// Returns an error if any formatter fails to write its output.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::{format_to_many, ListingFormatter, MarkdownFormatter};
+/// use dircat::OutputConfig;
+/// use dircat::core_types::FileInfo;
+/// use std::path::PathBuf;
+/// # fn main() -> anyhow::Result<()> {
+///
+/// let file = FileInfo {
+///     absolute_path: PathBuf::from("/abs/a.txt"),
+///     relative_path: PathBuf::from("a.txt"),
+///     size: 4,
+///     processed_content: Some("test".to_string()),
+///     ..Default::default()
+/// };
+/// let opts = OutputConfig::default();
+/// let (mut markdown, mut listing) = (Vec::new(), Vec::new());
+///
+/// format_to_many(
+///     &[file],
+///     &opts,
+///     &mut [(&MarkdownFormatter, &mut markdown), (&ListingFormatter, &mut listing)],
+/// )?;
+///
+/// assert!(String::from_utf8(markdown)?.contains("## File: a.txt"));
+/// assert_eq!(String::from_utf8(listing)?, "- a.txt (4 bytes)\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn format_to_many(
+    files: &[FileInfo],
+    opts: &OutputConfig,
+    targets: &mut [(&dyn OutputFormatter, &mut dyn Write)],
+) -> Result<()> {
+    let total = targets.len();
+    for (i, (formatter, writer)) in targets.iter_mut().enumerate() {
+        formatter
+            .format(files, opts, *writer)
+            .with_context(|| format!("Failed to write output {} of {}", i + 1, total))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{create_mock_file_info, create_mock_output_config};
+    use crate::output::MarkdownFormatter;
+    use std::io;
+
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_format_to_many_reports_failing_target() {
+        let opts = create_mock_output_config(false, false, false, false);
+        let mut file = create_mock_file_info("a.rs", 2);
+        file.processed_content = Some("fn a() {}".to_string());
+        let mut first = Vec::new();
+        let mut failing = FailingWriter;
+
+        let err = format_to_many(
+            &[file],
+            &opts,
+            &mut [
+                (&MarkdownFormatter, &mut first),
+                (&MarkdownFormatter, &mut failing),
+            ],
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Failed to write output 2 of 2");
+        assert!(String::from_utf8(first).unwrap().contains("fn a() {}"));
+    }
+}
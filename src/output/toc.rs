@@ -0,0 +1,128 @@
+// src/output/toc.rs
+
+//! Writes a table of contents linking to each file block.
+
+use crate::constants::TOC_HEADER;
+use crate::core_types::FileInfo;
+use crate::output::file_block::{header_path, header_text};
+use crate::output::formatter::format_path_for_display;
+use crate::output::OutputConfig;
+use anyhow::Result;
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Writes a `## Table of Contents` section with one link per file block.
+///
+/// Each link points to the anchor a GitHub-style renderer generates for the
+/// file's `## File:` header, so `files` must be the file blocks in the order
+/// they are written.
+pub(crate) fn write_toc(
+    writer: &mut dyn Write,
+    files: &[&FileInfo],
+    opts: &OutputConfig,
+) -> Result<()> {
+    writeln!(writer, "## {}", TOC_HEADER)?;
+    writeln!(writer)?;
+    let mut anchors = AnchorSet::default();
+    // The TOC header comes first in the document, so its anchor is taken first.
+    anchors.claim(TOC_HEADER);
+    for (i, file_info) in files.iter().enumerate() {
+        let path_str = format_path_for_display(&header_path(file_info, opts), opts);
+        let index = opts.index_headers.then_some(i + 1);
+        let anchor = anchors.claim(&header_text(file_info, &path_str, index, opts));
+        writeln!(writer, "- [{}](#{})", path_str, anchor)?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// Tracks the anchors already used in a document, so duplicate headers get
+/// the same `-1`, `-2`, ... suffixes GitHub gives them.
+#[derive(Default)]
+struct AnchorSet {
+    used: HashSet<String>,
+}
+
+impl AnchorSet {
+    /// Returns the anchor for the next header with this text and marks it used.
+    fn claim(&mut self, header: &str) -> String {
+        let base = github_anchor(header);
+        let mut anchor = base.clone();
+        let mut suffix = 0;
+        while self.used.contains(&anchor) {
+            suffix += 1;
+            anchor = format!("{}-{}", base, suffix);
+        }
+        self.used.insert(anchor.clone());
+        anchor
+    }
+}
+
+/// Converts header text to a GitHub-style anchor.
+///
+/// The text is lowercased, spaces become hyphens, and everything other than
+/// letters, digits, hyphens, and underscores is dropped.
+fn github_anchor(header: &str) -> String {
+    header
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{create_mock_file_info, create_mock_output_config};
+
+    #[test]
+    fn test_github_anchor() {
+        assert_eq!(github_anchor("File: src/main.rs"), "file-srcmainrs");
+        assert_eq!(
+            github_anchor("File: `my_app/lib-v2.rs`"),
+            "file-my_applib-v2rs"
+        );
+        assert_eq!(github_anchor("Table of Contents"), "table-of-contents");
+    }
+
+    #[test]
+    fn test_anchor_set_suffixes_duplicates() {
+        let mut anchors = AnchorSet::default();
+        assert_eq!(anchors.claim("File: a.rs"), "file-ars");
+        assert_eq!(anchors.claim("File: a.rs"), "file-ars-1");
+        assert_eq!(anchors.claim("File: a.rs"), "file-ars-2");
+    }
+
+    #[test]
+    fn test_write_toc() -> Result<()> {
+        let opts = create_mock_output_config(true, true, false, false); // Backticks, filename only
+        let file1 = create_mock_file_info("src/main.rs", 10);
+        let file2 = create_mock_file_info("tests/main.rs", 10);
+        let mut buffer = Vec::new();
+        write_toc(&mut buffer, &[&file1, &file2], &opts)?;
+
+        let output = String::from_utf8(buffer)?;
+        let expected = "## Table of Contents\n\n- [`main.rs`](#file-mainrs)\n- [`main.rs`](#file-mainrs-1)\n\n";
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_toc_with_index_headers() -> Result<()> {
+        let mut opts = create_mock_output_config(false, false, false, false);
+        opts.index_headers = true;
+        let file1 = create_mock_file_info("a.rs", 10);
+        let file2 = create_mock_file_info("b.rs", 10);
+        let mut buffer = Vec::new();
+        write_toc(&mut buffer, &[&file1, &file2], &opts)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.contains("- [a.rs](#file-1-ars)\n"));
+        assert!(output.contains("- [b.rs](#file-2-brs)\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+// src/output/unpack.rs
+
+//! Materializes a dircat bundle, Markdown or `--roundtrip` output, back into files.
+
+use crate::constants::FENCE_ESCAPE;
+use crate::errors::{io_error_with_path, Error, Result};
+use crate::output::roundtrip::{self, FILE_MARKER};
+use anyhow::anyhow;
+use base64::prelude::*;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Recreates the files in `packed` under `dest`, detecting the format.
+///
+/// Output containing a `>>>>> DIRCAT FILE` line is read with
+/// [`roundtrip::unpack`], which restores content byte for byte. Anything else
+/// is read as Markdown with [`unpack_markdown`].
+///
+/// # Errors
+/// Returns an error if the input is malformed, a path is unsafe, or a file
+/// cannot be written.
+pub fn unpack_bundle(packed: &[u8], dest: &Path) -> Result<Vec<PathBuf>> {
+    let is_roundtrip = packed
+        .split(|&b| b == b'\n')
+        .any(|line| line.starts_with(FILE_MARKER.as_bytes()));
+    if is_roundtrip {
+        roundtrip::unpack(packed, dest)
+    } else {
+        unpack_markdown(&String::from_utf8_lossy(packed), dest)
+    }
+}
+
+/// Recreates the files in dircat's Markdown output under `dest`.
+///
+/// Each `## File: path` header (or `## File N: path` with index headers)
+/// followed by a fenced code block becomes one file. Everything else, such as
+/// a table of contents or the summary, is ignored. Missing directories are
+/// created and existing files are overwritten. Returns the relative paths of
+/// the files written, in order.
+///
+/// Markdown does not record everything needed to restore a file exactly, so:
+///
+/// - Every non-empty file is written with a trailing newline, except binary
+///   files written with `--binary-encoding base64`, which are decoded exactly.
+/// - A file containing a line that matches its own code fence ends early;
+///   produce the bundle with `--markdown-safe` or a larger `--ticks` to avoid this.
+/// - Output made with `--filename-only` has no directories to restore.
+/// - Line numbers, wrapping, and the blockquote or indented content styles
+///   are not reversed; blocks without a code fence are skipped.
+/// - Content filters such as comment removal were applied before output, so
+///   their changes are not undone.
+///
+/// # Errors
+/// Returns an error if a code fence is not closed, a path is absolute or
+/// contains `..`, or a file cannot be written. Files unpacked before the error
+/// are left in place.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::unpack::unpack_markdown;
+///
+/// # fn main() -> dircat::errors::Result<()> {
+/// let dir = tempfile::tempdir().unwrap();
+/// let markdown = "## File: `src/a.rs`\n```rs\nfn a() {}\n```\n";
+///
+/// let written = unpack_markdown(markdown, dir.path())?;
+/// assert_eq!(written, vec![std::path::PathBuf::from("src/a.rs")]);
+/// assert_eq!(std::fs::read_to_string(dir.path().join("src/a.rs")).unwrap(), "fn a() {}\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn unpack_markdown(markdown: &str, dest: &Path) -> Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    let mut lines = markdown.lines();
+    while let Some(line) = lines.next() {
+        let Some(path) = header_path(line) else {
+            continue;
+        };
+        let Some(fence_line) = lines.next().filter(|l| opening_fence(l).is_some()) else {
+            log::warn!("Skipping '{}': no code fence after its header", path);
+            continue;
+        };
+        let fence = opening_fence(fence_line).unwrap_or_default();
+        let mut content = String::new();
+        let mut closed = false;
+        for content_line in lines.by_ref() {
+            if content_line.trim_end() == fence {
+                closed = true;
+                break;
+            }
+            content.push_str(&unescape_fence_like(content_line));
+            content.push('\n');
+        }
+        if !closed {
+            return Err(Error::Generic(anyhow!(
+                "Invalid Markdown bundle: code fence for '{}' is not closed",
+                path
+            )));
+        }
+        let bytes = if &fence_line[fence.len()..] == "base64" {
+            let encoded: String = content.split_whitespace().collect();
+            BASE64_STANDARD.decode(encoded).map_err(|e| {
+                Error::Generic(anyhow!(
+                    "Invalid Markdown bundle: base64 content of '{}' is malformed: {}",
+                    path,
+                    e
+                ))
+            })?
+        } else {
+            content.into_bytes()
+        };
+        written.push(write_unpacked_file(dest, path, &bytes)?);
+    }
+    Ok(written)
+}
+
+/// Writes one unpacked file below `dest`, creating its parent directories.
+///
+/// # Errors
+/// Returns an error if `path` is empty, absolute, or contains `..`, or if the
+/// file cannot be written.
+pub(crate) fn write_unpacked_file(dest: &Path, path: &str, content: &[u8]) -> Result<PathBuf> {
+    let relative = PathBuf::from(path);
+    let is_safe = !path.is_empty()
+        && relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !is_safe {
+        return Err(Error::Generic(anyhow!(
+            "Refusing to unpack unsafe path '{}'",
+            path
+        )));
+    }
+    let target = dest.join(&relative);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| io_error_with_path(e, parent))?;
+    }
+    fs::write(&target, content).map_err(|e| io_error_with_path(e, &target))?;
+    Ok(relative)
+}
+
+/// Returns the path in a `## File: path` or `## File N: path` header line.
+///
+/// Counts added by `counts_in_header`, like ` (L:2 W:3 C:14)`, and the size note of
+/// a base64 block, like ` (base64, 300 bytes)`, are not part of the path.
+fn header_path(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("## File")?;
+    let (index, path) = rest.split_once(": ")?;
+    if !index.is_empty() && !index.trim_start().chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let mut path = path;
+    while let Some((rest, note)) = path.rsplit_once(" (") {
+        let is_note = note.ends_with(')')
+            && (note.starts_with("L:")
+                || note.starts_with("Binary C:")
+                || note.starts_with("base64, "));
+        if !is_note {
+            break;
+        }
+        path = rest;
+    }
+    Some(
+        path.strip_prefix('`')
+            .and_then(|p| p.strip_suffix('`'))
+            .unwrap_or(path),
+    )
+}
+
+/// Returns the backtick run of an opening code fence line (e.g., "```rs").
+fn opening_fence(line: &str) -> Option<&str> {
+    let ticks = line.len() - line.trim_start_matches('`').len();
+    (ticks >= 3 && !line[ticks..].contains('`')).then(|| &line[..ticks])
+}
+
+/// Removes the marker dircat inserts before fence-like content lines.
+fn unescape_fence_like(line: &str) -> String {
+    let trimmed = line.trim_start();
+    match trimmed.strip_prefix(FENCE_ESCAPE) {
+        Some(rest) if rest.starts_with("```") || rest.starts_with("~~~") => {
+            format!("{}{}", &line[..line.len() - trimmed.len()], rest)
+        }
+        _ => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{create_mock_file_info, create_mock_output_config};
+    use crate::output::{MarkdownFormatter, OutputFormatter};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_header_path() {
+        assert_eq!(header_path("## File: src/a.rs"), Some("src/a.rs"));
+        assert_eq!(header_path("## File 12: `src/a.rs`"), Some("src/a.rs"));
+        assert_eq!(header_path("## File: a.txt (L:2 W:3 C:14)"), Some("a.txt"));
+        assert_eq!(
+            header_path("## File: a.png (base64, 5 bytes) (Binary C:5)"),
+            Some("a.png")
+        );
+        assert_eq!(header_path("## Files: a"), None);
+        assert_eq!(header_path("## Table of Contents"), None);
+    }
+
+    #[test]
+    fn test_unpack_markdown_output_of_formatter() -> Result<()> {
+        let mut opts = create_mock_output_config(true, false, false, true);
+        opts.toc = true;
+        opts.index_headers = true;
+        opts.markdown_safe = true;
+        let mut a = create_mock_file_info("src/a.rs", 0);
+        a.processed_content = Some("fn a() {}\n".to_string());
+        let mut doc = create_mock_file_info("docs/README.md", 0);
+        doc.processed_content = Some("# Title\n```sh\necho hi\n```\n".to_string());
+        let mut buffer = Vec::new();
+        MarkdownFormatter
+            .format(&[a, doc.clone()], &opts, &mut buffer)
+            .unwrap();
+
+        let dir = tempdir().unwrap();
+        let written = unpack_bundle(&buffer, dir.path())?;
+        assert_eq!(
+            written,
+            vec![PathBuf::from("src/a.rs"), PathBuf::from("docs/README.md")]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("src/a.rs")).unwrap(),
+            "fn a() {}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("docs/README.md")).unwrap(),
+            doc.processed_content.unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unpack_markdown_rejects_path_traversal() {
+        let dir = tempdir().unwrap();
+        let err = unpack_markdown("## File: /etc/passwd\n```\nx\n```\n", dir.path()).unwrap_err();
+        assert!(err.to_string().contains("unsafe path '/etc/passwd'"));
+    }
+
+    #[test]
+    fn test_unpack_markdown_rejects_unclosed_fence() {
+        let dir = tempdir().unwrap();
+        let err = unpack_markdown("## File: a.txt\n```\nx\n", dir.path()).unwrap_err();
+        assert!(err.to_string().contains("not closed"));
+    }
+}
@@ -6,15 +6,125 @@
 //! based on the user's configuration and to handle finalization steps, such as
 //! copying the output to the clipboard.
 
-use crate::config::{Config, OutputDestination};
+use crate::config::{Config, IfExists, OutputDestination};
 #[cfg(feature = "clipboard")]
 use crate::errors::ClipboardError;
+use crate::errors::ConfigError;
+use crate::output::newline::NewlineWriter;
 #[cfg(feature = "clipboard")]
 use anyhow::anyhow;
 use anyhow::Result;
-use std::fs::File;
+use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The file extension substituted for `{ext}` in an output template.
+pub const OUTPUT_TEMPLATE_EXTENSION: &str = "md";
+
+/// The UTF-8 byte order mark, written at the start of file output when `--bom` is set.
+pub(crate) const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Renders an output file path from a template containing placeholders.
+///
+/// Supported placeholders:
+/// - `{input_name}`: The name of the input directory, file, or repository (without `.git`).
+/// - `{date}`: The current UTC date as `YYYY-MM-DD`.
+/// - `{index}`: The 1-based index of the output file. Always `1` for a single output.
+/// - `{ext}`: The output file extension (`md`).
+///
+/// # Errors
+/// Returns `ConfigError::InvalidValue` if the template contains an unknown or
+/// unterminated placeholder.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::writer::render_output_template;
+/// use std::path::PathBuf;
+///
+/// let path = render_output_template("out/{input_name}-{index}.{ext}", "https://github.com/user/repo.git", 1).unwrap();
+/// assert_eq!(path, PathBuf::from("out/repo-1.md"));
+///
+/// assert!(render_output_template("{unknown}.md", ".", 1).is_err());
+/// ```
+pub fn render_output_template(
+    template: &str,
+    input_path: &str,
+    index: usize,
+) -> std::result::Result<PathBuf, ConfigError> {
+    let invalid = |reason: String| ConfigError::InvalidValue {
+        option: "--output-template".to_string(),
+        reason,
+    };
+
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .map(|i| start + i)
+            .ok_or_else(|| invalid(format!("unterminated placeholder in '{}'", template)))?;
+        match &rest[start + 1..end] {
+            "input_name" => rendered.push_str(&input_name(input_path)),
+            "date" => rendered.push_str(&current_date()),
+            "index" => rendered.push_str(&index.to_string()),
+            "ext" => rendered.push_str(OUTPUT_TEMPLATE_EXTENSION),
+            other => return Err(invalid(format!("unknown placeholder '{{{}}}'", other))),
+        }
+        rest = &rest[end + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(PathBuf::from(rendered))
+}
+
+/// Derives a short name for the input: the last path or URL segment, without `.git`.
+fn input_name(input_path: &str) -> String {
+    let trimmed = input_path.trim_end_matches(['/', '\\']);
+    let path = Path::new(trimmed);
+    // Resolve relative inputs like "." to the actual directory name.
+    let canonical = path.canonicalize().ok();
+    let name = canonical
+        .as_deref()
+        .unwrap_or(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .or_else(|| trimmed.rsplit(['/', '\\']).next().map(str::to_string))
+        .unwrap_or_default();
+    let name = name.strip_suffix(".git").unwrap_or(&name);
+    if name.is_empty() {
+        "output".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Returns the current UTC date formatted as `YYYY-MM-DD`.
+pub(crate) fn current_date() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Converts days since the Unix epoch to a (year, month, day) civil date.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's days-to-civil algorithm.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
 
 /// Holds the configured output writer and an optional buffer for clipboard operations.
 ///
@@ -35,17 +145,35 @@ pub struct OutputWriterSetup {
 /// buffer (for clipboard operations) and returns a struct containing the appropriate
 /// writer and any necessary context.
 ///
+/// If `output_newline` is set in the output config, the writer rewrites line
+/// endings as they are written. If `write_bom` is set, file output starts with a UTF-8
+/// byte order mark. Stdout and clipboard output are never prefixed, and neither
+/// is a non-empty file being appended to.
+///
+/// An existing regular file is handled according to `if_exists` in the output
+/// config. An output path that already exists as something other than a regular
+/// file, such as a named pipe (FIFO) or `/dev/null`, is opened for writing as-is.
+/// Opening a FIFO blocks until another process opens it for reading.
+///
 /// # Errors
-/// Returns an error if a file cannot be created for writing.
+/// Returns an error if a file cannot be created for writing, if the file exists and
+/// `if_exists` is [`IfExists::Fail`], or if an existing file cannot be backed up.
 pub fn setup_output_writer(config: &Config) -> Result<OutputWriterSetup> {
     #[cfg_attr(not(feature = "clipboard"), allow(unused_mut))]
     let mut clipboard_buffer = None;
     let writer: Box<dyn Write + Send> = match &config.output_destination {
         OutputDestination::Stdout => Box::new(io::stdout()),
         OutputDestination::File(path) => {
-            let file =
-                File::create(path).map_err(|e| crate::errors::io_error_with_path(e, path))?;
-            Box::new(BufWriter::new(file)) // Use BufWriter for file I/O
+            let file = open_output_file(path, config.output.if_exists)
+                .map_err(|e| crate::errors::io_error_with_path(e, path))?;
+            let is_empty = file.metadata().map_or(true, |m| m.len() == 0);
+            let mut writer = BufWriter::new(file); // Use BufWriter for file I/O
+            if config.output.write_bom && is_empty {
+                writer
+                    .write_all(UTF8_BOM)
+                    .map_err(|e| crate::errors::io_error_with_path(e, path))?;
+            }
+            Box::new(writer)
         }
         #[cfg(feature = "clipboard")]
         OutputDestination::Clipboard => {
@@ -55,12 +183,66 @@ pub fn setup_output_writer(config: &Config) -> Result<OutputWriterSetup> {
             Box::new(ArcMutexVecWriter(buffer)) // Wrap Arc<Mutex<Vec<u8>>>
         }
     };
+    let writer: Box<dyn Write + Send> = match config.output.output_newline {
+        Some(newline) => Box::new(NewlineWriter::new(writer, newline)),
+        None => writer,
+    };
     Ok(OutputWriterSetup {
         writer,
         clipboard_buffer,
     })
 }
 
+/// Opens the output file for writing.
+///
+/// New files are created. An existing regular file is truncated, appended to,
+/// rejected, or renamed to `<name>.bak` depending on `if_exists`. Existing special
+/// files (FIFOs, character devices) are opened without creating or truncating them,
+/// since they are not seekable and truncation has no meaning for them.
+fn open_output_file(path: &Path, if_exists: IfExists) -> io::Result<File> {
+    match fs::metadata(path) {
+        Ok(metadata) if !metadata.is_file() && !metadata.is_dir() => {
+            OpenOptions::new().write(true).open(path)
+        }
+        Ok(metadata) if metadata.is_file() => match if_exists {
+            IfExists::Overwrite => File::create(path),
+            IfExists::Append => OpenOptions::new().append(true).open(path),
+            IfExists::Fail => create_new_file(path),
+            IfExists::Backup => {
+                fs::rename(path, backup_path(path))?;
+                File::create(path)
+            }
+        },
+        _ if if_exists == IfExists::Fail => create_new_file(path),
+        _ => File::create(path),
+    }
+}
+
+/// Creates the file at `path`, failing if it already exists.
+///
+/// The check and the creation are one atomic operation, so a file created by
+/// another process after `open_output_file` looked is not overwritten either.
+fn create_new_file(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "output file already exists (use --if-exists to overwrite, append, or back it up)",
+            ),
+            _ => e,
+        })
+}
+
+/// Returns the path an existing output file is moved to by `--if-exists backup`.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
 /// Finalizes the output stream, handling special cases like copying to the clipboard.
 ///
 /// If the destination was `OutputDestination::Clipboard`, this function copies the
@@ -210,6 +392,119 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_setup_output_writer_file_with_bom() -> Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let path = temp_file.path().to_path_buf();
+        let mut config = create_mock_config();
+        config.output_destination = OutputDestination::File(path.clone());
+        config.output.write_bom = true;
+
+        let mut setup = setup_output_writer(&config)?;
+        write!(setup.writer, "Test content")?;
+        setup.writer.flush()?;
+        drop(setup.writer);
+
+        let bytes = std::fs::read(&path)?;
+        assert_eq!(bytes, b"\xEF\xBB\xBFTest content");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_output_writer_if_exists_append() -> Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let path = temp_file.path().to_path_buf();
+        std::fs::write(&path, "First\n")?;
+        let mut config = create_mock_config();
+        config.output_destination = OutputDestination::File(path.clone());
+        config.output.if_exists = IfExists::Append;
+        config.output.write_bom = true; // Not written into the middle of a file
+
+        let mut setup = setup_output_writer(&config)?;
+        writeln!(setup.writer, "Second")?;
+        setup.writer.flush()?;
+        drop(setup.writer);
+
+        assert_eq!(std::fs::read_to_string(&path)?, "First\nSecond\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_output_writer_if_exists_fail() -> Result<()> {
+        let temp_file = NamedTempFile::new()?;
+        let path = temp_file.path().to_path_buf();
+        std::fs::write(&path, "Keep me")?;
+        let mut config = create_mock_config();
+        config.output_destination = OutputDestination::File(path.clone());
+        config.output.if_exists = IfExists::Fail;
+
+        let err = setup_output_writer(&config).err().expect("should fail");
+        assert!(err.to_string().contains("already exists"));
+        assert_eq!(std::fs::read_to_string(&path)?, "Keep me");
+
+        // The exclusive create itself refuses the file, whatever an earlier check saw.
+        let err = create_new_file(&path).expect_err("should fail");
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert!(err.to_string().contains("--if-exists"));
+
+        // A path that does not exist yet is created as usual.
+        let new_path = path.with_extension("new");
+        config.output_destination = OutputDestination::File(new_path.clone());
+        drop(setup_output_writer(&config)?);
+        assert!(new_path.is_file());
+        std::fs::remove_file(new_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_setup_output_writer_if_exists_backup() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("out.md");
+        std::fs::write(&path, "Old content")?;
+        let mut config = create_mock_config();
+        config.output_destination = OutputDestination::File(path.clone());
+        config.output.if_exists = IfExists::Backup;
+
+        let mut setup = setup_output_writer(&config)?;
+        write!(setup.writer, "New content")?;
+        setup.writer.flush()?;
+        drop(setup.writer);
+
+        assert_eq!(std::fs::read_to_string(&path)?, "New content");
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("out.md.bak"))?,
+            "Old content"
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_setup_output_writer_fifo() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let fifo_path = temp_dir.path().join("out.fifo");
+        let status = std::process::Command::new("mkfifo")
+            .arg(&fifo_path)
+            .status()?;
+        assert!(status.success(), "mkfifo failed");
+
+        // Opening a FIFO for writing blocks until a reader opens it.
+        let reader_path = fifo_path.clone();
+        let reader = std::thread::spawn(move || std::fs::read_to_string(reader_path));
+
+        let mut config = create_mock_config();
+        config.output_destination = OutputDestination::File(fifo_path.clone());
+        let mut setup = setup_output_writer(&config)?;
+        write!(setup.writer, "Streamed content")?;
+        finalize_output(setup.writer, setup.clipboard_buffer, &config)?;
+
+        let received = reader.join().expect("reader thread panicked")?;
+        assert_eq!(received, "Streamed content");
+        assert!(!std::fs::metadata(&fifo_path)?.is_file()); // Still a FIFO
+        Ok(())
+    }
+
     #[cfg(feature = "clipboard")]
     #[test]
     fn test_setup_output_writer_clipboard() {
@@ -301,4 +596,38 @@ mod tests {
             .to_string()
             .contains("no buffer found during finalization"));
     }
+
+    #[test]
+    fn test_render_output_template_placeholders() {
+        let temp = tempfile::tempdir().unwrap();
+        let project = temp.path().join("frontend");
+        std::fs::create_dir(&project).unwrap();
+
+        let path =
+            render_output_template("{input_name}-{index}.{ext}", project.to_str().unwrap(), 2)
+                .unwrap();
+        assert_eq!(path, PathBuf::from("frontend-2.md"));
+    }
+
+    #[test]
+    fn test_render_output_template_date() {
+        let path = render_output_template("{date}", ".", 1).unwrap();
+        let date = path.to_string_lossy();
+        assert_eq!(date.len(), 10);
+        assert_eq!(&date[4..5], "-");
+        assert_eq!(&date[7..8], "-");
+    }
+
+    #[test]
+    fn test_render_output_template_errors() {
+        assert!(render_output_template("{nope}.md", ".", 1).is_err());
+        assert!(render_output_template("{input_name.md", ".", 1).is_err());
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_875), (2024, 6, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
 }
@@ -0,0 +1,349 @@
+// src/output/xml.rs
+
+//! A formatter that writes the processed files as an XML document.
+
+use crate::core_types::{FileCounts, FileInfo};
+use crate::output::{OutputConfig, OutputFormatter};
+use anyhow::Result;
+use log::debug;
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The XML declaration written at the start of every document.
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>";
+
+/// A formatter that writes the processed files as an XML document.
+///
+/// The root `<files>` element holds one `<file path="..." size="...">` element
+/// per file, with its content in a CDATA section so backticks, angle brackets,
+/// and ampersands are kept verbatim. Binary files get a `binary="true"`
+/// attribute, and counts, when computed, are written as `lines`, `characters`,
+/// and `words` attributes. If `OutputConfig::summary` is set, a `<summary>`
+/// element lists every path after the files, followed by an `<over_budget>`
+/// element with the paths of any files dropped to fit a token budget.
+///
+/// Attribute values escape `&`, `<`, `>`, and `"`. Content containing `]]>`
+/// is split across two CDATA sections, since that sequence would otherwise end
+/// the section early. Characters that XML 1.0 does not allow anywhere, such as
+/// form feed, escape, NUL, and U+FFFE, are replaced with U+FFFD in both
+/// content and attributes. A dry run writes the `<file>` elements without content.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::output::{OutputFormatter, XmlFormatter};
+/// use dircat::OutputConfig;
+/// use dircat::core_types::FileInfo;
+/// use std::path::PathBuf;
+/// # fn main() -> anyhow::Result<()> {
+///
+/// let file = FileInfo {
+///     absolute_path: PathBuf::from("/abs/a&b.txt"),
+///     relative_path: PathBuf::from("a&b.txt"),
+///     size: 6,
+///     processed_content: Some("a < b\n".to_string()),
+///     ..Default::default()
+/// };
+/// let opts = OutputConfig {
+///     filename_only_header: false, line_numbers: false, backticks: false,
+///     num_ticks: 3, summary: false, counts: false, ..Default::default()
+/// };
+///
+/// let mut buffer = Vec::new();
+/// XmlFormatter.format(&[file], &opts, &mut buffer)?;
+///
+/// assert_eq!(
+///     String::from_utf8(buffer)?,
+///     "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<files>\n  \
+///      <file path=\"a&amp;b.txt\" size=\"6\"><![CDATA[a < b\n]]></file>\n</files>\n"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XmlFormatter;
+
+impl OutputFormatter for XmlFormatter {
+    fn format(
+        &self,
+        files: &[FileInfo],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        self.format_with_over_budget(files, &[], opts, writer)
+    }
+
+    fn format_with_over_budget(
+        &self,
+        files: &[FileInfo],
+        over_budget: &[PathBuf],
+        opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        debug!("Writing XML output for {} files...", files.len());
+        writeln!(writer, "{}", XML_DECLARATION)?;
+        writeln!(writer, "<files>")?;
+        for file_info in files.iter().filter(|fi| !fi.is_dir && !fi.is_symlink()) {
+            let attributes = file_attributes(file_info);
+            match &file_info.processed_content {
+                Some(content) => {
+                    writeln!(writer, "  <file {}>{}</file>", attributes, cdata(content))?
+                }
+                None => writeln!(writer, "  <file {}/>", attributes)?,
+            }
+            if opts.flush_per_file {
+                writer.flush()?;
+            }
+        }
+        if opts.summary {
+            let listed: Vec<&FileInfo> = files.iter().filter(|fi| !fi.is_dir).collect();
+            write_summary(writer, &listed, over_budget, opts)?;
+        }
+        writeln!(writer, "</files>")?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn format_dry_run(
+        &self,
+        files: &[FileInfo],
+        _opts: &OutputConfig,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        writeln!(writer, "{}", XML_DECLARATION)?;
+        writeln!(writer, "<files>")?;
+        for file_info in files.iter().filter(|fi| !fi.is_dir) {
+            writeln!(writer, "  <file {}/>", file_attributes(file_info))?;
+        }
+        writeln!(writer, "</files>")?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Returns `true` if `c` may appear in an XML 1.0 document.
+fn is_xml_char(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r' | ' '..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..)
+}
+
+/// Replaces characters that XML 1.0 does not allow with U+FFFD.
+fn replace_invalid_chars(text: &str) -> Cow<'_, str> {
+    if text.chars().all(is_xml_char) {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(
+        text.chars()
+            .map(|c| if is_xml_char(c) { c } else { '\u{FFFD}' })
+            .collect(),
+    )
+}
+
+/// Escapes text for use in an XML attribute value or element text.
+fn escape_xml(text: &str) -> String {
+    replace_invalid_chars(text)
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Wraps `content` in CDATA, splitting any `]]>` across two sections.
+fn cdata(content: &str) -> String {
+    format!(
+        "<![CDATA[{}]]>",
+        replace_invalid_chars(content).replace("]]>", "]]]]><![CDATA[>")
+    )
+}
+
+fn path_attribute(path: &Path) -> String {
+    escape_xml(&path.to_string_lossy().replace('\\', "/"))
+}
+
+fn counts_attributes(counts: &FileCounts) -> String {
+    format!(
+        " lines=\"{}\" characters=\"{}\" words=\"{}\"",
+        counts.lines, counts.characters, counts.words
+    )
+}
+
+fn file_attributes(file_info: &FileInfo) -> String {
+    let mut attributes = format!(
+        "path=\"{}\" size=\"{}\"",
+        path_attribute(&file_info.relative_path),
+        file_info.size
+    );
+    if file_info.is_binary {
+        attributes.push_str(" binary=\"true\"");
+    }
+    if let Some(counts) = &file_info.counts {
+        attributes.push_str(&counts_attributes(counts));
+    }
+    attributes
+}
+
+/// Writes the `<summary>` element, with one `<path>` element per file, and the
+/// `<over_budget>` element if any files were dropped.
+///
+/// Files whose content looks minified get a `minified="true"` attribute.
+fn write_summary(
+    writer: &mut dyn Write,
+    files: &[&FileInfo],
+    over_budget: &[PathBuf],
+    opts: &OutputConfig,
+) -> Result<()> {
+    writeln!(writer, "  <summary count=\"{}\">", files.len())?;
+    for file_info in files {
+        let mut attributes = match (&file_info.counts, opts.counts) {
+            (Some(counts), true) => counts_attributes(counts),
+            _ => String::new(),
+        };
+        if file_info.is_minified {
+            attributes.push_str(" minified=\"true\"");
+        }
+        writeln!(
+            writer,
+            "    <path{}>{}</path>",
+            attributes,
+            path_attribute(&file_info.relative_path)
+        )?;
+    }
+    writeln!(writer, "  </summary>")?;
+    if !over_budget.is_empty() {
+        writeln!(writer, "  <over_budget count=\"{}\">", over_budget.len())?;
+        for path in over_budget {
+            writeln!(writer, "    <path>{}</path>", path_attribute(path))?;
+        }
+        writeln!(writer, "  </over_budget>")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{create_mock_file_info, create_mock_output_config};
+
+    #[test]
+    fn test_xml_formatter_escapes_paths_and_splits_cdata_end() -> Result<()> {
+        let opts = create_mock_output_config(true, false, false, false);
+        let mut dir = create_mock_file_info("src", 0);
+        dir.is_dir = true;
+        let mut a = create_mock_file_info("src/say \"a&b\".md", 20);
+        a.processed_content = Some("x[a[0]]> `y`\n".to_string());
+        let mut bin = create_mock_file_info("img.bin", 3);
+        bin.is_binary = true;
+
+        let mut output = Vec::new();
+        XmlFormatter.format(&[dir, a, bin], &opts, &mut output)?;
+
+        let expected = concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<files>\n",
+            "  <file path=\"src/say &quot;a&amp;b&quot;.md\" size=\"20\">",
+            "<![CDATA[x[a[0]]]]><![CDATA[> `y`\n]]></file>\n",
+            "  <file path=\"img.bin\" size=\"3\" binary=\"true\"/>\n",
+            "</files>\n"
+        );
+        assert_eq!(String::from_utf8(output)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_xml_formatter_output_parses_with_control_characters() -> Result<()> {
+        let opts = create_mock_output_config(false, false, false, true);
+        let mut a = create_mock_file_info("page\x0cbreak.txt", 12);
+        a.processed_content = Some("a\x0cb \x1b[31mred\x1b[0m ]]> \0 \u{FFFE}\n".to_string());
+        let mut bin = create_mock_file_info("blob.bin", 4);
+        bin.is_binary = true;
+        bin.processed_content = Some("\u{1}\u{2}\t\u{FFFF}".to_string());
+
+        let mut output = Vec::new();
+        XmlFormatter.format(&[a, bin], &opts, &mut output)?;
+
+        let output = String::from_utf8(output)?;
+        let document = roxmltree::Document::parse(&output)?;
+        let contents: Vec<&str> = document
+            .descendants()
+            .filter(|node| node.has_tag_name("file"))
+            .map(|node| node.text().unwrap_or_default())
+            .collect();
+        assert_eq!(
+            contents,
+            vec![
+                "a\u{FFFD}b \u{FFFD}[31mred\u{FFFD}[0m ]]> \u{FFFD} \u{FFFD}\n",
+                "\u{FFFD}\u{FFFD}\t\u{FFFD}"
+            ]
+        );
+        let path = document
+            .descendants()
+            .find(|node| node.has_tag_name("file"))
+            .and_then(|node| node.attribute("path"));
+        assert_eq!(path, Some("page\u{FFFD}break.txt"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_xml_formatter_with_summary_and_counts() -> Result<()> {
+        let mut opts = create_mock_output_config(false, false, false, true);
+        opts.counts = true;
+        let mut a = create_mock_file_info("a<1>.txt", 4);
+        a.processed_content = Some("A B\n".to_string());
+        a.counts = Some(FileCounts {
+            lines: 1,
+            characters: 4,
+            words: 2,
+        });
+
+        let mut output = Vec::new();
+        XmlFormatter.format(&[a], &opts, &mut output)?;
+
+        let output = String::from_utf8(output)?;
+        assert!(output.contains(
+            "<file path=\"a&lt;1&gt;.txt\" size=\"4\" lines=\"1\" characters=\"4\" words=\"2\">"
+        ));
+        assert!(output.ends_with(concat!(
+            "  <summary count=\"1\">\n",
+            "    <path lines=\"1\" characters=\"4\" words=\"2\">a&lt;1&gt;.txt</path>\n",
+            "  </summary>\n</files>\n"
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn test_xml_formatter_lists_over_budget_files_in_summary() -> Result<()> {
+        let opts = create_mock_output_config(false, false, false, true);
+        let mut a = create_mock_file_info("a.txt", 2);
+        a.processed_content = Some("A\n".to_string());
+        let dropped = [PathBuf::from("b&c.txt")];
+
+        let mut output = Vec::new();
+        XmlFormatter.format_with_over_budget(&[a], &dropped, &opts, &mut output)?;
+
+        let output = String::from_utf8(output)?;
+        assert!(output.ends_with(concat!(
+            "  <summary count=\"1\">\n    <path>a.txt</path>\n  </summary>\n",
+            "  <over_budget count=\"1\">\n    <path>b&amp;c.txt</path>\n  </over_budget>\n",
+            "</files>\n"
+        )));
+        roxmltree::Document::parse(&output)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_xml_formatter_dry_run_omits_content() -> Result<()> {
+        let opts = create_mock_output_config(false, false, false, false);
+        let files = [create_mock_file_info("src/lib.rs", 42)];
+
+        let mut output = Vec::new();
+        XmlFormatter.format_dry_run(&files, &opts, &mut output)?;
+
+        assert_eq!(
+            String::from_utf8(output)?,
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<files>\n",
+                "  <file path=\"src/lib.rs\" size=\"42\"/>\n</files>\n"
+            )
+        );
+        Ok(())
+    }
+}
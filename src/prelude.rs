@@ -21,29 +21,38 @@
 
 pub use crate::cancellation::CancellationToken;
 pub use crate::config::{
-    Config, ConfigBuilder, DiscoveryConfig, OutputConfig, OutputDestination, ProcessingConfig,
+    BinaryEncoding, Config, ConfigBuilder, ContentStyle, DiscoverCallback, DiscoveryConfig,
+    Extensionless, IfExists, LastSort, Newline, OutputConfig, OutputDestination, OutputFormat,
+    PostProcessCallback, Preset, ProcessingConfig, RelativeBase, TicksPolicy,
 };
 pub use crate::core_types::{FileCounts, FileInfo};
 pub use crate::discovery::discover_files;
 pub use crate::errors::{Error, Result};
 pub use crate::filtering::{
-    check_process_last, is_file_type, is_likely_text, is_likely_text_from_buffer, is_lockfile,
-    passes_extension_filters, passes_size_filter,
+    check_process_last, classify_text, generated_marker, has_binary_extension, is_file_type,
+    is_likely_minified, is_likely_secret_file, is_likely_text, is_likely_text_from_buffer,
+    is_lockfile, passes_extension_filters, passes_size_filter, TextClassification,
+};
+pub use crate::output::{
+    DelimitedFormatter, IndexFormatter, JsonFormatter, ListingFormatter, MarkdownFormatter,
+    OutputFormatter, XmlFormatter,
 };
-pub use crate::output::{MarkdownFormatter, OutputFormatter};
 pub use crate::processing::process_files;
 pub use crate::processing::{
     calculate_counts,
     filters::{
-        remove_comments, remove_empty_lines, ContentFilter, RemoveCommentsFilter,
+        remove_comments, remove_comments_collapsing_spaces, remove_comments_with_placeholder,
+        remove_empty_lines, CompactRemoveCommentsFilter, ContentFilter, RemoveCommentsFilter,
         RemoveEmptyLinesFilter,
     },
+    StreamingCounter,
 };
 pub use crate::{execute, run, DircatResult};
 
 // Also re-export key git utility functions if the feature is enabled.
 #[cfg(feature = "git")]
 pub use crate::git::{
-    download_directory_via_api, get_repo, is_git_url, parse_clone_url, parse_github_folder_url,
-    parse_github_folder_url_with_hint, parse_github_repo_url, ParsedGitUrl,
+    download_directory_via_api, download_directory_via_api_with_max_depth, get_repo, is_git_url,
+    parse_clone_url, parse_github_folder_url, parse_github_folder_url_with_hint,
+    parse_github_repo_url, ParsedGitUrl,
 };
@@ -0,0 +1,108 @@
+// src/processing/context.rs
+
+//! Condenses file content to the regions around regex matches.
+
+use regex::Regex;
+
+/// The marker written in place of omitted lines.
+pub const OMITTED_LINES_MARKER: &str = "...";
+
+/// Returns `true` if any of the regexes matches the content.
+pub(crate) fn content_matches(content: &str, regexes: &[Regex]) -> bool {
+    regexes.iter().any(|re| re.is_match(content))
+}
+
+/// Extracts windows of `context` lines around every line matching any of the regexes.
+///
+/// Overlapping or adjacent windows are merged. Wherever lines are omitted (before,
+/// between, or after the windows), a `...` marker line is written instead. If no
+/// line matches, an empty string is returned.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::processing::extract_context;
+/// use regex::Regex;
+///
+/// let content = "a\nb\nMATCH\nc\nd\ne\nf";
+/// let regexes = vec![Regex::new("MATCH").unwrap()];
+///
+/// assert_eq!(extract_context(content, &regexes, 1), "...\nb\nMATCH\nc\n...");
+/// ```
+pub fn extract_context(content: &str, regexes: &[Regex], context: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+
+    // Merge the windows around each matching line into sorted, disjoint ranges.
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if !regexes.iter().any(|re| re.is_match(line)) {
+            continue;
+        }
+        let start = i.saturating_sub(context);
+        let end = (i + context).min(lines.len() - 1);
+        match windows.last_mut() {
+            Some((_, last_end)) if start <= *last_end + 1 => *last_end = end.max(*last_end),
+            _ => windows.push((start, end)),
+        }
+    }
+
+    let mut output: Vec<&str> = Vec::new();
+    let mut next_line = 0;
+    for (start, end) in windows {
+        if start > next_line {
+            output.push(OMITTED_LINES_MARKER);
+        }
+        output.extend_from_slice(&lines[start..=end]);
+        next_line = end + 1;
+    }
+    if next_line > 0 && next_line < lines.len() {
+        output.push(OMITTED_LINES_MARKER);
+    }
+    output.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn regexes(patterns: &[&str]) -> Vec<Regex> {
+        patterns.iter().map(|p| Regex::new(p).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_content_matches() {
+        assert!(content_matches("fn main() {}", &regexes(&["main"])));
+        assert!(!content_matches("fn main() {}", &regexes(&["foo", "bar"])));
+    }
+
+    #[test]
+    fn test_extract_context_separate_windows() {
+        let content = "1\n2\nX\n4\n5\n6\n7\nX\n9";
+        let result = extract_context(content, &regexes(&["X"]), 1);
+        assert_eq!(result, "...\n2\nX\n4\n...\n7\nX\n9");
+    }
+
+    #[test]
+    fn test_extract_context_merges_overlapping_windows() {
+        let content = "1\nX\n3\n4\nX\n6\n7";
+        let result = extract_context(content, &regexes(&["X"]), 1);
+        assert_eq!(result, "1\nX\n3\n4\nX\n6\n...");
+    }
+
+    #[test]
+    fn test_extract_context_zero_context() {
+        let content = "a\nX\nb";
+        assert_eq!(extract_context(content, &regexes(&["X"]), 0), "...\nX\n...");
+    }
+
+    #[test]
+    fn test_extract_context_window_clamped_to_bounds() {
+        let content = "X\nb\nc";
+        assert_eq!(extract_context(content, &regexes(&["X"]), 5), "X\nb\nc");
+    }
+
+    #[test]
+    fn test_extract_context_no_match() {
+        assert_eq!(extract_context("a\nb", &regexes(&["X"]), 2), "");
+    }
+}
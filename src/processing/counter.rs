@@ -1,6 +1,12 @@
 // src/processing/counter.rs
 
 use crate::core_types::FileCounts;
+use crate::filtering::{classify_head, HeadVerdict, READ_BUFFER_SIZE};
+use std::io::{self, Read};
+use std::str;
+
+/// The size of the chunks `count_reader` reads at a time.
+const CHUNK_SIZE: usize = 64 * 1024;
 
 /// Calculates line, character, and word counts for a string slice.
 ///
@@ -32,10 +38,285 @@ pub fn calculate_counts(content: &str) -> FileCounts {
     }
 }
 
+/// Computes the same counts as [`calculate_counts`] from content fed in chunks.
+///
+/// Only the current chunk and up to three bytes of a UTF-8 sequence split
+/// across chunks are held at a time, so counting a file does not require
+/// holding its whole content. Invalid UTF-8 is counted as if each invalid
+/// sequence were replaced with U+FFFD, matching
+/// [`String::from_utf8_lossy`].
+///
+/// # Examples
+///
+/// ```
+/// use dircat::processing::{calculate_counts, StreamingCounter};
+///
+/// let content = "Hello, wörld!\nThis is a test.";
+/// let mut counter = StreamingCounter::new();
+/// for chunk in content.as_bytes().chunks(5) {
+///     counter.update(chunk);
+/// }
+/// assert!(counter.is_valid_utf8());
+///
+/// let counts = counter.finish();
+/// let expected = calculate_counts(content);
+/// assert_eq!(
+///     (counts.lines, counts.characters, counts.words),
+///     (expected.lines, expected.characters, expected.words)
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingCounter {
+    counts: FileCounts,
+    newlines: usize,
+    bytes_read: usize,
+    in_word: bool,
+    ends_with_newline: bool,
+    valid_utf8: bool,
+    /// The start of a UTF-8 sequence that was cut off at the end of the last chunk.
+    partial: Vec<u8>,
+}
+
+impl Default for StreamingCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamingCounter {
+    /// Creates a counter that has not seen any content.
+    pub fn new() -> Self {
+        Self {
+            counts: FileCounts::default(),
+            newlines: 0,
+            bytes_read: 0,
+            in_word: false,
+            ends_with_newline: false,
+            valid_utf8: true,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Counts the next chunk of content.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.bytes_read += chunk.len();
+        let mut rest = chunk;
+        // Complete a sequence left over from the previous chunk one byte at a time.
+        while !self.partial.is_empty() && !rest.is_empty() {
+            let mut partial = std::mem::take(&mut self.partial);
+            partial.push(rest[0]);
+            rest = &rest[1..];
+            self.partial = self.count_bytes(&partial).to_vec();
+        }
+        if self.partial.is_empty() {
+            self.partial = self.count_bytes(rest).to_vec();
+        }
+    }
+
+    /// Returns the number of raw bytes passed to [`update`](Self::update) so far.
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
+    /// Returns `true` if all content seen so far is valid UTF-8. A sequence cut
+    /// off at the end of the last chunk does not count as invalid until
+    /// [`finish`](Self::finish), since the next chunk may complete it.
+    pub fn is_valid_utf8(&self) -> bool {
+        self.valid_utf8
+    }
+
+    /// Finishes counting and returns the counts for all the content.
+    pub fn finish(mut self) -> FileCounts {
+        if !self.partial.is_empty() {
+            // The content ended in the middle of a UTF-8 sequence.
+            self.valid_utf8 = false;
+            self.count_str("\u{FFFD}");
+        }
+        let unterminated = self.counts.characters > 0 && !self.ends_with_newline;
+        self.counts.lines = self.newlines + usize::from(unterminated);
+        self.counts
+    }
+
+    /// Counts the valid UTF-8 in `bytes` and returns a trailing incomplete
+    /// sequence, if any, for the next chunk to complete.
+    fn count_bytes<'a>(&mut self, bytes: &'a [u8]) -> &'a [u8] {
+        let mut rest = bytes;
+        loop {
+            match str::from_utf8(rest) {
+                Ok(text) => {
+                    self.count_str(text);
+                    return &[];
+                }
+                Err(e) => {
+                    let (valid, invalid) = rest.split_at(e.valid_up_to());
+                    self.count_str(str::from_utf8(valid).unwrap_or_default());
+                    match e.error_len() {
+                        Some(len) => {
+                            self.valid_utf8 = false;
+                            self.count_str("\u{FFFD}");
+                            rest = &invalid[len..];
+                        }
+                        None => return invalid,
+                    }
+                }
+            }
+        }
+    }
+
+    fn count_str(&mut self, text: &str) {
+        self.counts.characters += text.len();
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.in_word = false;
+            } else if !self.in_word {
+                self.in_word = true;
+                self.counts.words += 1;
+            }
+            if c == '\n' {
+                self.newlines += 1;
+            }
+        }
+        if let Some(last) = text.chars().last() {
+            self.ends_with_newline = last == '\n';
+        }
+    }
+}
+
+/// Reads `reader` to the end in chunks and returns its counts and whether it
+/// is binary, without holding the whole content in memory.
+///
+/// The binary verdict matches [`classify_text`](crate::filtering::classify_text)
+/// on the whole content. As in a full run, binary content is counted by size
+/// alone.
+///
+/// # Errors
+/// Returns an error if reading fails.
+pub(crate) fn count_reader(mut reader: impl Read) -> io::Result<(FileCounts, bool)> {
+    let mut head = Vec::with_capacity(READ_BUFFER_SIZE);
+    (&mut reader)
+        .take(READ_BUFFER_SIZE as u64)
+        .read_to_end(&mut head)?;
+    let verdict = classify_head(&head);
+    if verdict == HeadVerdict::Binary {
+        let rest = io::copy(&mut reader, &mut io::sink())?;
+        return Ok((binary_counts(head.len() + rest as usize), true));
+    }
+
+    // Without a BOM, any invalid UTF-8 makes the content binary, so reading
+    // can stop counting as soon as some is found.
+    let must_be_utf8 = verdict == HeadVerdict::TextIfValidUtf8;
+    let mut counter = StreamingCounter::new();
+    counter.update(&head);
+    let mut buffer = vec![0; CHUNK_SIZE];
+    while !must_be_utf8 || counter.is_valid_utf8() {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        counter.update(&buffer[..read]);
+    }
+    if must_be_utf8 && !counter.is_valid_utf8() {
+        let rest = io::copy(&mut reader, &mut io::sink())?;
+        return Ok((binary_counts(counter.bytes_read() + rest as usize), true));
+    }
+    let bytes_read = counter.bytes_read();
+    let truncated = !counter.partial.is_empty();
+    let counts = counter.finish();
+    if must_be_utf8 && truncated {
+        // The content ended in the middle of a UTF-8 sequence.
+        return Ok((binary_counts(bytes_read), true));
+    }
+    Ok((counts, false))
+}
+
+fn binary_counts(size: usize) -> FileCounts {
+    FileCounts {
+        lines: 0,
+        characters: size,
+        words: 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn streamed(content: &[u8], chunk_size: usize) -> FileCounts {
+        let mut counter = StreamingCounter::new();
+        for chunk in content.chunks(chunk_size) {
+            counter.update(chunk);
+        }
+        counter.finish()
+    }
+
+    fn assert_same_counts(actual: FileCounts, expected: FileCounts) {
+        assert_eq!(
+            (actual.lines, actual.characters, actual.words),
+            (expected.lines, expected.characters, expected.words)
+        );
+    }
+
+    #[test]
+    fn test_streaming_counter_matches_calculate_counts() {
+        let samples: [&[u8]; 6] = [
+            b"",
+            b"One two three.\nFour five.\n",
+            "  \n\t \n ".as_bytes(),
+            "caf\u{e9}\u{2003}na\u{ef}ve \u{1F980} crab\r\nend".as_bytes(),
+            b"bad \xE2\x82 bytes \xFF\nand a cut-off \xF0\x9F",
+            b"\n\n",
+        ];
+        for content in samples {
+            let expected = calculate_counts(&String::from_utf8_lossy(content));
+            for chunk_size in [1, 2, 3, 5, 64] {
+                assert_same_counts(streamed(content, chunk_size), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_streaming_counter_tracks_utf8_validity() {
+        let mut counter = StreamingCounter::new();
+        counter.update(&[b'a', 0xE2, 0x82]);
+        assert!(counter.is_valid_utf8()); // The sequence may still be completed.
+        counter.update(&[0xAC]);
+        assert!(counter.is_valid_utf8());
+        counter.update(&[0xFF]);
+        assert!(!counter.is_valid_utf8());
+        assert_eq!(counter.bytes_read(), 5);
+    }
+
+    #[test]
+    fn test_count_reader_detects_binary_like_classify_text() -> io::Result<()> {
+        let mut long_text = "x".repeat(READ_BUFFER_SIZE + 10).into_bytes();
+        long_text.extend_from_slice(b" \n");
+        let mut invalid_late = long_text.clone();
+        invalid_late.push(0xFF);
+        let mut bom_invalid = vec![0xEF, 0xBB, 0xBF];
+        bom_invalid.extend_from_slice(b"a \xFF b\n");
+        let samples: [&[u8]; 5] = [
+            &long_text,
+            &invalid_late,
+            &bom_invalid,
+            b"nul\0byte",
+            b"ends mid \xE2\x82",
+        ];
+        for content in samples {
+            let (counts, is_binary) = count_reader(content)?;
+            let is_text = crate::filtering::classify_text(content).is_text();
+            assert_eq!(is_binary, !is_text);
+            let expected = if is_text {
+                calculate_counts(&String::from_utf8_lossy(content))
+            } else {
+                binary_counts(content.len())
+            };
+            assert_same_counts(counts, expected);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_counts_basic() {
         let content = "One two three.\nFour five.\n";
@@ -23,6 +23,50 @@ use log::debug;
 /// assert_eq!(remove_comments(code), expected);
 /// ```
 pub fn remove_comments(content: &str) -> String {
+    remove_comments_impl(content, false, None)
+}
+
+/// Removes C/C++ style comments like [`remove_comments`], but replaces each block
+/// comment with `placeholder` instead of dropping it.
+///
+/// Line comments are still removed entirely. This signals to a reader that code
+/// was elided without keeping the comment text itself.
+///
+/// # Examples
+/// ```
+/// use dircat::processing::filters::remove_comments_with_placeholder;
+///
+/// let code = "let x = 1; /* long explanation */ // note";
+/// assert_eq!(remove_comments_with_placeholder(code, "/* ... */"), "let x = 1; /* ... */");
+/// ```
+pub fn remove_comments_with_placeholder(content: &str, placeholder: &str) -> String {
+    remove_comments_impl(content, false, Some(placeholder))
+}
+
+/// Removes C/C++ style comments like [`remove_comments`], and also collapses the
+/// whitespace left behind where a block comment was removed.
+///
+/// When a block comment sits between two runs of whitespace, only the whitespace
+/// before it is kept, so `code /* x */ more` becomes `code more` instead of
+/// `code  more`. Indentation at the start of a line is preserved, and whitespace
+/// inside string and character literals is never touched.
+///
+/// # Examples
+/// ```
+/// use dircat::processing::filters::remove_comments_collapsing_spaces;
+///
+/// assert_eq!(remove_comments_collapsing_spaces("code /* x */ more"), "code more");
+/// assert_eq!(remove_comments_collapsing_spaces("    /* x */ let y;"), "    let y;");
+/// ```
+pub fn remove_comments_collapsing_spaces(content: &str) -> String {
+    remove_comments_impl(content, true, None)
+}
+
+fn remove_comments_impl(
+    content: &str,
+    collapse_spaces: bool,
+    block_replacement: Option<&str>,
+) -> String {
     enum State {
         Normal,
         MaybeSlash,           // Seen '/'
@@ -37,9 +81,18 @@ pub fn remove_comments(content: &str) -> String {
 
     let mut result = String::with_capacity(content.len());
     let mut state = State::Normal;
+    // Set right after a block comment ends; cleared by the first non-blank character.
+    let mut after_block_comment = false;
     let chars = content.chars().peekable();
 
     for c in chars {
+        if after_block_comment {
+            if (c == ' ' || c == '\t') && result.ends_with([' ', '\t']) {
+                // Skip whitespace that would double up with the whitespace before the comment.
+                continue;
+            }
+            after_block_comment = false;
+        }
         match state {
             State::Normal => match c {
                 '/' => state = State::MaybeSlash,
@@ -96,7 +149,13 @@ pub fn remove_comments(content: &str) -> String {
                 // Consume the character
             }
             State::MaybeEndBlockComment => match c {
-                '/' => state = State::Normal,
+                '/' => {
+                    state = State::Normal;
+                    after_block_comment = collapse_spaces;
+                    if let Some(replacement) = block_replacement {
+                        result.push_str(replacement);
+                    }
+                }
                 '*' => {} // Still in MaybeEndBlockComment (e.g., /* ***/ )
                 _ => state = State::BlockComment, // Not the end, back to BlockComment
             },
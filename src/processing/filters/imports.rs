@@ -0,0 +1,126 @@
+/// Sorts and deduplicates runs of consecutive single-line import statements.
+///
+/// A run is a block of adjacent import lines with the same indentation. Blank
+/// lines, comments, and any other line end a run, so deliberately separated
+/// groups (e.g., standard library, then third-party) stay separate. Recognized
+/// imports are:
+///
+/// - Rust: `use ...;`, optionally with a `pub` or `pub(...)` visibility.
+/// - Python: `import ...` and `from ... import ...`.
+/// - JavaScript/TypeScript: `import ... from '...'` and `import '...'`.
+///
+/// This is a heuristic, cosmetic transform. Imports are recognized by their
+/// syntax, not by the file's language. An import spanning several lines, or
+/// with a trailing comment, is not recognized and is left in place. Where
+/// import order has side effects, as it can in Python and JavaScript, sorting
+/// may change what the code does.
+///
+/// # Examples
+/// ```
+/// use dircat::processing::filters::organize_imports;
+///
+/// let code = "use std::io;\nuse std::fmt;\nuse std::io;\n\nfn main() {}";
+/// assert_eq!(organize_imports(code), "use std::fmt;\nuse std::io;\n\nfn main() {}");
+/// ```
+pub fn organize_imports(content: &str) -> String {
+    let lines: Vec<&str> = content.split('\n').collect();
+    let mut organized = Vec::with_capacity(lines.len());
+    let mut i = 0;
+    while i < lines.len() {
+        if !is_single_line_import(lines[i]) {
+            organized.push(lines[i]);
+            i += 1;
+            continue;
+        }
+        let indent = indentation(lines[i]);
+        let start = i;
+        while i < lines.len() && is_single_line_import(lines[i]) && indentation(lines[i]) == indent
+        {
+            i += 1;
+        }
+        let mut run = lines[start..i].to_vec();
+        run.sort_unstable();
+        run.dedup();
+        organized.extend(run);
+    }
+    organized.join("\n")
+}
+
+/// Returns `true` if the line is a complete Rust, Python, or JavaScript import.
+fn is_single_line_import(line: &str) -> bool {
+    let trimmed = line.trim();
+    if rust_use_body(trimmed).is_some() {
+        return trimmed.ends_with(';') && is_balanced(trimmed, '{', '}');
+    }
+    let python_from = trimmed.starts_with("from ") && trimmed.contains(" import ");
+    if trimmed.starts_with("import ") || python_from {
+        // A trailing `\` or an open bracket continues the import on the next line.
+        return !trimmed.ends_with('\\')
+            && is_balanced(trimmed, '(', ')')
+            && is_balanced(trimmed, '{', '}');
+    }
+    false
+}
+
+/// Returns what follows `use ` in a Rust `use` declaration, skipping any visibility.
+fn rust_use_body(line: &str) -> Option<&str> {
+    let rest = match line.strip_prefix("pub") {
+        Some(rest) if rest.starts_with('(') => rest.split_once(") ")?.1,
+        Some(rest) => rest.strip_prefix(' ')?,
+        None => line,
+    };
+    rest.strip_prefix("use ")
+}
+
+fn is_balanced(line: &str, open: char, close: char) -> bool {
+    line.matches(open).count() == line.matches(close).count()
+}
+
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_uses_sorted_per_group() {
+        let code = "use std::io;\npub(crate) use crate::b;\nuse std::fmt;\n\nuse serde::Serialize;\nuse anyhow::Result;\n";
+        let expected = "pub(crate) use crate::b;\nuse std::fmt;\nuse std::io;\n\nuse anyhow::Result;\nuse serde::Serialize;\n";
+        assert_eq!(organize_imports(code), expected);
+    }
+
+    #[test]
+    fn test_rust_multi_line_use_left_in_place() {
+        let code = "use std::io;\nuse std::{\n    fmt,\n};\nuse std::fs;";
+        assert_eq!(organize_imports(code), code);
+    }
+
+    #[test]
+    fn test_python_imports() {
+        let code = "import sys\nfrom os import path\nimport sys\nimport abc\n\ndef main():\n    import re\n    import json\n";
+        let expected = "from os import path\nimport abc\nimport sys\n\ndef main():\n    import json\n    import re\n";
+        assert_eq!(organize_imports(code), expected);
+    }
+
+    #[test]
+    fn test_python_parenthesized_import_left_in_place() {
+        let code = "import sys\nfrom os import (\n    path,\n)\nimport abc";
+        assert_eq!(organize_imports(code), code);
+    }
+
+    #[test]
+    fn test_javascript_imports() {
+        let code = "import React from 'react';\nimport './styles.css';\nimport { b, a } from \"./util\";\nimport React from 'react';\n\nexport default App;";
+        let expected = "import './styles.css';\nimport React from 'react';\nimport { b, a } from \"./util\";\n\nexport default App;";
+        assert_eq!(organize_imports(code), expected);
+    }
+
+    #[test]
+    fn test_prose_is_not_an_import() {
+        let text = "from here on, we use it;\nuse the force;\nAnd more.";
+        // `use the force;` looks like a Rust import, but it is alone in its run.
+        assert_eq!(organize_imports(text), text);
+    }
+}
@@ -0,0 +1,182 @@
+/// Keywords (lowercase) that mark a leading comment block as a license header.
+const LICENSE_KEYWORDS: &[&str] = &[
+    "copyright",
+    "licensed under",
+    "spdx-license-identifier",
+    "permission is hereby granted",
+];
+
+/// Removes a license header from the start of the content.
+///
+/// A license header is the first comment block in the file, written either as
+/// consecutive `//` line comments or as a single `/* ... */` block comment, that
+/// contains a keyword such as `Copyright`, `Licensed under`, or
+/// `SPDX-License-Identifier`. Only a shebang line and blank lines may precede it;
+/// they are kept. Blank lines between the header and the first code line are
+/// removed along with it. Doc comments (`///`, `//!`) are never treated as part
+/// of a header.
+///
+/// If `marker` is set, it is written on its own line in place of the header.
+/// Content without a license header is returned unchanged.
+///
+/// # Examples
+/// ```
+/// use dircat::processing::filters::strip_license_header;
+///
+/// let text = "// Copyright 2024 Example Corp.\n// SPDX-License-Identifier: MIT\n\nfn main() {}\n";
+/// assert_eq!(strip_license_header(text, None), "fn main() {}\n");
+/// assert_eq!(
+///     strip_license_header(text, Some("// license header removed")),
+///     "// license header removed\nfn main() {}\n"
+/// );
+/// ```
+pub fn strip_license_header(content: &str, marker: Option<&str>) -> String {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+
+    // Skip a shebang and leading blank lines, which are kept as-is.
+    let mut start = 0;
+    if lines.first().is_some_and(|line| line.starts_with("#!")) {
+        start = 1;
+    }
+    while start < lines.len() && lines[start].trim().is_empty() {
+        start += 1;
+    }
+
+    let Some(end) = header_block_end(&lines, start) else {
+        return content.to_string();
+    };
+    let header = lines[start..end].concat().to_lowercase();
+    if !LICENSE_KEYWORDS.iter().any(|k| header.contains(k)) {
+        return content.to_string();
+    }
+
+    // Drop the blank lines between the header and the first code line.
+    let mut rest = end;
+    while rest < lines.len() && lines[rest].trim().is_empty() {
+        rest += 1;
+    }
+
+    let mut result = lines[..start].concat();
+    if let Some(marker) = marker {
+        result.push_str(marker);
+        result.push('\n');
+    }
+    result.push_str(&lines[rest..].concat());
+    result
+}
+
+/// Returns the index one past the last line of the comment block starting at
+/// `start`, or `None` if no comment block starts there.
+fn header_block_end(lines: &[&str], start: usize) -> Option<usize> {
+    let first = lines.get(start)?.trim_start();
+    if first.starts_with("/*") {
+        // The block ends on the line holding the closing `*/`, which must not
+        // be followed by code on the same line.
+        let offset = lines[start..].iter().enumerate().find_map(|(i, line)| {
+            let search_from = if i == 0 { line.find("/*")? + 2 } else { 0 };
+            line[search_from..]
+                .find("*/")
+                .map(|pos| (i, search_from + pos + 2))
+        });
+        let (i, close) = offset?;
+        if !lines[start + i][close..].trim().is_empty() {
+            return None;
+        }
+        Some(start + i + 1)
+    } else if is_plain_line_comment(first) {
+        let count = lines[start..]
+            .iter()
+            .take_while(|line| is_plain_line_comment(line.trim_start()))
+            .count();
+        Some(start + count)
+    } else {
+        None
+    }
+}
+
+/// Checks if a (left-trimmed) line is a `//` comment that is not a doc comment.
+fn is_plain_line_comment(line: &str) -> bool {
+    line.starts_with("//") && !line.starts_with("///") && !line.starts_with("//!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKER: &str = "// license header removed";
+
+    #[test]
+    fn test_strip_apache_block_comment() {
+        let input = "/*\n * Copyright 2020 The Authors\n *\n * Licensed under the Apache License, Version 2.0 (the \"License\");\n * you may not use this file except in compliance with the License.\n */\n\npackage main;\n";
+        assert_eq!(strip_license_header(input, None), "package main;\n");
+    }
+
+    #[test]
+    fn test_strip_mit_line_comments() {
+        let input = "// Copyright (c) 2021 Someone\n//\n// Permission is hereby granted, free of charge, to any person obtaining a copy\n// of this software...\n\nuse std::io;\n";
+        assert_eq!(strip_license_header(input, None), "use std::io;\n");
+    }
+
+    #[test]
+    fn test_strip_spdx_single_line() {
+        let input = "// SPDX-License-Identifier: Apache-2.0\nfn main() {}";
+        assert_eq!(strip_license_header(input, None), "fn main() {}");
+    }
+
+    #[test]
+    fn test_strip_with_marker() {
+        let input = "/* Copyright 2022 Example. All rights reserved. */\nint x;\n";
+        assert_eq!(
+            strip_license_header(input, Some(MARKER)),
+            "// license header removed\nint x;\n"
+        );
+    }
+
+    #[test]
+    fn test_keeps_shebang() {
+        let input = "#!/usr/bin/env node\n// Copyright 2023 Me\n\nconsole.log(1);\n";
+        assert_eq!(
+            strip_license_header(input, None),
+            "#!/usr/bin/env node\nconsole.log(1);\n"
+        );
+    }
+
+    #[test]
+    fn test_ignores_comment_without_license_keywords() {
+        let input = "// Entry point for the CLI.\nfn main() {}\n";
+        assert_eq!(strip_license_header(input, None), input);
+    }
+
+    #[test]
+    fn test_ignores_header_not_at_start() {
+        let input = "use std::io;\n// Copyright 2020 Someone\n";
+        assert_eq!(strip_license_header(input, None), input);
+    }
+
+    #[test]
+    fn test_ignores_doc_comments() {
+        let input = "//! Copyright notices are handled here.\nmod license;\n";
+        assert_eq!(strip_license_header(input, None), input);
+    }
+
+    #[test]
+    fn test_ignores_block_comment_followed_by_code_on_same_line() {
+        let input = "/* Copyright 2020 */ int x;\n";
+        assert_eq!(strip_license_header(input, None), input);
+    }
+
+    #[test]
+    fn test_ignores_unterminated_block_comment() {
+        let input = "/* Copyright 2020\nint x;\n";
+        assert_eq!(strip_license_header(input, None), input);
+    }
+
+    #[test]
+    fn test_strips_only_first_block() {
+        let input = "// Copyright 2020 A\n\n// Copyright 2021 B\nfn f() {}\n";
+        assert_eq!(
+            strip_license_header(input, None),
+            "// Copyright 2021 B\nfn f() {}\n"
+        );
+    }
+}
@@ -0,0 +1,26 @@
+/// Converts Windows (`\r\n`) and classic Mac (`\r`) line endings to `\n`.
+///
+/// # Examples
+/// ```
+/// use dircat::processing::filters::normalize_line_endings;
+///
+/// assert_eq!(normalize_line_endings("a\r\nb\rc\n"), "a\nb\nc\n");
+/// ```
+pub fn normalize_line_endings(content: &str) -> String {
+    content.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_unix_line_endings() {
+        assert_eq!(normalize_line_endings("a\nb\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn test_crlf_is_not_doubled() {
+        assert_eq!(normalize_line_endings("a\r\n\r\nb"), "a\n\nb");
+    }
+}
@@ -0,0 +1,84 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+/// Matches a standalone hex or decimal literal, optionally `0x`-prefixed, or a UUID.
+static LITERAL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(0[xX])?([0-9a-fA-F]{8}(?:-[0-9a-fA-F]{4}){3}-[0-9a-fA-F]{12}|[0-9a-fA-F]+)\b")
+        .expect("literal regex is valid")
+});
+
+/// Replaces hex and decimal literals of at least `min_len` characters with a placeholder.
+///
+/// Literals made only of decimal digits become `<number:N>`; other hex literals,
+/// including UUIDs, become `<hash:N>`, where `N` is the literal's length. A `0x`
+/// prefix is kept and not counted. Words made only of the letters `a`-`f`
+/// (like `facade`) are left alone, since they contain no digit.
+///
+/// # Examples
+/// ```
+/// use dircat::processing::filters::mask_long_literals;
+///
+/// let sha = "commit = \"3f786850e387550fdab836ed7e6dc881de23001b\"";
+/// assert_eq!(mask_long_literals(sha, 16), "commit = \"<hash:40>\"");
+/// assert_eq!(mask_long_literals("port = 8080", 16), "port = 8080");
+/// ```
+pub fn mask_long_literals(content: &str, min_len: usize) -> String {
+    LITERAL_RE
+        .replace_all(content, |caps: &Captures| {
+            let literal = &caps[2];
+            if literal.len() < min_len || !literal.bytes().any(|b| b.is_ascii_digit()) {
+                return caps[0].to_string();
+            }
+            let prefix = caps.get(1).map_or("", |m| m.as_str());
+            let kind = if literal.bytes().all(|b| b.is_ascii_digit()) {
+                "number"
+            } else {
+                "hash"
+            };
+            format!("{}<{}:{}>", prefix, kind, literal.len())
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_numbers_survive() {
+        let input = "let x = 42;\nlet port = 8080;\nlet v = 1.2.3;";
+        assert_eq!(mask_long_literals(input, 8), input);
+    }
+
+    #[test]
+    fn test_masks_long_decimal_and_hex_literals() {
+        assert_eq!(
+            mask_long_literals("id = 12345678901234567890", 16),
+            "id = <number:20>"
+        );
+        assert_eq!(
+            mask_long_literals("key = 0xDEADBEEF00112233", 16),
+            "key = 0x<hash:16>"
+        );
+    }
+
+    #[test]
+    fn test_masks_uuid_as_one_literal() {
+        assert_eq!(
+            mask_long_literals("550e8400-e29b-41d4-a716-446655440000", 16),
+            "<hash:36>"
+        );
+    }
+
+    #[test]
+    fn test_keeps_words_and_identifiers() {
+        // No digit, so not a literal.
+        assert_eq!(
+            mask_long_literals("deadbeefcafebabe", 8),
+            "deadbeefcafebabe"
+        );
+        // Part of a longer identifier, so not standalone.
+        let ident = "sha1_3f786850e387550fdab836ed7e6dc881de23001b";
+        assert_eq!(mask_long_literals(ident, 8), ident);
+    }
+}
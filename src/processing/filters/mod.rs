@@ -5,10 +5,71 @@ use std::fmt;
 
 mod comments;
 mod empty_lines;
+mod imports;
+mod license;
+mod line_endings;
+mod literals;
 
 // Re-export the standalone functions
-pub use comments::remove_comments;
+pub use comments::{
+    remove_comments, remove_comments_collapsing_spaces, remove_comments_with_placeholder,
+};
 pub use empty_lines::remove_empty_lines;
+pub use imports::organize_imports;
+pub use license::strip_license_header;
+pub use line_endings::normalize_line_endings;
+pub use literals::mask_long_literals;
+
+/// A built-in [`ContentFilter`] and the command-line flags that enable it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuiltinFilter {
+    /// The filter's [`ContentFilter::name`].
+    pub name: &'static str,
+    /// The flags that add the filter, with placeholders for their values,
+    /// like `--mask-literals <LEN>`.
+    pub flags: &'static str,
+}
+
+/// Every built-in [`ContentFilter`], with the flags that enable it.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::processing::filters::BUILTIN_FILTERS;
+///
+/// let filter = BUILTIN_FILTERS.iter().find(|f| f.name == "RemoveEmptyLinesFilter").unwrap();
+/// assert_eq!(filter.flags, "--remove-empty-lines");
+/// ```
+pub const BUILTIN_FILTERS: &[BuiltinFilter] = &[
+    BuiltinFilter {
+        name: "RemoveCommentsFilter",
+        flags: "--remove-comments",
+    },
+    BuiltinFilter {
+        name: "CompactRemoveCommentsFilter",
+        flags: "--remove-comments --collapse-comment-spaces",
+    },
+    BuiltinFilter {
+        name: "RemoveEmptyLinesFilter",
+        flags: "--remove-empty-lines",
+    },
+    BuiltinFilter {
+        name: "OrganizeImportsFilter",
+        flags: "--organize-imports",
+    },
+    BuiltinFilter {
+        name: "StripLicenseHeaderFilter",
+        flags: "--strip-license",
+    },
+    BuiltinFilter {
+        name: "NormalizeLineEndingsFilter",
+        flags: "--stable",
+    },
+    BuiltinFilter {
+        name: "MaskLongLiteralsFilter",
+        flags: "--mask-literals <LEN>",
+    },
+];
 
 /// A trait for content transformation filters.
 ///
@@ -82,25 +143,62 @@ impl fmt::Debug for Box<dyn ContentFilter> {
 /// This filter uses a state machine to correctly handle comments inside
 /// string literals and other edge cases.
 ///
+/// By default comments are dropped. If `replacement` is set, each block comment
+/// is replaced with it instead (e.g., `/* ... */`), signalling that content was elided.
+///
 /// # Examples
 ///
 /// ```
 /// use dircat::processing::filters::{ContentFilter, RemoveCommentsFilter};
-/// let filter = RemoveCommentsFilter;
+/// let filter = RemoveCommentsFilter::default();
 /// assert_eq!(filter.apply("code // comment"), "code");
+///
+/// let filter = RemoveCommentsFilter { replacement: Some("/* ... */".to_string()) };
+/// assert_eq!(filter.apply("a /* b */ c"), "a /* ... */ c");
 /// ```
-#[derive(Debug, Clone)]
-pub struct RemoveCommentsFilter;
+#[derive(Debug, Clone, Default)]
+pub struct RemoveCommentsFilter {
+    /// The text to write in place of each removed block comment, if any.
+    pub replacement: Option<String>,
+}
 
 impl ContentFilter for RemoveCommentsFilter {
     fn apply(&self, content: &str) -> String {
-        comments::remove_comments(content)
+        match &self.replacement {
+            Some(replacement) => comments::remove_comments_with_placeholder(content, replacement),
+            None => comments::remove_comments(content),
+        }
     }
     fn name(&self) -> &'static str {
         "RemoveCommentsFilter"
     }
 }
 
+/// A [`ContentFilter`] that removes C/C++ style comments and collapses the
+/// whitespace left behind by removed block comments.
+///
+/// This is a variant of [`RemoveCommentsFilter`] that avoids the double spaces
+/// produced when a block comment sits between two words.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::processing::filters::{CompactRemoveCommentsFilter, ContentFilter};
+/// let filter = CompactRemoveCommentsFilter;
+/// assert_eq!(filter.apply("code /* x */ more"), "code more");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompactRemoveCommentsFilter;
+
+impl ContentFilter for CompactRemoveCommentsFilter {
+    fn apply(&self, content: &str) -> String {
+        comments::remove_comments_collapsing_spaces(content)
+    }
+    fn name(&self) -> &'static str {
+        "CompactRemoveCommentsFilter"
+    }
+}
+
 /// A [`ContentFilter`] that removes lines containing only whitespace.
 ///
 /// This filter works by splitting the content into lines, filtering out any
@@ -127,20 +225,163 @@ impl ContentFilter for RemoveEmptyLinesFilter {
     }
 }
 
+/// A [`ContentFilter`] that sorts and deduplicates runs of single-line imports.
+///
+/// See [`organize_imports`] for which imports are recognized and the limits of
+/// this heuristic.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::processing::filters::{ContentFilter, OrganizeImportsFilter};
+/// let filter = OrganizeImportsFilter;
+/// assert_eq!(filter.apply("import sys\nimport abc"), "import abc\nimport sys");
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrganizeImportsFilter;
+
+impl ContentFilter for OrganizeImportsFilter {
+    fn apply(&self, content: &str) -> String {
+        imports::organize_imports(content)
+    }
+    fn name(&self) -> &'static str {
+        "OrganizeImportsFilter"
+    }
+}
+
+/// A [`ContentFilter`] that removes a license/copyright header from the start of a file.
+///
+/// See [`strip_license_header`] for how headers are detected. If `marker` is set,
+/// it replaces the removed header on its own line.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::processing::filters::{ContentFilter, StripLicenseHeaderFilter};
+/// let filter = StripLicenseHeaderFilter::default();
+/// let input = "// SPDX-License-Identifier: MIT\nfn main() {}";
+/// assert_eq!(filter.apply(input), "fn main() {}");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StripLicenseHeaderFilter {
+    /// The line to write in place of the removed header, if any.
+    pub marker: Option<String>,
+}
+
+impl ContentFilter for StripLicenseHeaderFilter {
+    fn apply(&self, content: &str) -> String {
+        license::strip_license_header(content, self.marker.as_deref())
+    }
+    fn name(&self) -> &'static str {
+        "StripLicenseHeaderFilter"
+    }
+}
+
+/// A [`ContentFilter`] that converts `\r\n` and `\r` line endings to `\n`.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::processing::filters::{ContentFilter, NormalizeLineEndingsFilter};
+/// let filter = NormalizeLineEndingsFilter;
+/// assert_eq!(filter.apply("a\r\nb"), "a\nb");
+/// ```
+#[derive(Debug, Clone)]
+pub struct NormalizeLineEndingsFilter;
+
+impl ContentFilter for NormalizeLineEndingsFilter {
+    fn apply(&self, content: &str) -> String {
+        line_endings::normalize_line_endings(content)
+    }
+    fn name(&self) -> &'static str {
+        "NormalizeLineEndingsFilter"
+    }
+}
+
+/// A [`ContentFilter`] that replaces long hex and decimal literals with a placeholder.
+///
+/// See [`mask_long_literals`] for what counts as a literal.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::processing::filters::{ContentFilter, MaskLongLiteralsFilter};
+/// let filter = MaskLongLiteralsFilter { min_len: 16 };
+/// assert_eq!(filter.apply("n = 12345678901234567890"), "n = <number:20>");
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaskLongLiteralsFilter {
+    /// The minimum length of a literal to mask, not counting a `0x` prefix.
+    pub min_len: usize,
+}
+
+impl ContentFilter for MaskLongLiteralsFilter {
+    fn apply(&self, content: &str) -> String {
+        literals::mask_long_literals(content, self.min_len)
+    }
+    fn name(&self) -> &'static str {
+        "MaskLongLiteralsFilter"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     // --- Trait Implementation Tests ---
 
+    #[test]
+    fn test_builtin_filters_flags_build_the_named_filter() {
+        use crate::cli::Cli;
+        use crate::config::ConfigBuilder;
+        use clap::Parser;
+
+        for builtin in BUILTIN_FILTERS {
+            let args =
+                builtin
+                    .flags
+                    .split_whitespace()
+                    .map(|arg| if arg.starts_with('<') { "16" } else { arg });
+            let cli = Cli::try_parse_from(std::iter::once("dircat").chain(args))
+                .unwrap_or_else(|e| panic!("{}: {}", builtin.flags, e));
+            let config = ConfigBuilder::from_cli(cli).build().unwrap();
+            let names: Vec<&str> = config
+                .processing
+                .content_filters
+                .iter()
+                .map(|filter| filter.name())
+                .collect();
+            assert_eq!(names, [builtin.name], "{}", builtin.flags);
+        }
+    }
+
+    #[test]
+    fn test_builtin_filters_covers_every_filter_impl() {
+        let impls = include_str!("mod.rs")
+            .lines()
+            .filter(|line| line.starts_with("impl ContentFilter for "))
+            .count();
+        assert_eq!(impls, BUILTIN_FILTERS.len());
+    }
+
     #[test]
     fn test_remove_comments_filter_apply() {
-        let filter = RemoveCommentsFilter;
+        let filter = RemoveCommentsFilter::default();
         let input = "code // comment\nmore code";
         let expected = "code\nmore code";
         assert_eq!(filter.apply(input), expected);
     }
 
+    #[test]
+    fn test_strip_license_header_filter_apply() {
+        let filter = StripLicenseHeaderFilter {
+            marker: Some("// license header removed".to_string()),
+        };
+        let input = "/*\n * Copyright 2020 Someone\n */\nint main() {}\n";
+        let expected = "// license header removed\nint main() {}\n";
+        assert_eq!(filter.apply(input), expected);
+    }
+
     #[test]
     fn test_remove_empty_lines_filter_apply() {
         let filter = RemoveEmptyLinesFilter;
@@ -215,6 +456,41 @@ mod tests {
         assert_eq!(remove_comments(input), expected);
     }
 
+    #[test]
+    fn test_remove_block_comment_collapsing_spaces() {
+        assert_eq!(
+            remove_comments_collapsing_spaces("code /* x */ more"),
+            "code more"
+        );
+        assert_eq!(
+            remove_comments_collapsing_spaces("code/* x */ more"),
+            "code more"
+        );
+        assert_eq!(
+            remove_comments_collapsing_spaces("a /* x */ /* y */ b"),
+            "a b"
+        );
+    }
+
+    #[test]
+    fn test_remove_block_comment_collapsing_preserves_indentation_and_strings() {
+        let input = "fn f() {\n    /* note */ let s = \"a  /* b */  c\";\n}";
+        let expected = "fn f() {\n    let s = \"a  /* b */  c\";\n}";
+        assert_eq!(remove_comments_collapsing_spaces(input), expected);
+    }
+
+    #[test]
+    fn test_remove_comments_filter_with_replacement() {
+        let filter = RemoveCommentsFilter {
+            replacement: Some("/* ... */".to_string()),
+        };
+        let input = "fn f() /* doc\n more */ {\n    g(); // call\n}";
+        let expected = "fn f() /* ... */ {\n    g();\n}";
+        assert_eq!(filter.apply(input), expected);
+        // Comment markers inside strings are left alone
+        assert_eq!(filter.apply("\"/* s */\""), "\"/* s */\"");
+    }
+
     #[test]
     fn test_remove_block_comment_multiline() {
         let input = "code /* comment\n more comment */ more code";
@@ -0,0 +1,25 @@
+// src/processing/hash.rs
+
+//! Hashes file content for the duplicate-files report.
+
+use sha2::{Digest, Sha256};
+
+/// Returns the lowercase hex SHA-256 digest of `content`.
+pub(crate) fn content_hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_is_sha256_hex() {
+        assert_eq!(
+            content_hash(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(content_hash(b"abc"), content_hash(b"abc"));
+        assert_ne!(content_hash(b"abc"), content_hash(b"abd"));
+    }
+}
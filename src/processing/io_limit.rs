@@ -0,0 +1,85 @@
+// src/processing/io_limit.rs
+
+//! Bounds the number of files read at the same time by the parallel processing stage.
+
+use std::sync::{Condvar, Mutex};
+
+/// A counting semaphore limiting concurrent file reads.
+///
+/// This is independent of the Rayon thread count: workers beyond the limit
+/// block before opening a file until another read finishes, which keeps slow
+/// or networked storage from being flooded with requests.
+#[derive(Debug)]
+pub(crate) struct IoLimit {
+    available: Mutex<usize>,
+    released: Condvar,
+}
+
+/// A permit to read one file, returned to its [`IoLimit`] when dropped.
+#[derive(Debug)]
+pub(crate) struct IoPermit<'a> {
+    limit: &'a IoLimit,
+}
+
+impl IoLimit {
+    /// Creates a limit allowing up to `permits` reads at once.
+    pub(crate) fn new(permits: usize) -> Self {
+        Self {
+            available: Mutex::new(permits),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Blocks until a read is allowed, then returns its permit.
+    pub(crate) fn acquire(&self) -> IoPermit<'_> {
+        let mut available = self
+            .available
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        while *available == 0 {
+            available = self
+                .released
+                .wait(available)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        *available -= 1;
+        IoPermit { limit: self }
+    }
+}
+
+impl Drop for IoPermit<'_> {
+    fn drop(&mut self) {
+        *self
+            .limit
+            .available
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) += 1;
+        self.limit.released.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn test_acquire_never_exceeds_permits() {
+        let limit = IoLimit::new(2);
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    let _permit = limit.acquire();
+                    let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(10));
+                    active.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+}
@@ -0,0 +1,116 @@
+// src/processing/memory.rs
+
+//! Bounds the amount of file content held in memory by the parallel processing stage.
+
+use crate::core_types::FileInfo;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Tracks the bytes of file content currently being processed in parallel.
+///
+/// Each processing run creates its own budget, so concurrent runs in one
+/// process (such as a server embedding the library) never share a quota, and
+/// every reservation is returned by [`Reservation`]'s `Drop`, even when a
+/// worker panics.
+///
+/// Before a worker reads a file, it reserves the file's size against the budget.
+/// If the reservation would push the total over the limit, the file is deferred
+/// instead of failing, and processed later in a serial pass, when no other
+/// file content is held by the parallel workers.
+#[derive(Debug)]
+pub(crate) struct MemoryBudget {
+    limit: u64,
+    in_use: AtomicU64,
+    deferred: Mutex<Vec<FileInfo>>,
+}
+
+/// A reservation against a [`MemoryBudget`], released when dropped.
+#[derive(Debug)]
+pub(crate) struct Reservation<'a> {
+    budget: &'a MemoryBudget,
+    bytes: u64,
+}
+
+impl MemoryBudget {
+    /// Creates a budget allowing up to `limit` bytes to be processed concurrently.
+    pub(crate) fn new(limit: u64) -> Self {
+        Self {
+            limit,
+            in_use: AtomicU64::new(0),
+            deferred: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Reserves `bytes` against the budget, or returns `None` if they would exceed the limit.
+    pub(crate) fn reserve(&self, bytes: u64) -> Option<Reservation<'_>> {
+        self.in_use
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |in_use| {
+                in_use
+                    .checked_add(bytes)
+                    .filter(|total| *total <= self.limit)
+            })
+            .ok()
+            .map(|_| Reservation {
+                budget: self,
+                bytes,
+            })
+    }
+
+    /// Queues a file that did not fit in the budget for the serial pass.
+    pub(crate) fn defer(&self, file_info: FileInfo) {
+        self.deferred
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(file_info);
+    }
+
+    /// Takes all deferred files, leaving the queue empty.
+    pub(crate) fn take_deferred(&self) -> Vec<FileInfo> {
+        std::mem::take(
+            &mut *self
+                .deferred
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        )
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        self.budget.in_use.fetch_sub(self.bytes, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reserve_within_limit_and_release() {
+        let budget = MemoryBudget::new(100);
+        let a = budget.reserve(60).expect("fits");
+        assert!(budget.reserve(50).is_none());
+        drop(a);
+        assert!(budget.reserve(100).is_some());
+    }
+
+    #[test]
+    fn test_defer_and_take() {
+        let budget = MemoryBudget::new(0);
+        budget.defer(FileInfo::default());
+        assert_eq!(budget.take_deferred().len(), 1);
+        assert!(budget.take_deferred().is_empty());
+    }
+
+    #[test]
+    fn test_reservation_released_when_worker_panics() {
+        let budget = MemoryBudget::new(100);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _reservation = budget.reserve(100).expect("fits");
+            panic!("worker failed mid-read");
+        }));
+        assert!(result.is_err());
+        // The unwinding drop returned the quota, so the full budget is available again.
+        assert!(budget.reserve(100).is_some());
+    }
+}
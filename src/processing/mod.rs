@@ -5,19 +5,60 @@
 //! Operations are performed in parallel using Rayon for efficiency.
 
 use crate::cancellation::CancellationToken;
-use crate::config::{Config, ProcessingConfig};
+use crate::config::{BinaryEncoding, Config, ProcessingConfig};
+use crate::constants::MAX_GLOBAL_MEM_USAGE;
 use crate::core_types::FileInfo;
 use crate::errors::{io_error_with_path, Error, Result};
-use crate::filtering::is_likely_text_from_buffer;
-use log::debug;
+use crate::filtering::{classify_text, generated_marker, has_binary_extension, is_likely_minified};
+use crate::progress::ProgressReporter;
+use base64::prelude::*;
+use log::{debug, info};
 use rayon::prelude::*;
+use regex::Regex;
 
 use crate::core_types::FileContent;
+pub mod context;
 pub mod counter;
 pub mod filters;
-pub use counter::calculate_counts;
+mod hash;
+mod io_limit;
+mod memory;
+pub mod outline;
+mod transform;
+use context::content_matches;
+pub use context::extract_context;
+use counter::count_reader;
+pub use counter::{calculate_counts, StreamingCounter};
 use filters::ContentFilter;
+use hash::content_hash;
+use io_limit::IoLimit;
+pub(crate) use memory::MemoryBudget;
+pub use outline::outline;
 use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counts completed files and forwards the count to an optional progress reporter.
+pub(crate) struct FileProgress<'a> {
+    reporter: Option<&'a dyn ProgressReporter>,
+    completed: AtomicU64,
+}
+
+impl<'a> FileProgress<'a> {
+    pub(crate) fn new(reporter: Option<&'a dyn ProgressReporter>) -> Self {
+        Self {
+            reporter,
+            completed: AtomicU64::new(0),
+        }
+    }
+
+    /// Records that one more file has finished processing, whether or not it was kept.
+    pub(crate) fn file_done(&self) {
+        if let Some(reporter) = self.reporter {
+            let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+            reporter.set_position(completed);
+        }
+    }
+}
 
 /// A struct holding borrowed configuration relevant to the processing stage.
 ///
@@ -28,18 +69,54 @@ use std::fs;
 pub struct ProcessingOptions<'a> {
     /// Whether to include files detected as binary/non-text.
     pub include_binary: bool,
+    /// Whether to keep *only* files detected as binary/non-text.
+    pub only_binary: bool,
+    /// How the content of included binary files is stored.
+    pub binary_encoding: BinaryEncoding,
     /// Whether to calculate and display line, character, and word counts in the summary.
     pub counts: bool,
+    /// If `Some(n)`, text files with more than `n` lines are skipped.
+    pub max_lines: Option<usize>,
+    /// Whether to skip text files that look minified.
+    pub skip_minified: bool,
+    /// Whether to skip text files with a generated-code marker in their first lines.
+    pub skip_generated: bool,
+    /// Whether to reduce supported source files to an outline of their declarations.
+    pub outline: bool,
+    /// Whether to record each text file's content hash for the duplicate-files report.
+    pub hash_content: bool,
+    /// If `Some`, only content matching at least one of these regexes is kept.
+    pub content_match: Option<&'a [Regex]>,
+    /// If `Some(n)`, matched content is condensed to windows of `n` lines around each match.
+    pub context_lines: Option<usize>,
     /// A vector of content filters to be applied sequentially to each file's content.
     pub content_filters: &'a [Box<dyn ContentFilter>],
+    /// If `Some(n)`, processed text content is truncated to at most `n` bytes.
+    pub max_file_content_bytes: Option<usize>,
 }
 
 impl<'a> From<&'a Config> for ProcessingOptions<'a> {
     fn from(config: &'a Config) -> Self {
+        Self::from(&config.processing)
+    }
+}
+
+impl<'a> From<&'a ProcessingConfig> for ProcessingOptions<'a> {
+    fn from(config: &'a ProcessingConfig) -> Self {
         Self {
-            include_binary: config.processing.include_binary,
-            counts: config.processing.counts,
-            content_filters: &config.processing.content_filters,
+            include_binary: config.include_binary,
+            only_binary: config.only_binary,
+            binary_encoding: config.binary_encoding,
+            counts: config.counts,
+            max_lines: config.max_lines,
+            skip_minified: config.skip_minified,
+            skip_generated: config.skip_generated,
+            outline: config.outline,
+            hash_content: config.hash_content,
+            content_match: config.content_match.as_deref(),
+            context_lines: config.context_lines,
+            content_filters: &config.content_filters,
+            max_file_content_bytes: config.max_file_content_bytes,
         }
     }
 }
@@ -71,7 +148,12 @@ impl<'a> From<&'a Config> for ProcessingOptions<'a> {
 ///     content: b"fn main() {}".to_vec(),
 ///     ..Default::default()
 /// }];
-/// let opts = ProcessingOptions { include_binary: false, counts: false, content_filters: &[] };
+/// let opts = ProcessingOptions {
+///     include_binary: false, only_binary: false, binary_encoding: Default::default(),
+///     counts: false, max_lines: None, skip_minified: false, skip_generated: false, outline: false,
+///     hash_content: false, content_match: None, context_lines: None, content_filters: &[],
+///     max_file_content_bytes: None,
+/// };
 /// let token = CancellationToken::new();
 /// let processed_files: Vec<_> = process_content(files_content.into_iter(), opts, &token).collect::<Result<Vec<_>, _>>()?;
 ///
@@ -83,6 +165,17 @@ pub fn process_content<'a>(
     files_content: impl Iterator<Item = FileContent> + Send + 'a,
     opts: ProcessingOptions<'a>,
     token: &'a CancellationToken,
+) -> impl Iterator<Item = Result<FileInfo>> {
+    process_content_with(files_content, opts, None, token)
+}
+
+/// Like [`process_content`], but also runs the steps of `full_config` that
+/// `ProcessingOptions` does not carry, so replaying an archive matches a run from disk.
+pub(crate) fn process_content_with<'a>(
+    files_content: impl Iterator<Item = FileContent> + Send + 'a,
+    opts: ProcessingOptions<'a>,
+    full_config: Option<&'a ProcessingConfig>,
+    token: &'a CancellationToken,
 ) -> impl Iterator<Item = Result<FileInfo>> {
     files_content
         .par_bridge()
@@ -96,58 +189,24 @@ pub fn process_content<'a>(
                 file_content.relative_path.display()
             );
 
-            let content_bytes = &file_content.content;
-
-            // --- Perform Binary Check ---
-            let is_binary = !is_likely_text_from_buffer(content_bytes);
-
-            // --- Filter Based on Binary Check ---
-            if is_binary && !opts.include_binary {
-                debug!(
-                    "Skipping binary content: {}",
-                    file_content.relative_path.display()
-                );
-                return None;
-            }
-
-            // --- Process Content ---
-            let original_content_str = String::from_utf8_lossy(content_bytes).to_string();
-
-            let mut file_info = FileInfo {
+            let file_info = FileInfo {
                 // Since we are decoupled from the FS, absolute_path is the same as relative.
                 absolute_path: file_content.relative_path.clone(),
                 relative_path: file_content.relative_path,
-                size: content_bytes.len() as u64,
+                size: file_content.content.len() as u64,
                 processed_content: None,
                 counts: None,
                 is_process_last: file_content.is_process_last,
                 process_last_order: file_content.process_last_order,
-                is_binary,
+                is_binary: false,
+                is_minified: false,
+                is_dir: false,
+                symlink_target: None,
+                discovery_index: None,
+                content_hash: None,
+                raw_content: None,
             };
-
-            // --- Calculate Counts ---
-            if opts.counts {
-                file_info.counts = Some(if is_binary {
-                    crate::core_types::FileCounts {
-                        lines: 0,
-                        characters: content_bytes.len(),
-                        words: 0,
-                    }
-                } else {
-                    calculate_counts(&original_content_str)
-                });
-            }
-
-            // --- Apply Content Filters ---
-            file_info.processed_content = Some(if !is_binary {
-                opts.content_filters
-                    .iter()
-                    .fold(original_content_str, |acc, filter| filter.apply(&acc))
-            } else {
-                original_content_str
-            });
-
-            Some(Ok(file_info))
+            process_loaded_content(file_info, file_content.content, None, &opts, full_config)
         })
         .collect::<Vec<_>>()
         .into_iter()
@@ -170,14 +229,22 @@ pub fn process_content<'a>(
 /// A `Result` containing a new `Vec<FileInfo>` with processed content. Files that
 /// were filtered out (e.g., binaries) are not included in the returned vector.
 ///
+/// Files whose size does not fit in the remaining `budget` are not processed here;
+/// they are queued on the budget for a later serial pass (see [`process_files`]).
+/// Every other file is reported to `progress` once it has been processed. If
+/// `config.io_concurrency` is set, at most that many files are read at once.
+///
 /// # Errors
 /// Returns an error if file I/O fails for any file or if the operation is interrupted.
 pub(crate) fn process_and_filter_files_internal<'a>(
     files: impl ParallelIterator<Item = FileInfo> + 'a,
     config: &'a ProcessingConfig,
     token: &'a CancellationToken,
+    budget: &'a MemoryBudget,
+    progress: &'a FileProgress<'a>,
 ) -> impl ParallelIterator<Item = Result<FileInfo>> + 'a {
-    files.filter_map(move |mut file_info| {
+    let io_limit = config.io_concurrency.map(IoLimit::new);
+    files.filter_map(move |file_info| {
         // The closure captures `config` and `token` which have lifetime 'a
         // Check for cancellation signal. If cancelled, return an error for this item
         // and subsequent calls will be filtered out by the was_cancelled flag.
@@ -185,75 +252,353 @@ pub(crate) fn process_and_filter_files_internal<'a>(
             return Some(Err(Error::Interrupted));
         }
 
-        debug!("Processing file: {}", file_info.absolute_path.display());
-
-        // --- 1. Read File Content (once) ---
-        let content_bytes = match fs::read(&file_info.absolute_path) {
-            Ok(bytes) => bytes,
-            Err(e) => {
-                let app_err = io_error_with_path(e, &file_info.absolute_path);
-                return Some(Err(app_err));
+        // Defer files that would exceed the memory budget instead of failing them.
+        let _reservation = match budget.reserve(file_info.size) {
+            Some(reservation) => reservation,
+            None => {
+                debug!(
+                    "Memory budget exceeded, deferring file to serial pass: {}",
+                    file_info.relative_path.display()
+                );
+                budget.defer(file_info);
+                return None;
             }
         };
 
-        // --- 2. Perform Binary Check ---
-        let is_binary = !is_likely_text_from_buffer(&content_bytes);
-        file_info.is_binary = is_binary;
+        let result = process_single_file(file_info, config, io_limit.as_ref());
+        progress.file_done();
+        result
+    })
+}
+
+/// Reads and processes a single discovered file.
+///
+/// Returns `None` if the file is filtered out (e.g., binary or minified).
+fn process_single_file(
+    file_info: FileInfo,
+    config: &ProcessingConfig,
+    io_limit: Option<&IoLimit>,
+) -> Option<Result<FileInfo>> {
+    // Directories and symlinks have no content to read, so pass them through untouched.
+    if file_info.is_dir || file_info.is_symlink() {
+        return Some(Ok(file_info));
+    }
+
+    debug!("Processing file: {}", file_info.absolute_path.display());
 
-        // --- 3. Filter Based on Binary Check ---
-        if is_binary && !config.include_binary {
+    // --- 0. Trust the Extension, if Configured ---
+    // The binary verdict is known before the read, so filtered-out files are never opened.
+    let trusted_binary = config
+        .trust_extensions
+        .then(|| has_binary_extension(&file_info.relative_path));
+    if let Some(is_binary) = trusted_binary {
+        if !passes_binary_filter(is_binary, config) {
             debug!(
-                "Skipping binary file: {}",
+                "Skipping file by extension (binary: {}): {}",
+                is_binary,
                 file_info.relative_path.display()
             );
-            return None; // Filter out this file by returning None
+            return None;
         }
+    }
 
-        // --- 4. Process Content ---
-        let original_content_str = String::from_utf8_lossy(&content_bytes).to_string();
-
-        // --- Calculate Counts ---
-        if config.counts {
-            if is_binary {
-                file_info.counts = Some(crate::core_types::FileCounts {
-                    lines: 0,
-                    characters: content_bytes.len(),
-                    words: 0,
-                });
-            } else {
-                file_info.counts = Some(calculate_counts(&original_content_str));
-            }
-            debug!(
-                "Calculated counts for {}: {:?}",
-                file_info.relative_path.display(),
-                file_info.counts
-            );
+    // --- 1. Read File Content (once) ---
+    // The permit is held only while the file is open, not while it is processed.
+    let read_result = {
+        let _permit = io_limit.map(IoLimit::acquire);
+        fs::read(&file_info.absolute_path)
+    };
+    let content_bytes = match read_result {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            let app_err = io_error_with_path(e, &file_info.absolute_path);
+            return Some(Err(app_err));
         }
+    };
+
+    process_loaded_content(
+        file_info,
+        content_bytes,
+        trusted_binary,
+        &ProcessingOptions::from(config),
+        Some(config),
+    )
+}
 
-        // --- Apply Content Filters ---
-        let mut processed_content = original_content_str;
-        if !is_binary {
-            // Apply all configured filters sequentially
-            for filter in &config.content_filters {
-                processed_content = filter.apply(&processed_content);
+/// Runs the content steps shared by files read from disk and content given in memory.
+///
+/// `known_binary` is the binary verdict if it was already decided (and filtered on)
+/// before the read. `full_config`, if given, adds the steps `opts` does not carry:
+/// the transform command, the post-process callback, and keeping the raw bytes.
+/// Returns `None` if the file is filtered out.
+fn process_loaded_content(
+    mut file_info: FileInfo,
+    content_bytes: Vec<u8>,
+    known_binary: Option<bool>,
+    opts: &ProcessingOptions,
+    full_config: Option<&ProcessingConfig>,
+) -> Option<Result<FileInfo>> {
+    // --- 2. Perform Binary Check ---
+    let is_binary = match known_binary {
+        Some(is_binary) => is_binary,
+        None => {
+            let classification = classify_text(&content_bytes);
+            let is_binary = !classification.is_text();
+
+            // --- 3. Filter Based on Binary Check ---
+            if is_binary && !(opts.include_binary || opts.only_binary) {
+                debug!(
+                    "Skipping binary file ({}): {}",
+                    classification,
+                    file_info.relative_path.display()
+                );
+                return None; // Filter out this file by returning None
+            }
+            if !is_binary && opts.only_binary {
                 debug!(
-                    "Applied filter '{}' to {}",
-                    filter.name(),
+                    "Skipping text file (only binary): {}",
                     file_info.relative_path.display()
                 );
+                return None;
             }
+            is_binary
+        }
+    };
+    file_info.is_binary = is_binary;
+    if opts.hash_content && !is_binary {
+        file_info.content_hash = Some(content_hash(&content_bytes));
+    }
+
+    // --- 4. Filter by Line Count ---
+    if let (false, Some(max_lines)) = (is_binary, opts.max_lines) {
+        let lines = count_lines(&content_bytes);
+        if lines > max_lines {
+            debug!(
+                "Skipping file with {} lines (max {}): {}",
+                lines,
+                max_lines,
+                file_info.relative_path.display()
+            );
+            return None;
+        }
+    }
+
+    // --- 5. Process Content ---
+    let original_content_str = String::from_utf8_lossy(&content_bytes).to_string();
+
+    // --- 6. Filter Minified Content ---
+    file_info.is_minified = !is_binary && is_likely_minified(&original_content_str);
+    if file_info.is_minified && opts.skip_minified {
+        debug!(
+            "Skipping minified file: {}",
+            file_info.relative_path.display()
+        );
+        return None;
+    }
+
+    // --- 6b. Filter Generated Code ---
+    if opts.skip_generated && !is_binary {
+        if let Some(marker) = generated_marker(&original_content_str) {
+            info!(
+                "Skipping generated file (marker '{}'): {}",
+                marker,
+                file_info.relative_path.display()
+            );
+            return None;
+        }
+    }
+
+    // --- 7. Filter by Content Match ---
+    if let Some(regexes) = opts.content_match {
+        if is_binary || !content_matches(&original_content_str, regexes) {
+            debug!(
+                "Skipping file without a content match: {}",
+                file_info.relative_path.display()
+            );
+            return None;
+        }
+    }
+
+    // --- Calculate Counts ---
+    if opts.counts {
+        if is_binary {
+            file_info.counts = Some(crate::core_types::FileCounts {
+                lines: 0,
+                characters: content_bytes.len(),
+                words: 0,
+            });
         } else {
+            file_info.counts = Some(calculate_counts(&original_content_str));
+        }
+        debug!(
+            "Calculated counts for {}: {:?}",
+            file_info.relative_path.display(),
+            file_info.counts
+        );
+    }
+
+    // --- Apply Content Filters ---
+    let mut processed_content = original_content_str;
+    if !is_binary {
+        // Run the external transform first, so built-in filters see its output
+        if let Some(command) = full_config.and_then(|c| c.transform_cmd.as_ref()) {
+            processed_content = match transform::run_transform(
+                command,
+                &file_info.absolute_path,
+                &processed_content,
+            ) {
+                Ok(transformed) => transformed,
+                Err(e) => return Some(Err(e)),
+            };
+            debug!(
+                "Applied transform command to {}",
+                file_info.relative_path.display()
+            );
+        }
+        // Apply all configured filters sequentially
+        for filter in opts.content_filters {
+            processed_content = filter.apply(&processed_content);
             debug!(
-                "Skipping content filters for binary file {}",
+                "Applied filter '{}' to {}",
+                filter.name(),
                 file_info.relative_path.display()
             );
         }
+        // Reduce supported languages to their declarations; others keep their content
+        if opts.outline {
+            if let Some(outlined) = file_info
+                .language()
+                .and_then(|language| outline(&processed_content, language))
+            {
+                processed_content = outlined;
+                debug!("Outlined {}", file_info.relative_path.display());
+            }
+        }
+        // Then the library callback, which sees the filtered content
+        if let Some(callback) = full_config.and_then(|c| c.post_process_callback.as_ref()) {
+            processed_content = callback.apply(&file_info, processed_content);
+        }
+        // Condense to the regions around matches, after all filters have run
+        if let (Some(regexes), Some(lines)) = (opts.content_match, opts.context_lines) {
+            processed_content = extract_context(&processed_content, regexes, lines);
+            // Every match was in content the filters removed, such as a comment
+            if processed_content.is_empty() {
+                debug!(
+                    "Skipping file whose content matches were all filtered out: {}",
+                    file_info.relative_path.display()
+                );
+                return None;
+            }
+        }
+        // Cap the file's contribution last, so the limit applies to what is output
+        if let Some(max_bytes) = opts.max_file_content_bytes {
+            processed_content = truncate_to_bytes(processed_content, max_bytes);
+        }
+    } else {
+        debug!(
+            "Skipping content filters for binary file {}",
+            file_info.relative_path.display()
+        );
+        processed_content = encode_binary(&content_bytes, processed_content, opts.binary_encoding);
+    }
 
-        // Store the final processed content
-        file_info.processed_content = Some(processed_content);
+    // Store the final processed content
+    file_info.processed_content = Some(processed_content);
+    if full_config.is_some_and(|c| c.keep_raw_content) {
+        file_info.raw_content = Some(content_bytes);
+    }
 
-        Some(Ok(file_info))
-    })
+    Some(Ok(file_info))
+}
+
+/// Returns the content stored for a binary file: its lossy UTF-8 decoding as-is,
+/// or its bytes as base64 in lines of 76 characters.
+fn encode_binary(bytes: &[u8], lossy: String, encoding: BinaryEncoding) -> String {
+    match encoding {
+        BinaryEncoding::Lossy => lossy,
+        BinaryEncoding::Base64 => {
+            let encoded = BASE64_STANDARD.encode(bytes);
+            let mut wrapped = String::with_capacity(encoded.len() + encoded.len() / 76 + 1);
+            for chunk in encoded.as_bytes().chunks(76) {
+                // Base64 output is ASCII, so every chunk is valid UTF-8.
+                wrapped.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+                wrapped.push('\n');
+            }
+            wrapped
+        }
+    }
+}
+
+/// Reads a file only to fill in its counts, discarding the content.
+///
+/// This is used by dry runs with `dry_run_counts`. The file is read in chunks
+/// by a [`StreamingCounter`], so its content is never held in memory at once.
+/// As in a full run, binary files are counted by size alone and no content
+/// filters are applied.
+///
+/// # Errors
+/// Returns an error if the file cannot be read.
+pub(crate) fn count_file(file_info: &mut FileInfo) -> Result<()> {
+    let (counts, is_binary) = fs::File::open(&file_info.absolute_path)
+        .and_then(count_reader)
+        .map_err(|e| io_error_with_path(e, &file_info.absolute_path))?;
+    file_info.is_binary = is_binary;
+    file_info.counts = Some(counts);
+    Ok(())
+}
+
+/// Reads a file only to fill in its content hash, for a dry run.
+///
+/// Binary files are left without a hash, as in a normal run.
+///
+/// # Errors
+/// Returns an error if the file cannot be read.
+pub(crate) fn hash_file(file_info: &mut FileInfo) -> Result<()> {
+    let content_bytes = fs::read(&file_info.absolute_path)
+        .map_err(|e| io_error_with_path(e, &file_info.absolute_path))?;
+    if classify_text(&content_bytes).is_text() {
+        file_info.content_hash = Some(content_hash(&content_bytes));
+    }
+    Ok(())
+}
+
+/// Counts lines the way `str::lines` would, without decoding the content.
+fn count_lines(content: &[u8]) -> usize {
+    let newlines = content.iter().filter(|&&b| b == b'\n').count();
+    match content.last() {
+        Some(b'\n') | None => newlines,
+        Some(_) => newlines + 1,
+    }
+}
+
+/// Returns `true` if a file with this binary verdict is kept by the
+/// `include_binary` and `only_binary` settings.
+fn passes_binary_filter(is_binary: bool, config: &ProcessingConfig) -> bool {
+    if is_binary {
+        config.include_binary || config.only_binary
+    } else {
+        !config.only_binary
+    }
+}
+
+/// Truncates content to at most `max_bytes` bytes, backing off to a UTF-8 char
+/// boundary, and appends a note on its own line saying where it was cut.
+///
+/// Content that already fits is returned unchanged.
+fn truncate_to_bytes(mut content: String, max_bytes: usize) -> String {
+    if content.len() <= max_bytes {
+        return content;
+    }
+    let mut end = max_bytes;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    content.truncate(end);
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(&format!("... (truncated at {} bytes)", max_bytes));
+    content
 }
 
 /// Processes a list of discovered files.
@@ -350,12 +695,80 @@ pub fn process_files<'a>(
     files: impl Iterator<Item = FileInfo> + Send + 'a,
     config: &'a ProcessingConfig,
     token: &'a CancellationToken,
+) -> impl Iterator<Item = Result<FileInfo>> {
+    process_files_with_progress(files, config, token, None)
+}
+
+/// Processes a list of discovered files, reporting per-file progress.
+///
+/// This behaves like [`process_files`], but calls `set_position` on `progress`
+/// with the number of files completed so far as each file finishes. Files that
+/// are filtered out still count as completed. Setting the total length is left
+/// to the caller, which knows how many files were discovered.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::config::{self, ConfigBuilder};
+/// use dircat::progress::{NoOpProgress, ProgressReporter};
+/// use dircat::{discover, process_files_with_progress, CancellationToken};
+/// use tempfile::tempdir;
+/// use std::fs;
+///
+/// # fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
+/// let temp = tempdir()?;
+/// fs::write(temp.path().join("a.rs"), "fn main(){}")?;
+/// let config = ConfigBuilder::new()
+///     .input_path(temp.path().to_str().unwrap())
+///     .build()?;
+///
+/// let resolved = config::resolve_input(&config.input_path, &None, None, &None, &None, false, None)?;
+/// let token = CancellationToken::new();
+///
+/// let discovered: Vec<_> = discover(&config.discovery, &resolved, &token)?.collect();
+/// let reporter = NoOpProgress;
+/// reporter.set_length(discovered.len() as u64);
+/// let processed: Vec<_> = process_files_with_progress(
+///     discovered.into_iter(),
+///     &config.processing,
+///     &token,
+///     Some(&reporter),
+/// )
+/// .collect::<dircat::errors::Result<_>>()?;
+/// assert_eq!(processed.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn process_files_with_progress<'a>(
+    files: impl Iterator<Item = FileInfo> + Send + 'a,
+    config: &'a ProcessingConfig,
+    token: &'a CancellationToken,
+    progress: Option<&'a dyn ProgressReporter>,
 ) -> impl Iterator<Item = Result<FileInfo>> {
     // Bridge the sequential iterator to a parallel one for processing,
     // then collect the results into a vector to return a sequential iterator.
-    process_and_filter_files_internal(files.par_bridge(), config, token)
-        .collect::<Vec<_>>()
-        .into_iter()
+    let budget = MemoryBudget::new(MAX_GLOBAL_MEM_USAGE);
+    let file_progress = FileProgress::new(progress);
+    let mut results = process_and_filter_files_internal(
+        files.par_bridge(),
+        config,
+        token,
+        &budget,
+        &file_progress,
+    )
+    .collect::<Vec<_>>();
+
+    // Files that did not fit in the memory budget are processed one at a time,
+    // now that the parallel workers no longer hold any content.
+    for file_info in budget.take_deferred() {
+        if token.is_cancelled() {
+            results.push(Err(Error::Interrupted));
+            break;
+        }
+        results.extend(process_single_file(file_info, config, None));
+        file_progress.file_done();
+    }
+    results.into_iter()
 }
 
 #[cfg(test)]
@@ -383,6 +796,12 @@ mod tests {
             is_process_last: false,
             process_last_order: None,
             is_binary: false,
+            is_minified: false,
+            is_dir: false,
+            symlink_target: None,
+            discovery_index: None,
+            content_hash: None,
+            raw_content: None,
         };
 
         (dir, file_info)
@@ -398,7 +817,7 @@ mod tests {
         config
             .processing
             .content_filters
-            .push(Box::new(RemoveCommentsFilter));
+            .push(Box::new(RemoveCommentsFilter::default()));
         config
             .processing
             .content_filters
@@ -408,6 +827,8 @@ mod tests {
             vec![file_info].into_par_iter(),
             &config.processing,
             &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
         )
         .collect::<Result<_>>()?;
 
@@ -436,6 +857,8 @@ mod tests {
             vec![file_info].into_par_iter(),
             &config.processing,
             &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
         )
         .collect::<Result<_>>()?;
 
@@ -449,6 +872,116 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_process_files_max_file_content_bytes() -> Result<()> {
+        let (_dir, file_info) = setup_test_file("// comment\nlet s = \"héllo\";\n".as_bytes());
+        let token = CancellationToken::new();
+
+        let mut config = Config::new_for_test();
+        config
+            .processing
+            .content_filters
+            .push(Box::new(RemoveCommentsFilter::default()));
+        config.processing.max_file_content_bytes = Some(11); // Cuts through 'é'
+
+        let processed: Vec<_> = process_and_filter_files_internal(
+            vec![file_info].into_par_iter(),
+            &config.processing,
+            &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
+        )
+        .collect::<Result<_>>()?;
+
+        // The limit applies after filters, and backs off to a char boundary.
+        assert_eq!(
+            processed[0].processed_content.as_deref(),
+            Some("let s = \"h\n... (truncated at 11 bytes)")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_files_with_io_concurrency_limit() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let files: Vec<FileInfo> = (0..8)
+            .map(|i| {
+                let path = dir.path().join(format!("f{}.txt", i));
+                fs::write(&path, format!("file {}", i)).unwrap();
+                FileInfo {
+                    absolute_path: path,
+                    relative_path: PathBuf::from(format!("f{}.txt", i)),
+                    size: 6,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let token = CancellationToken::new();
+        let mut config = Config::new_for_test();
+        config.processing.io_concurrency = Some(1);
+
+        let processed: Vec<_> = process_and_filter_files_internal(
+            files.into_par_iter(),
+            &config.processing,
+            &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
+        )
+        .collect::<Result<_>>()?;
+        assert_eq!(processed.len(), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_files_trust_extensions_skips_read() -> Result<()> {
+        let dir = tempdir().unwrap();
+        let text_path = dir.path().join("data.txt");
+        fs::write(&text_path, b"null\0byte").unwrap();
+        let files = vec![
+            // Never created: reading it would fail, so it must be skipped by name.
+            FileInfo {
+                absolute_path: dir.path().join("missing.png"),
+                relative_path: PathBuf::from("missing.png"),
+                ..Default::default()
+            },
+            FileInfo {
+                absolute_path: text_path,
+                relative_path: PathBuf::from("data.txt"),
+                size: 9,
+                ..Default::default()
+            },
+        ];
+        let token = CancellationToken::new();
+        let mut config = Config::new_for_test();
+        config.processing.trust_extensions = true;
+
+        let processed: Vec<_> = process_and_filter_files_internal(
+            files.into_par_iter(),
+            &config.processing,
+            &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
+        )
+        .collect::<Result<_>>()?;
+        assert_eq!(processed.len(), 1);
+        assert_eq!(processed[0].relative_path, PathBuf::from("data.txt"));
+        assert!(!processed[0].is_binary);
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncate_to_bytes() {
+        assert_eq!(truncate_to_bytes("short".to_string(), 5), "short");
+        assert_eq!(
+            truncate_to_bytes("line 1\nline 2\n".to_string(), 7),
+            "line 1\n... (truncated at 7 bytes)"
+        );
+        assert_eq!(
+            truncate_to_bytes("日本".to_string(), 2),
+            "... (truncated at 2 bytes)"
+        );
+    }
+
     #[test]
     fn test_process_files_skips_filters_for_binary() -> Result<()> {
         // This content will be detected as binary
@@ -461,12 +994,14 @@ mod tests {
         config
             .processing
             .content_filters
-            .push(Box::new(RemoveCommentsFilter));
+            .push(Box::new(RemoveCommentsFilter::default()));
 
         let processed: Vec<_> = process_and_filter_files_internal(
             vec![file_info].into_par_iter(),
             &config.processing,
             &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
         )
         .collect::<Result<_>>()?;
 
@@ -481,4 +1016,191 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_process_files_only_binary() -> Result<()> {
+        let token = CancellationToken::new();
+        let mut config = Config::new_for_test();
+        config.processing.only_binary = true;
+
+        let (_dir, text_file) = setup_test_file(b"fn main() {}");
+        let (_dir2, binary_file) = setup_test_file(b"binary\0content");
+        let processed: Vec<_> = process_and_filter_files_internal(
+            vec![text_file, binary_file].into_par_iter(),
+            &config.processing,
+            &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
+        )
+        .collect::<Result<_>>()?;
+
+        assert_eq!(processed.len(), 1);
+        assert!(processed[0].is_binary);
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_files_skip_minified() -> Result<()> {
+        let minified = "var a=1;".repeat(200);
+        let token = CancellationToken::new();
+        let mut config = Config::new_for_test();
+
+        // Detected but kept by default
+        let (_dir, file_info) = setup_test_file(minified.as_bytes());
+        let processed: Vec<_> = process_and_filter_files_internal(
+            vec![file_info].into_par_iter(),
+            &config.processing,
+            &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
+        )
+        .collect::<Result<_>>()?;
+        assert_eq!(processed.len(), 1);
+        assert!(processed[0].is_minified);
+
+        // Skipped when requested
+        config.processing.skip_minified = true;
+        let (_dir2, file_info2) = setup_test_file(minified.as_bytes());
+        let processed: Vec<_> = process_and_filter_files_internal(
+            vec![file_info2].into_par_iter(),
+            &config.processing,
+            &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
+        )
+        .collect::<Result<_>>()?;
+        assert!(processed.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_files_max_lines() -> Result<()> {
+        let token = CancellationToken::new();
+        let mut config = Config::new_for_test();
+        config.processing.max_lines = Some(2);
+
+        let (_dir, short) = setup_test_file(b"one\ntwo\n");
+        let (_dir2, long) = setup_test_file(b"one\ntwo\nthree");
+        let processed: Vec<_> = process_and_filter_files_internal(
+            vec![short, long].into_par_iter(),
+            &config.processing,
+            &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
+        )
+        .collect::<Result<_>>()?;
+        assert_eq!(processed.len(), 1);
+        assert_eq!(
+            processed[0].processed_content.as_deref(),
+            Some("one\ntwo\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_lines() {
+        assert_eq!(count_lines(b""), 0);
+        assert_eq!(count_lines(b"a"), 1);
+        assert_eq!(count_lines(b"a\n"), 1);
+        assert_eq!(count_lines(b"a\n\nb"), 3);
+    }
+
+    #[test]
+    fn test_process_files_content_match_with_context() -> Result<()> {
+        let token = CancellationToken::new();
+        let mut config = Config::new_for_test();
+        config.processing.content_match = Some(vec![regex::Regex::new("needle").unwrap()]);
+        config.processing.context_lines = Some(1);
+
+        let (_dir, matching) = setup_test_file(b"a\nb\nneedle\nc\nd\ne");
+        let (_dir2, other) = setup_test_file(b"nothing here");
+        let processed: Vec<_> = process_and_filter_files_internal(
+            vec![matching, other].into_par_iter(),
+            &config.processing,
+            &token,
+            &MemoryBudget::new(MAX_GLOBAL_MEM_USAGE),
+            &FileProgress::new(None),
+        )
+        .collect::<Result<_>>()?;
+
+        assert_eq!(processed.len(), 1);
+        assert_eq!(
+            processed[0].processed_content.as_deref(),
+            Some("...\nb\nneedle\nc\n...")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_files_defers_files_over_memory_budget() -> Result<()> {
+        let token = CancellationToken::new();
+        let config = Config::new_for_test();
+        let (_dir, mut file_info) = setup_test_file(b"fn main() {}");
+        file_info.size = 12;
+
+        // With no budget, the file is deferred rather than processed or dropped.
+        let budget = MemoryBudget::new(0);
+        let processed: Vec<_> = process_and_filter_files_internal(
+            vec![file_info].into_par_iter(),
+            &config.processing,
+            &token,
+            &budget,
+            &FileProgress::new(None),
+        )
+        .collect::<Result<_>>()?;
+        assert!(processed.is_empty());
+
+        let deferred = budget.take_deferred();
+        assert_eq!(deferred.len(), 1);
+
+        // The serial pass processes it normally.
+        let result = process_single_file(
+            deferred.into_iter().next().unwrap(),
+            &config.processing,
+            None,
+        );
+        let file_info = result.expect("file should be kept")?;
+        assert_eq!(file_info.processed_content.as_deref(), Some("fn main() {}"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_files_with_progress_counts_every_file() -> Result<()> {
+        use std::sync::Mutex;
+
+        struct RecordingProgress(Mutex<Vec<u64>>);
+        impl ProgressReporter for RecordingProgress {
+            fn set_length(&self, _len: u64) {}
+            fn set_position(&self, pos: u64) {
+                self.0.lock().unwrap().push(pos);
+            }
+            fn set_message(&self, _msg: String) {}
+            fn finish(&self) {}
+            fn finish_with_message(&self, _msg: String) {}
+        }
+
+        let token = CancellationToken::new();
+        let config = Config::new_for_test();
+        let (_dir1, text) = setup_test_file(b"fn main() {}");
+        let (_dir2, binary) = setup_test_file(b"\0\x01\x02binary");
+        let (_dir3, mut deferred) = setup_test_file(b"fn big() {}");
+        deferred.size = MAX_GLOBAL_MEM_USAGE + 1; // Forces the serial pass
+
+        let reporter = RecordingProgress(Mutex::new(Vec::new()));
+        let processed: Vec<_> = process_files_with_progress(
+            vec![text, binary, deferred].into_iter(),
+            &config.processing,
+            &token,
+            Some(&reporter),
+        )
+        .collect::<Result<_>>()?;
+
+        // The binary file is filtered out but still counts as completed.
+        assert_eq!(processed.len(), 2);
+        let mut positions = reporter.0.into_inner().unwrap();
+        positions.sort_unstable();
+        assert_eq!(positions, vec![1, 2, 3]);
+        Ok(())
+    }
 }
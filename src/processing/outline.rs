@@ -0,0 +1,663 @@
+// src/processing/outline.rs
+
+//! Reduces source files to their declarations for `--outline`.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// The placeholder written in place of an omitted body.
+pub const OUTLINE_BODY_PLACEHOLDER: &str = "...";
+
+/// The longest signature, in lines, that is searched for the start of a body.
+const MAX_SIGNATURE_LINES: usize = 20;
+
+/// How a declaration recognized by the outline is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeclKind {
+    /// The signature is kept and the body is replaced with `{ ... }`.
+    Body,
+    /// The signature is kept and the body is scanned for nested declarations.
+    Container,
+    /// The whole declaration is kept, e.g. a struct with its fields.
+    Whole,
+}
+
+/// A brace-delimited language's declaration patterns.
+struct BraceSyntax {
+    /// Patterns recognized at any level, checked in order.
+    decls: Vec<(Regex, DeclKind)>,
+    /// Patterns recognized only directly inside a container, like class methods.
+    members: Vec<(Regex, DeclKind)>,
+    /// Characters that delimit string literals, whose braces are not counted.
+    quotes: &'static [char],
+    /// The subset of `quotes` whose literals may continue onto later lines.
+    multiline_quotes: &'static [char],
+    /// Whether `'x'` is a character literal rather than a quote and `r#"..."#` is
+    /// a raw string, as in Rust.
+    rust_literals: bool,
+}
+
+static RUST: Lazy<BraceSyntax> = Lazy::new(|| {
+    let vis = r"^\s*(?:pub(?:\([^)]*\))?\s+)?";
+    BraceSyntax {
+        decls: vec![
+            (
+                Regex::new(&format!(
+                    r#"{vis}(?:default\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+"[^"]*"\s+)?fn\s"#
+                ))
+                .unwrap(),
+                DeclKind::Body,
+            ),
+            (
+                Regex::new(&format!(r"{vis}(?:unsafe\s+)?(?:impl|trait|mod)\b")).unwrap(),
+                DeclKind::Container,
+            ),
+            (
+                Regex::new(&format!(r"{vis}(?:struct|enum|union|type)\s")).unwrap(),
+                DeclKind::Whole,
+            ),
+        ],
+        members: Vec::new(),
+        quotes: &['"'],
+        multiline_quotes: &['"'],
+        rust_literals: true,
+    }
+});
+
+static JS: Lazy<BraceSyntax> = Lazy::new(|| {
+    let export = r"^\s*(?:export\s+)?(?:default\s+)?(?:declare\s+)?";
+    BraceSyntax {
+        decls: vec![
+            (
+                Regex::new(&format!(r"{export}(?:async\s+)?function\b")).unwrap(),
+                DeclKind::Body,
+            ),
+            (
+                Regex::new(&format!(
+                    r"{export}(?:const|let|var)\s+[\w$]+\s*(?::[^=]+)?=\s*(?:async\s+)?(?:function\b|(?:\([^)]*\)|[\w$]+)\s*(?::[^=]+)?=>)"
+                ))
+                .unwrap(),
+                DeclKind::Body,
+            ),
+            (
+                Regex::new(&format!(r"{export}(?:abstract\s+)?(?:class|namespace)\b")).unwrap(),
+                DeclKind::Container,
+            ),
+            (
+                Regex::new(&format!(r"{export}(?:const\s+)?(?:interface|enum|type)\s")).unwrap(),
+                DeclKind::Whole,
+            ),
+        ],
+        members: vec![(
+            Regex::new(
+                r"^\s*(?:(?:public|private|protected|static|async|readonly|override|abstract|get|set)\s+)*\*?[\w$#]+\s*(?:<[^>]*>)?\s*\(",
+            )
+            .unwrap(),
+            DeclKind::Body,
+        )],
+        quotes: &['"', '\'', '`'],
+        multiline_quotes: &['`'],
+        rust_literals: false,
+    }
+});
+
+/// Reduces `content` to an outline of its declarations, for the languages that
+/// support it.
+///
+/// `language` is a file's language hint as returned by
+/// [`FileInfo::language`](crate::core_types::FileInfo::language). Rust, Python,
+/// and JavaScript/TypeScript are supported; `None` is returned for any other
+/// language.
+///
+/// Functions keep their signature, and their body is replaced with `{ ... }` (or
+/// an indented `...` in Python). Classes, `impl` blocks, traits, and modules keep
+/// their nested declarations. Structs, enums, interfaces, and type aliases are
+/// kept whole. Everything else, including imports and top-level statements, is
+/// dropped.
+///
+/// This is a line-based heuristic, not a parser: braces are counted outside of
+/// string literals (including Rust's raw strings and character literals) and
+/// `//` comments only, and a declaration is recognized only at the start of a
+/// line. If the braces counted that way do not balance, the outline could drop
+/// arbitrary code, so the content is returned unchanged instead.
+///
+/// # Examples
+///
+/// ```
+/// use dircat::processing::outline;
+///
+/// let code = "use std::io;\n\npub fn main() {\n    println!(\"hi\");\n}\n";
+/// assert_eq!(outline(code, "rs").as_deref(), Some("pub fn main() { ... }"));
+///
+/// let code = "class A:\n    def f(self):\n        return 1\n";
+/// assert_eq!(outline(code, "py").as_deref(), Some("class A:\n    def f(self):\n        ..."));
+///
+/// assert_eq!(outline("key: value", "yaml"), None);
+/// ```
+pub fn outline(content: &str, language: &str) -> Option<String> {
+    match language.to_lowercase().as_str() {
+        "rs" | "rust" => Some(outline_braced(content, &RUST)),
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => {
+            Some(outline_braced(content, &JS))
+        }
+        "py" | "pyi" | "python" => Some(outline_python(content)),
+        _ => None,
+    }
+}
+
+/// Outlines a brace-delimited language.
+fn outline_braced(content: &str, syntax: &BraceSyntax) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let code = code_chars(&lines, syntax);
+    if !braces_balance(&code) {
+        return content.to_string();
+    }
+    let mut kept: Vec<String> = Vec::new();
+    // The brace depth before the current line, and the depth inside each open container.
+    let mut depth = 0i32;
+    let mut containers: Vec<i32> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let visible_depth = containers.last().copied().unwrap_or(0);
+        let delta = brace_delta(&code[i]);
+        // Inside a body or block that is not part of the outline.
+        if depth != visible_depth {
+            depth += delta;
+            i += 1;
+            continue;
+        }
+        // The closing brace of the innermost container.
+        if !containers.is_empty() && depth + delta < visible_depth {
+            kept.push(line.to_string());
+            containers.pop();
+            depth += delta;
+            i += 1;
+            continue;
+        }
+        let Some(kind) = syntax
+            .decls
+            .iter()
+            .chain(syntax.members.iter().filter(|_| !containers.is_empty()))
+            .find(|(re, _)| re.is_match(line))
+            .map(|(_, kind)| *kind)
+        else {
+            depth += delta;
+            i += 1;
+            continue;
+        };
+
+        if kind == DeclKind::Whole {
+            let mut opened = false;
+            while i < lines.len() {
+                let line = lines[i];
+                kept.push(line.to_string());
+                opened |= line.contains('{');
+                depth += brace_delta(&code[i]);
+                i += 1;
+                if depth <= visible_depth && (opened || line.trim_end().ends_with(';')) {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        match find_body_start(&lines[i..], &code[i..]) {
+            Some(SignatureEnd::Brace(offset, column)) => {
+                let open_line = lines[i + offset];
+                kept.extend(lines[i..i + offset].iter().map(|l| l.to_string()));
+                if kind == DeclKind::Body {
+                    kept.push(format!(
+                        "{} {} }}",
+                        &open_line[..=column],
+                        OUTLINE_BODY_PLACEHOLDER
+                    ));
+                } else {
+                    kept.push(open_line.to_string());
+                }
+                for line_code in &code[i..=i + offset] {
+                    depth += brace_delta(line_code);
+                }
+                if kind == DeclKind::Container && depth > visible_depth {
+                    containers.push(depth);
+                }
+                i += offset + 1;
+            }
+            Some(SignatureEnd::Semicolon(offset)) => {
+                kept.extend(lines[i..=i + offset].iter().map(|l| l.to_string()));
+                for line_code in &code[i..=i + offset] {
+                    depth += brace_delta(line_code);
+                }
+                i += offset + 1;
+            }
+            None => {
+                depth += delta;
+                i += 1;
+            }
+        }
+    }
+    kept.join("\n")
+}
+
+/// Where a declaration's signature ends.
+enum SignatureEnd {
+    /// At the body's opening brace, as (line offset, byte column).
+    Brace(usize, usize),
+    /// At a `;` on the given line offset, with no body.
+    Semicolon(usize),
+}
+
+/// Finds the first `{` outside parentheses in the signature starting at `lines[0]`,
+/// given the code characters of each line.
+fn find_body_start(lines: &[&str], code: &[Vec<(usize, char)>]) -> Option<SignatureEnd> {
+    let mut parens = 0i32;
+    for (offset, (line, line_code)) in lines.iter().zip(code).take(MAX_SIGNATURE_LINES).enumerate()
+    {
+        for &(column, c) in line_code {
+            match c {
+                '(' | '[' => parens += 1,
+                ')' | ']' => parens -= 1,
+                '{' if parens <= 0 => return Some(SignatureEnd::Brace(offset, column)),
+                _ => {}
+            }
+        }
+        if parens <= 0 && line.trim_end().ends_with(';') {
+            return Some(SignatureEnd::Semicolon(offset));
+        }
+    }
+    None
+}
+
+/// Returns the net change in brace depth over a line's code characters.
+fn brace_delta(line_code: &[(usize, char)]) -> i32 {
+    line_code
+        .iter()
+        .map(|&(_, c)| match c {
+            '{' => 1,
+            '}' => -1,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Returns `true` if the braces close in order and none are left open.
+fn braces_balance(code: &[Vec<(usize, char)>]) -> bool {
+    let mut depth = 0i32;
+    for line_code in code {
+        depth += brace_delta(line_code);
+        if depth < 0 {
+            return false;
+        }
+    }
+    depth == 0
+}
+
+/// A literal left open at the end of a line.
+#[derive(Debug, Clone, Copy)]
+enum OpenLiteral {
+    /// A string delimited by the given quote.
+    Quoted(char),
+    /// A Rust raw string, closed by `"` and the given number of `#`s.
+    Raw(usize),
+}
+
+/// Returns, for each line, its characters outside string literals and `//`
+/// comments, with their byte columns.
+///
+/// Literals in `syntax.multiline_quotes` and raw strings carry over to the
+/// following lines until they are closed.
+fn code_chars(lines: &[&str], syntax: &BraceSyntax) -> Vec<Vec<(usize, char)>> {
+    let mut open: Option<OpenLiteral> = None;
+    lines
+        .iter()
+        .map(|line| {
+            let chars: Vec<(usize, char)> = line.char_indices().collect();
+            let mut code = Vec::new();
+            let mut j = 0;
+            while j < chars.len() {
+                let c = chars[j].1;
+                match open {
+                    Some(OpenLiteral::Quoted(quote)) => {
+                        if c == '\\' {
+                            j += 1;
+                        } else if c == quote {
+                            open = None;
+                        }
+                        j += 1;
+                        continue;
+                    }
+                    Some(OpenLiteral::Raw(hashes)) => {
+                        let closes = chars
+                            .get(j + 1..j + 1 + hashes)
+                            .is_some_and(|rest| rest.iter().all(|&(_, h)| h == '#'));
+                        if c == '"' && closes {
+                            open = None;
+                            j += hashes;
+                        }
+                        j += 1;
+                        continue;
+                    }
+                    None => {}
+                }
+                if c == '/' && chars.get(j + 1).is_some_and(|&(_, next)| next == '/') {
+                    break;
+                }
+                if syntax.rust_literals {
+                    if let Some(len) = char_literal_len(&chars[j..]) {
+                        j += len;
+                        continue;
+                    }
+                    if let Some(hashes) = raw_string_hashes(&chars, j) {
+                        open = Some(OpenLiteral::Raw(hashes));
+                        j += hashes + 2;
+                        continue;
+                    }
+                }
+                if syntax.quotes.contains(&c) {
+                    open = Some(OpenLiteral::Quoted(c));
+                } else {
+                    code.push(chars[j]);
+                }
+                j += 1;
+            }
+            if let Some(OpenLiteral::Quoted(quote)) = open {
+                if !syntax.multiline_quotes.contains(&quote) {
+                    open = None;
+                }
+            }
+            code
+        })
+        .collect()
+}
+
+/// Returns the length in characters of the Rust character literal at the start
+/// of `chars`, like `'{'` or `'\''`, or `None` if it is not one, as with the
+/// lifetime in `&'a str`.
+fn char_literal_len(chars: &[(usize, char)]) -> Option<usize> {
+    match chars {
+        [(_, '\''), (_, '\\'), _, rest @ ..] => rest
+            .iter()
+            .position(|&(_, c)| c == '\'')
+            .map(|position| position + 4),
+        [(_, '\''), _, (_, '\''), ..] => Some(3),
+        _ => None,
+    }
+}
+
+/// Returns the number of `#`s if a Rust raw string, like `r"..."` or
+/// `br#"..."#`, starts with the `r` at `chars[j]`.
+fn raw_string_hashes(chars: &[(usize, char)], j: usize) -> Option<usize> {
+    let is_ident = |i: usize| chars[i].1.is_alphanumeric() || chars[i].1 == '_';
+    if chars[j].1 != 'r' {
+        return None;
+    }
+    let starts_word = match j {
+        0 => true,
+        _ if !is_ident(j - 1) => true,
+        _ => chars[j - 1].1 == 'b' && (j == 1 || !is_ident(j - 2)),
+    };
+    if !starts_word {
+        return None;
+    }
+    let hashes = chars[j + 1..]
+        .iter()
+        .take_while(|&&(_, c)| c == '#')
+        .count();
+    (chars.get(j + 1 + hashes)?.1 == '"').then_some(hashes)
+}
+
+/// Outlines Python, whose blocks are delimited by indentation.
+fn outline_python(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut kept: Vec<String> = Vec::new();
+    // Lines indented deeper than this belong to a block left out of the outline.
+    let mut skip_deeper_than: Option<usize> = None;
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim_start();
+        i += 1;
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+        match skip_deeper_than {
+            Some(level) if indent > level => continue,
+            _ => skip_deeper_than = None,
+        }
+
+        let is_def = trimmed.starts_with("def ") || trimmed.starts_with("async def ");
+        let is_class = trimmed.starts_with("class ");
+        if !is_def && !is_class {
+            // A compound statement's block is not part of the outline either.
+            if trimmed.trim_end().ends_with(':') {
+                skip_deeper_than = Some(indent);
+            }
+            continue;
+        }
+
+        // The signature ends at the line with the block's `:`, outside brackets.
+        let mut signature_end = i - 1;
+        let mut brackets = 0i32;
+        for (offset, sig_line) in lines[i - 1..].iter().take(MAX_SIGNATURE_LINES).enumerate() {
+            brackets += sig_line.matches(['(', '[']).count() as i32;
+            brackets -= sig_line.matches([')', ']']).count() as i32;
+            signature_end = i - 1 + offset;
+            if brackets <= 0 && sig_line.trim_end().ends_with(':') {
+                break;
+            }
+        }
+        kept.extend(lines[i - 1..=signature_end].iter().map(|l| l.to_string()));
+        i = signature_end + 1;
+        if is_def {
+            kept.push(format!(
+                "{}    {}",
+                &line[..indent],
+                OUTLINE_BODY_PLACEHOLDER
+            ));
+            skip_deeper_than = Some(indent);
+        }
+    }
+    kept.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_outline_keeps_items_and_elides_bodies() {
+        let code = r#"use std::fmt;
+
+/// A point.
+#[derive(Debug)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        let s = "}";
+        Self { x, y }
+    }
+
+    fn norm(&self) -> i32 { self.x.abs() + self.y.abs() }
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+fn generic<T>(value: T)
+where
+    T: fmt::Debug,
+{
+    println!("{:?}", value);
+}
+
+static NAMES: &[&str] = &["a", "b"];
+"#;
+        let expected = "pub struct Point {
+    x: i32,
+    y: i32,
+}
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self { ... }
+    fn norm(&self) -> i32 { ... }
+}
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+fn generic<T>(value: T)
+where
+    T: fmt::Debug,
+{ ... }";
+        assert_eq!(outline(code, "rs").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_rust_nested_module_and_unit_struct() {
+        let code = "mod inner {\n    pub struct Unit;\n    fn hidden() {\n        if true {\n            fn nested() {}\n        }\n    }\n}\nmod other;";
+        assert_eq!(
+            outline(code, "rs").unwrap(),
+            "mod inner {\n    pub struct Unit;\n    fn hidden() { ... }\n}\nmod other;"
+        );
+    }
+
+    #[test]
+    fn test_js_outline_classes_and_functions() {
+        let code = r#"import { x } from './x';
+
+export function greet(name = { a: 1 }) {
+  return `Hi ${name}`;
+}
+
+export const add = (a, b) => {
+  return a + b;
+};
+
+const double = (n) => n * 2;
+
+class Counter {
+  count = 0;
+  constructor(start) {
+    this.count = start;
+  }
+  async increment() {
+    if (this.count) {
+      this.count++;
+    }
+  }
+}
+
+export interface Props {
+  title: string;
+}
+
+greet('world');
+"#;
+        let expected = "export function greet(name = { a: 1 }) { ... }
+export const add = (a, b) => { ... }
+const double = (n) => n * 2;
+class Counter {
+  constructor(start) { ... }
+  async increment() { ... }
+}
+export interface Props {
+  title: string;
+}";
+        assert_eq!(outline(code, "ts").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_python_outline() {
+        let code = r#"import os
+
+CONSTANT = 1
+
+@dataclass
+class Config:
+    """Docs."""
+    name: str
+
+    def load(self,
+             path):
+        with open(path) as f:
+            return f.read()
+
+    async def save(self):
+        pass
+
+def main():
+    def helper():
+        pass
+    helper()
+
+if __name__ == "__main__":
+    def not_in_outline():
+        pass
+    main()
+"#;
+        let expected = "class Config:
+    def load(self,
+             path):
+        ...
+    async def save(self):
+        ...
+def main():
+    ...";
+        assert_eq!(outline(code, "py").unwrap(), expected);
+    }
+
+    #[test]
+    fn test_unsupported_language_is_none() {
+        assert_eq!(outline("fn main() {}", "go"), None);
+        assert_eq!(outline("", "md"), None);
+    }
+
+    fn deltas(code: &str, syntax: &BraceSyntax) -> Vec<i32> {
+        let lines: Vec<&str> = code.lines().collect();
+        code_chars(&lines, syntax)
+            .iter()
+            .map(|line_code| brace_delta(line_code))
+            .collect()
+    }
+
+    #[test]
+    fn test_brace_delta_ignores_strings_and_comments() {
+        assert_eq!(deltas(r#"let s = "{"; // {"#, &RUST), [0]);
+        assert_eq!(deltas("fn a<'a>(x: &'a str) {", &RUST), [1]);
+        assert_eq!(deltas("const s = '}' + `{`;", &JS), [0]);
+    }
+
+    #[test]
+    fn test_brace_delta_skips_rust_char_literals_and_raw_strings() {
+        assert_eq!(
+            deltas("match c { '{' => 1, '\\'' => 2, _ => 0 }", &RUST),
+            [0]
+        );
+        assert_eq!(deltas("let (a, b) = ('\\u{7B}', b'}');", &RUST), [0]);
+        assert_eq!(deltas("'outer: loop { let r = r#\"\"}\"#;", &RUST), [1]);
+        assert_eq!(deltas("let s = br\"{\n}}\" ; {", &RUST), [0, 1]);
+        assert_eq!(deltas("let s = \"{\n\";", &RUST), [0, 0]);
+        assert_eq!(deltas("let s = '{\n}';", &JS), [0, -1]);
+    }
+
+    #[test]
+    fn test_rust_outline_with_char_literal_braces() {
+        let code = "fn open(c: char) -> i32 {\n    match c {\n        '{' => 1,\n        _ => 0,\n    }\n}\n\nfn after() {\n    let s = r#\"}\"#;\n}\n";
+        assert_eq!(
+            outline(code, "rs").unwrap(),
+            "fn open(c: char) -> i32 { ... }\nfn after() { ... }"
+        );
+    }
+
+    #[test]
+    fn test_unbalanced_braces_keep_full_content() {
+        let code = "fn broken() {\n    if x {\n}\nfn hidden() {}\n";
+        assert_eq!(outline(code, "rs").unwrap(), code);
+        let code = "}\nfunction f() {}";
+        assert_eq!(outline(code, "js").unwrap(), code);
+    }
+}
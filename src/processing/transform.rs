@@ -0,0 +1,99 @@
+//! Pipes file content through an external command (`--transform-cmd`).
+
+use crate::constants::TRANSFORM_PATH_ENV_VAR;
+use crate::errors::{Error, Result};
+use anyhow::anyhow;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Runs `command` through the system shell with `content` on its stdin and
+/// returns its stdout as the new content.
+///
+/// The path of the file being transformed is passed in the
+/// `TRANSFORM_PATH_ENV_VAR` environment variable.
+///
+/// # Errors
+/// Returns an error if the command cannot be started, exits unsuccessfully,
+/// or writes output that is not valid UTF-8.
+pub(crate) fn run_transform(command: &str, path: &Path, content: &str) -> Result<String> {
+    let mut child = shell_command(command)
+        .env(TRANSFORM_PATH_ENV_VAR, path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start transform command '{}': {}", command, e))?;
+
+    // Feed stdin from a separate thread so a command that writes before it has
+    // read all of its input cannot deadlock on a full stdout pipe.
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = content.to_owned();
+    let feeder = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| anyhow!("Failed to run transform command '{}': {}", command, e))?;
+    // A command may exit without reading its input; a broken pipe is not an error then.
+    if let Ok(Err(e)) = feeder.join() {
+        if e.kind() != std::io::ErrorKind::BrokenPipe {
+            return Err(crate::errors::io_error_with_path(e, path));
+        }
+    }
+
+    if !output.status.success() {
+        return Err(Error::Generic(anyhow!(
+            "Transform command '{}' failed for '{}' ({}): {}",
+            command,
+            path.display(),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    String::from_utf8(output.stdout).map_err(|_| {
+        Error::Generic(anyhow!(
+            "Transform command '{}' produced non-UTF-8 output for '{}'",
+            command,
+            path.display()
+        ))
+    })
+}
+
+/// Builds a `Command` that runs `command` through the platform's shell.
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_transform_replaces_content_with_stdout() {
+        let result = run_transform("tr a-z A-Z", Path::new("a.txt"), "hello\n").unwrap();
+        assert_eq!(result, "HELLO\n");
+    }
+
+    #[test]
+    fn test_run_transform_passes_path_in_env() {
+        let command = format!("printf '%s' \"${}\"", TRANSFORM_PATH_ENV_VAR);
+        let result = run_transform(&command, Path::new("src/lib.rs"), "ignored").unwrap();
+        assert_eq!(result, "src/lib.rs");
+    }
+
+    #[test]
+    fn test_run_transform_reports_failure() {
+        let err = run_transform("echo oops >&2; exit 3", Path::new("a.txt"), "x").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("a.txt"));
+        assert!(message.contains("oops"));
+    }
+}
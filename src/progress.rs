@@ -45,6 +45,11 @@ pub trait ProgressReporter: Send + Sync {
     fn finish(&self);
     /// Finishes the progress reporting with a final message and hides the progress bar.
     fn finish_with_message(&self, msg: String);
+    /// Resets the position and elapsed time so the reporter can be reused for a new
+    /// operation, such as processing files after a clone has finished.
+    ///
+    /// The default implementation does nothing.
+    fn reset(&self) {}
 }
 
 /// A `ProgressReporter` that does nothing.
@@ -90,12 +95,16 @@ pub struct IndicatifProgress {
 #[cfg(feature = "progress")]
 impl IndicatifProgress {
     /// Creates a new progress bar with a default style.
+    ///
+    /// The bar shows an estimated time remaining, derived from the rate at which
+    /// the position has advanced. Call [`ProgressReporter::reset`] between
+    /// operations so the estimate is not skewed by an earlier phase.
     pub fn new() -> Self {
         let pb = ProgressBar::new(0);
         pb.set_style(
             ProgressStyle::default_bar()
                 .template(
-                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%) {msg}",
+                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({percent}%, ETA {eta}) {msg}",
                 )
                 .unwrap()
                 .progress_chars("#>-"),
@@ -132,4 +141,8 @@ impl ProgressReporter for IndicatifProgress {
     fn finish_with_message(&self, msg: String) {
         self.bar.finish_with_message(msg);
     }
+
+    fn reset(&self) {
+        self.bar.reset();
+    }
 }
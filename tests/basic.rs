@@ -46,3 +46,84 @@ fn test_specific_file_input() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_deterministic_output_is_byte_identical() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir_all(temp.path().join("src/nested"))?;
+    for name in ["z.rs", "a.rs", "m.txt", "src/b.rs", "src/nested/c.rs"] {
+        fs::write(temp.path().join(name), format!("content of {}\n", name))?;
+    }
+
+    let run = || -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let output = dircat_cmd()
+            .args(["--deterministic", "-C", "-z", "m.txt"])
+            .current_dir(temp.path())
+            .output()?;
+        assert!(output.status.success());
+        Ok(output.stdout)
+    };
+
+    let first = run()?;
+    let second = run()?;
+    assert!(!first.is_empty());
+    assert_eq!(first, second);
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_print_config_shows_settings_and_exits() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.rs"), "fn a() {}")?;
+
+    dircat_cmd()
+        .args(["-e", "rs", "-c", "--print-config"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("discovery.extensions = rs"))
+        .stdout(predicate::str::contains(
+            "processing.content_filters = RemoveCommentsFilter",
+        ))
+        .stdout(predicate::str::contains("resolved_input = "))
+        .stdout(predicate::str::contains("## File:").not());
+
+    dircat_cmd()
+        .arg("--print-config=json")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("{"))
+        .stdout(predicate::str::contains(
+            r#""output.destination": "stdout""#,
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_capabilities_prints_json_and_exits() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.rs"), "fn a() {}")?;
+
+    dircat_cmd()
+        .arg("--capabilities")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(r#"{"filters": ["#))
+        .stdout(predicate::str::contains(
+            r#"{"name": "MaskLongLiteralsFilter", "flags": "--mask-literals <LEN>"}"#,
+        ))
+        .stdout(predicate::str::contains(
+            r#""output_formats": ["markdown", "json", "xml"]"#,
+        ))
+        .stdout(predicate::str::contains(r#""rust""#))
+        .stdout(predicate::str::contains("## File:").not());
+
+    temp.close()?;
+    Ok(())
+}
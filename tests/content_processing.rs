@@ -89,3 +89,281 @@ fn main() { /* Block */
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_content_match_with_context_lines() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(
+        temp.path().join("a.rs"),
+        "line1\nline2\nline3\nTODO: fix\nline5\nline6\nline7\n",
+    )?;
+    fs::write(temp.path().join("b.rs"), "nothing to see\n")?;
+
+    dircat_cmd()
+        .arg("--content-match")
+        .arg("todo")
+        .arg("--context-lines")
+        .arg("1")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: a.rs"))
+        .stdout(predicate::str::contains(
+            "```rs\n...\nline3\nTODO: fix\nline5\n...\n```",
+        ))
+        .stdout(predicate::str::contains("b.rs").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_context_lines_skips_files_whose_matches_were_filtered_out(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(
+        temp.path().join("comment.rs"),
+        "// TODO: remove
+fn a() {}
+",
+    )?;
+    fs::write(
+        temp.path().join("code.rs"),
+        "fn todo() {}
+",
+    )?;
+
+    dircat_cmd()
+        .arg("--content-match")
+        .arg("(?i)todo")
+        .arg("--context-lines")
+        .arg("0")
+        .arg("-c")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: code.rs"))
+        .stdout(predicate::str::contains("comment.rs").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_context_lines_requires_content_match() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.rs"), "fn main() {}")?;
+
+    dircat_cmd()
+        .arg("--context-lines")
+        .arg("2")
+        .current_dir(temp.path())
+        .assert()
+        .failure();
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_collapse_comment_spaces() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.rs"), "let x = 1 /* one */ + 2;\n")?;
+
+    dircat_cmd()
+        .arg("-c")
+        .arg("--collapse-comment-spaces")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("let x = 1 + 2;"));
+
+    // The llm preset implies -c, so the flag is accepted with it.
+    dircat_cmd()
+        .arg("--preset")
+        .arg("llm")
+        .arg("--collapse-comment-spaces")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("let x = 1 + 2;"));
+
+    // Without -c, the builder's validation still rejects it.
+    dircat_cmd()
+        .arg("--collapse-comment-spaces")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--remove-comments"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_comment_placeholder_replaces_block_comments() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(
+        temp.path().join("a.rs"),
+        "/* License header */\nfn main() {} // entry\n",
+    )?;
+
+    dircat_cmd()
+        .arg("-c")
+        .arg("--comment-placeholder")
+        .arg("/* ... */")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("/* ... */\nfn main() {}\n"))
+        .stdout(predicate::str::contains("License header").not())
+        .stdout(predicate::str::contains("entry").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_stable_normalizes_line_endings() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "one\r\ntwo\rthree\n")?;
+
+    dircat_cmd()
+        .arg("--stable")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: a.txt"))
+        .stdout(predicate::str::contains("one\ntwo\nthree\n"))
+        .stdout(predicate::str::contains("\r").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_stable_conflicts_with_index_headers() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "a")?;
+
+    dircat_cmd()
+        .arg("--stable")
+        .arg("--index-headers")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--stable"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_organize_imports() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(
+        temp.path().join("main.rs"),
+        "use std::io;\nuse std::fmt; // formatting\nuse std::fs;\nuse std::fs;\n",
+    )?;
+
+    // Comment removal runs first, so the commented import is sorted too.
+    dircat_cmd()
+        .arg("--organize-imports")
+        .arg("-c")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "```rs\nuse std::fmt;\nuse std::fs;\nuse std::io;\n```",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_canonicalize_output_newlines() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("mixed.txt"), "a\r\nb\nc\r")?;
+
+    let output = dircat_cmd()
+        .arg("--canonicalize-output-newlines=crlf")
+        .current_dir(temp.path())
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "## File: mixed.txt\r\n```txt\r\na\r\nb\r\nc\r\n```\r\n"
+    );
+
+    let output = dircat_cmd()
+        .arg("--canonicalize-output-newlines")
+        .current_dir(temp.path())
+        .output()?;
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "## File: mixed.txt\n```txt\na\nb\nc\n```\n"
+    );
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_mask_literals() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(
+        temp.path().join("data.json"),
+        "{\"sha\": \"3f786850e387550fdab836ed7e6dc881de23001b\", \"port\": 8080}",
+    )?;
+
+    dircat_cmd()
+        .arg("--mask-literals")
+        .arg("16")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "{\"sha\": \"<hash:40>\", \"port\": 8080}",
+        ));
+
+    dircat_cmd()
+        .arg("--mask-literals")
+        .arg("0")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--mask-literals"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_outline_reduces_supported_languages() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(
+        temp.path().join("lib.rs"),
+        "use std::io;\n\npub fn run() -> io::Result<()> {\n    Ok(())\n}\n",
+    )?;
+    fs::write(
+        temp.path().join("app.py"),
+        "import os\n\ndef main():\n    print(os.name)\n",
+    )?;
+    fs::write(temp.path().join("notes.txt"), "keep me\n")?;
+
+    dircat_cmd()
+        .arg("--outline")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "```rs\npub fn run() -> io::Result<()> { ... }\n```",
+        ))
+        .stdout(predicate::str::contains("```py\ndef main():\n    ...\n```"))
+        .stdout(predicate::str::contains("use std::io;").not())
+        .stdout(predicate::str::contains("keep me"));
+
+    temp.close()?;
+    Ok(())
+}
@@ -37,3 +37,69 @@ var url = "http://insecure.com";"#;
     temp.close()?;
     Ok(())
 }
+
+/// Tests that `--transform-cmd` replaces content with the command's output
+/// before the built-in filters run.
+#[cfg(unix)]
+#[test]
+fn test_transform_cmd_runs_before_filters() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "hello\n\nworld\n")?;
+
+    dircat_cmd()
+        .args(["--transform-cmd", "tr a-z A-Z", "-l"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("```txt\nHELLO\nWORLD\n```"));
+
+    temp.close()?;
+    Ok(())
+}
+
+/// Tests that a failing `--transform-cmd` fails the run and reports the file.
+#[cfg(unix)]
+#[test]
+fn test_transform_cmd_failure_is_reported() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "hello")?;
+
+    dircat_cmd()
+        .args(["--transform-cmd", "echo bad input >&2; exit 1"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("a.txt"))
+        .stderr(predicate::str::contains("bad input"));
+
+    temp.close()?;
+    Ok(())
+}
+
+/// Tests that `--strip-license` drops a leading license header, and that
+/// `--license-marker` leaves a note in its place.
+#[test]
+fn test_strip_license_header() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let file_content = "/*\n * Copyright 2024 Example Corp.\n * Licensed under the MIT License.\n */\n\nfn main() {}\n";
+    fs::write(temp.path().join("main.rs"), file_content)?;
+
+    dircat_cmd()
+        .arg("--strip-license")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("```rs\nfn main() {}\n```"));
+
+    dircat_cmd()
+        .args(["--strip-license", "--license-marker"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "```rs\n// license header removed\nfn main() {}\n```",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
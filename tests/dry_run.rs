@@ -34,3 +34,28 @@ fn test_dry_run() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_dry_run_count() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "one two\nthree\n")?;
+
+    dircat_cmd()
+        .arg("-D")
+        .arg("--dry-run-count")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- a.txt (L:2 C:14 W:3)"))
+        .stdout(predicate::str::contains("one two").not());
+
+    // Without -D, the flag is rejected.
+    dircat_cmd()
+        .arg("--dry-run-count")
+        .current_dir(temp.path())
+        .assert()
+        .failure();
+
+    temp.close()?;
+    Ok(())
+}
@@ -44,6 +44,112 @@ fn test_input_path_is_file_with_filters() -> Result<(), Box<dyn std::error::Erro
     Ok(())
 }
 
+/// The walker never filters its root entry, so `-i` patterns must be checked
+/// against a file input separately, by the name the file is shown under.
+#[test]
+fn test_input_path_is_file_honors_ignore_patterns() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir(temp.path().join("src"))?;
+    fs::write(temp.path().join("src").join("gen.rs"), "// generated")?;
+
+    dircat_cmd()
+        .arg("src/gen.rs")
+        .arg("-i")
+        .arg("gen.*")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File:").not());
+
+    dircat_cmd()
+        .arg("src/gen.rs")
+        .arg("-i")
+        .arg("*.md")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: gen.rs"));
+
+    temp.close()?;
+    Ok(())
+}
+
+/// A symlink given as the input is followed, but shown under its own name.
+#[test]
+#[cfg(unix)]
+fn test_input_path_is_symlink_to_file() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::symlink;
+
+    let temp = tempdir()?;
+    fs::write(temp.path().join("target.txt"), "real content")?;
+    symlink(temp.path().join("target.txt"), temp.path().join("link.txt"))?;
+
+    dircat_cmd()
+        .arg("link.txt")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: link.txt"))
+        .stdout(predicate::str::contains("real content"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_treat_as_list_reads_listed_paths() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir_all(temp.path().join("src/nested"))?;
+    fs::write(temp.path().join("src/main.rs"), "fn main() {}")?;
+    fs::write(temp.path().join("src/nested/util.rs"), "fn util() {}")?;
+    fs::write(temp.path().join("unlisted.rs"), "fn unlisted() {}")?;
+    fs::write(
+        temp.path().join("files.txt"),
+        "# Files to review\nsrc/main.rs\n\nsrc/nested\nmissing.rs\nsrc/main.rs\n",
+    )?;
+
+    let assert = dircat_cmd()
+        .arg("files.txt")
+        .arg("--treat-as-list")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: src/main.rs"))
+        .stdout(predicate::str::contains("## File: src/nested/util.rs"))
+        .stdout(predicate::str::contains("unlisted").not())
+        .stdout(predicate::str::contains("Files to review").not());
+    let stdout = String::from_utf8(assert.get_output().stdout.clone())?;
+    assert_eq!(stdout.matches("## File: src/main.rs").count(), 1);
+
+    // Without the flag, the list file is included as content.
+    dircat_cmd()
+        .arg("files.txt")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: files.txt"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_treat_as_list_requires_file_input() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "A")?;
+
+    dircat_cmd()
+        .arg(".")
+        .arg("--treat-as-list")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--treat-as-list"));
+
+    temp.close()?;
+    Ok(())
+}
+
 #[test]
 fn test_dir_with_only_ignored_files() -> Result<(), Box<dyn std::error::Error>> {
     let temp = tempdir()?;
@@ -67,6 +173,30 @@ fn test_dir_with_only_ignored_files() -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+#[test]
+fn test_report_empty_dirs_lists_filtered_out_dirs() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir_all(temp.path().join("src"))?;
+    fs::create_dir_all(temp.path().join("logs"))?;
+    fs::create_dir_all(temp.path().join("empty"))?;
+    fs::write(temp.path().join("src/main.rs"), "fn main() {}")?;
+    fs::write(temp.path().join("logs/run.log"), "log")?;
+
+    dircat_cmd()
+        .args(["-x", "log", "--report-empty-dirs", "--dry-run"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("src/main.rs"))
+        .stdout(predicate::str::contains("logs").not())
+        .stderr(predicate::str::contains(
+            "--- empty directories ---\nempty/\nlogs/\n",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
 #[test]
 fn test_dir_with_only_binary_files() -> Result<(), Box<dyn std::error::Error>> {
     let temp = tempdir()?;
@@ -235,6 +365,111 @@ fn test_symlinks_are_ignored_by_default() -> Result<(), Box<dyn std::error::Erro
     Ok(())
 }
 
+/// Tests that `--show-symlinks` lists links with their targets in the summary,
+/// without following them or emitting a content block for them.
+#[test]
+#[cfg(unix)]
+fn test_show_symlinks_lists_links_in_summary() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::symlink;
+
+    let temp = tempdir()?;
+    fs::write(temp.path().join("target.txt"), "Real content")?;
+    symlink("target.txt", temp.path().join("link.txt"))?;
+
+    dircat_cmd()
+        .arg("--show-symlinks")
+        .arg("-s")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: target.txt"))
+        .stdout(predicate::str::contains("## File: link.txt").not())
+        .stdout(predicate::str::contains("Processed Files: (2)"))
+        .stdout(predicate::str::contains(
+            "- link.txt -> target.txt (symlink)\n",
+        ));
+
+    dircat_cmd()
+        .arg("--show-symlinks")
+        .arg("-D")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "- link.txt -> target.txt (symlink)\n",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
+/// Tests that `--follow-internal-symlinks` follows links to files and directories
+/// inside the input root, and skips links that escape it.
+#[test]
+#[cfg(unix)]
+fn test_follow_internal_symlinks_stays_inside_root() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::symlink;
+
+    let temp = tempdir()?;
+    let root = temp.path().join("root");
+    fs::create_dir_all(root.join("src"))?;
+    fs::write(root.join("src/a.txt"), "Inside")?;
+    fs::write(temp.path().join("outside.txt"), "Outside")?;
+    symlink("src", root.join("shared"))?;
+    symlink("src/a.txt", root.join("link.txt"))?;
+    symlink("../outside.txt", root.join("escape.txt"))?;
+
+    dircat_cmd()
+        .arg("--follow-internal-symlinks")
+        .current_dir(&root)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: src/a.txt"))
+        .stdout(predicate::str::contains("## File: shared/a.txt"))
+        .stdout(predicate::str::contains("## File: link.txt"))
+        .stdout(predicate::str::contains("escape.txt").not())
+        .stdout(predicate::str::contains("Outside").not());
+
+    // Without the flag, links are not followed.
+    dircat_cmd()
+        .current_dir(&root)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: src/a.txt"))
+        .stdout(predicate::str::contains("shared/a.txt").not())
+        .stdout(predicate::str::contains("link.txt").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+/// Tests that files with non-UTF-8 names are processed and shown with a lossy path
+/// instead of crashing or being silently dropped by the filters.
+#[test]
+#[cfg(target_os = "linux")] // Other Unix filesystems (e.g., APFS) reject non-UTF-8 names
+fn test_non_utf8_filename_is_handled_lossily() -> Result<(), Box<dyn std::error::Error>> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let temp = tempdir()?;
+    let name = OsStr::from_bytes(b"bad\xffname.txt");
+    fs::write(temp.path().join(name), "non-utf8 content")?;
+
+    dircat_cmd()
+        .arg("-e")
+        .arg("txt")
+        .arg("--summary")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: bad\u{FFFD}name.txt"))
+        .stdout(predicate::str::contains("non-utf8 content"))
+        .stdout(predicate::str::contains("- bad\u{FFFD}name.txt"));
+
+    temp.close()?;
+    Ok(())
+}
+
 #[test]
 fn test_github_directory_does_not_trigger_git_traversal() -> Result<(), Box<dyn std::error::Error>>
 {
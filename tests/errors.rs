@@ -124,3 +124,80 @@ fn test_include_invalid_utf8_lossy() -> Result<(), Box<dyn std::error::Error>> {
 
 // Note: Testing clipboard errors (-p without feature or clipboard unavailable)
 // is harder in CI and depends on the feature flag.
+
+#[test]
+fn test_no_files_found_exit_control() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::File::create(temp.path().join("binary.bin"))?.write_all(b"Bin\0Data")?;
+
+    dircat_cmd()
+        .arg("--fail-if-empty")
+        .current_dir(temp.path())
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "dircat: No files found matching the specified criteria.",
+        ));
+
+    dircat_cmd()
+        .arg("--allow-empty")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("");
+
+    // The output file is still written, so it does not keep a previous run's content.
+    let output_path = temp.path().join("out.md");
+    fs::write(&output_path, "stale output")?;
+    dircat_cmd()
+        .arg("--allow-empty")
+        .arg("-o")
+        .arg(&output_path)
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stderr("");
+    assert_eq!(fs::read(&output_path)?, b"");
+
+    dircat_cmd()
+        .arg("--allow-empty")
+        .arg("--fail-if-empty")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_error_format_json() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+
+    dircat_cmd()
+        .arg("--error-format")
+        .arg("json")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            r#""error_type": "no_files_found""#,
+        ));
+
+    dircat_cmd()
+        .arg("--error-format")
+        .arg("json")
+        .arg("--top")
+        .arg("0")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::starts_with(r#"{"error_type": "config", "#))
+        .stderr(predicate::str::contains(r#""option": "--top""#));
+
+    temp.close()?;
+    Ok(())
+}
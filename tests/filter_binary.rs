@@ -93,3 +93,102 @@ fn test_include_image_flag() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_only_binary_flag_lists_binaries() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let binary_content = b"This has a null \0 byte.";
+    fs::write(temp.path().join("text.txt"), "This is text.")?;
+    fs::File::create(temp.path().join("binary.bin"))?.write_all(binary_content)?;
+
+    let expected_output = format!("- binary.bin ({} bytes)\n", binary_content.len());
+
+    dircat_cmd()
+        .arg("--only-binary")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::eq(expected_output));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_only_binary_dry_run() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("text.txt"), "This is text.")?;
+    fs::File::create(temp.path().join("binary.bin"))?.write_all(b"null \0 byte")?;
+
+    dircat_cmd()
+        .arg("--only-binary")
+        .arg("-D")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("- binary.bin"))
+        .stdout(predicate::str::contains("text.txt").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_trust_extensions_classifies_by_name() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    // Text content behind a binary extension, and binary content behind a text one.
+    fs::write(temp.path().join("fake.png"), "not really an image")?;
+    fs::write(temp.path().join("data.txt"), b"null\0byte")?;
+
+    for dry_run in [false, true] {
+        let mut cmd = dircat_cmd();
+        cmd.arg("--trust-extensions").current_dir(temp.path());
+        if dry_run {
+            cmd.arg("-D");
+        }
+        cmd.assert()
+            .success()
+            .stdout(predicate::str::contains("data.txt"))
+            .stdout(predicate::str::contains("fake.png").not());
+    }
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_binary_encoding_base64_unpacks_exactly() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let src = temp.path().join("src");
+    fs::create_dir(&src)?;
+    let bytes: Vec<u8> = (0..=255u8).cycle().take(300).collect();
+    fs::write(src.join("blob.bin"), &bytes)?;
+    let bundle = temp.path().join("bundle.md");
+
+    dircat_cmd()
+        .arg("src")
+        .arg("-B")
+        .arg("--binary-encoding")
+        .arg("base64")
+        .arg("-o")
+        .arg(&bundle)
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    let markdown = fs::read_to_string(&bundle)?;
+    assert!(markdown.starts_with("## File: blob.bin (base64, 300 bytes)\n```base64\nAAECAwQF"));
+    // Lines are wrapped at 76 characters.
+    assert!(markdown.lines().all(|line| line.len() <= 76));
+
+    let restored = temp.path().join("restored");
+    dircat_cmd()
+        .arg("--unpack")
+        .arg(&bundle)
+        .arg(&restored)
+        .assert()
+        .success();
+    assert_eq!(fs::read(restored.join("blob.bin"))?, bytes);
+
+    temp.close()?;
+    Ok(())
+}
@@ -32,6 +32,42 @@ fn test_extension_filter_include() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_lang_filter_expands_to_extensions() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("main.rs"), "Rust")?;
+    fs::write(temp.path().join("app.py"), "Python")?;
+    fs::write(temp.path().join("stubs.pyi"), "Stub")?;
+    fs::write(temp.path().join("notes.txt"), "Text")?;
+    fs::write(temp.path().join("index.js"), "Script")?;
+
+    dircat_cmd()
+        .arg("--lang")
+        .arg("rust")
+        .arg("Python")
+        .arg("-e")
+        .arg("txt") // Combined with --ext
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: main.rs"))
+        .stdout(predicate::str::contains("## File: app.py"))
+        .stdout(predicate::str::contains("## File: stubs.pyi"))
+        .stdout(predicate::str::contains("## File: notes.txt"))
+        .stdout(predicate::str::contains("## File: index.js").not());
+
+    dircat_cmd()
+        .arg("--lang")
+        .arg("klingon")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown language 'klingon'"));
+
+    temp.close()?;
+    Ok(())
+}
+
 #[test]
 fn test_extension_filter_exclude() -> Result<(), Box<dyn std::error::Error>> {
     let temp = tempdir()?;
@@ -55,3 +91,58 @@ fn test_extension_filter_exclude() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_extensions_from_environment() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.rs"), "fn a() {}")?;
+    fs::write(temp.path().join("b.txt"), "text")?;
+
+    dircat_cmd()
+        .env("DIRCAT_EXTENSIONS", "rs")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: a.rs"))
+        .stdout(predicate::str::contains("## File: b.txt").not());
+
+    // A flag on the command line wins over the variable.
+    dircat_cmd()
+        .env("DIRCAT_EXTENSIONS", "rs")
+        .arg("-e")
+        .arg("txt")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: b.txt"))
+        .stdout(predicate::str::contains("## File: a.rs").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_extensionless_flags() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("deploy"), "#!/bin/sh\necho deploy\n")?;
+    fs::write(temp.path().join("main.rs"), "fn main() {}\n")?;
+
+    dircat_cmd()
+        .arg("--no-extensionless")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: main.rs"))
+        .stdout(predicate::str::contains("## File: deploy").not());
+
+    dircat_cmd()
+        .arg("--only-extensionless")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: deploy"))
+        .stdout(predicate::str::contains("## File: main.rs").not());
+
+    temp.close()?;
+    Ok(())
+}
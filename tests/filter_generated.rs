@@ -0,0 +1,67 @@
+// tests/filter_generated.rs
+
+mod common;
+
+use assert_cmd::prelude::*;
+use common::dircat_cmd;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_generated_kept_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(
+        temp.path().join("api.pb.go"),
+        "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n",
+    )?;
+    fs::write(temp.path().join("main.go"), "package main\n")?;
+
+    dircat_cmd()
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: api.pb.go"))
+        .stdout(predicate::str::contains("## File: main.go"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_skip_generated_flag() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(
+        temp.path().join("api.pb.go"),
+        "// Code generated by protoc-gen-go. DO NOT EDIT.\npackage api\n",
+    )?;
+    fs::write(
+        temp.path().join("schema.rs"),
+        "// @generated by build.rs\npub struct Row;\n",
+    )?;
+    // A marker past the first lines does not make a file generated.
+    fs::write(
+        temp.path().join("main.go"),
+        "package main\n\nimport \"fmt\"\n\nfunc main() {}\n\n// Files under pb/ are @generated.\n",
+    )?;
+
+    dircat_cmd()
+        .arg("--skip-generated")
+        .env("RUST_LOG", "info")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: api.pb.go").not())
+        .stdout(predicate::str::contains("## File: schema.rs").not())
+        .stdout(predicate::str::contains("## File: main.go"))
+        .stderr(predicate::str::contains(
+            "Skipping generated file (marker 'Code generated by'): api.pb.go",
+        ))
+        .stderr(predicate::str::contains(
+            "Skipping generated file (marker '@generated'): schema.rs",
+        ))
+        .stderr(predicate::str::contains("main.go").not());
+
+    temp.close()?;
+    Ok(())
+}
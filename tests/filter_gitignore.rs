@@ -82,6 +82,7 @@ fn test_no_gitignore_flag() -> Result<(), Box<dyn std::error::Error>> {
 
     dircat_cmd()
         .arg("-t") // Ignore .gitignore AND hidden files
+        .arg("--no-default-excludes") // target/ is also a built-in exclude
         .current_dir(temp.path())
         .assert()
         .success()
@@ -142,3 +143,70 @@ fn test_no_gitignore_includes_hidden_files() -> Result<(), Box<dyn std::error::E
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_include_git_dir_flag() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let git_dir = temp.path().join(".git");
+    fs::create_dir_all(&git_dir)?;
+    fs::write(git_dir.join("config"), "[core]\n\tbare = false\n")?;
+    fs::write(temp.path().join("main.rs"), "Main")?;
+
+    dircat_cmd()
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: .git/config").not());
+
+    dircat_cmd()
+        .arg("--include-git-dir")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: .git/config"))
+        .stdout(predicate::str::contains("## File: main.rs"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_explicit_input_file_in_ignored_dir_is_processed() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp = tempdir()?;
+    fs::write(temp.path().join(".gitignore"), "build/\n")?;
+    fs::create_dir(temp.path().join("build"))?;
+    fs::write(temp.path().join("build").join("gen.rs"), "generated")?;
+
+    // Naming the file includes it by default, despite the ignored directory.
+    dircat_cmd()
+        .arg("build/gen.rs")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "## File: gen.rs\n```rs\ngenerated\n```",
+        ));
+
+    // With --ignore-explicit-input, the .gitignore rule applies to it too.
+    dircat_cmd()
+        .arg("build/gen.rs")
+        .arg("--ignore-explicit-input")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("generated").not());
+
+    // --no-ignore-explicit-input restores the default.
+    dircat_cmd()
+        .arg("build/gen.rs")
+        .arg("--ignore-explicit-input")
+        .arg("--no-ignore-explicit-input")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("generated"));
+
+    temp.close()?;
+    Ok(())
+}
@@ -289,3 +289,68 @@ fn test_last_flag_does_not_globally_disable_gitignore() -> Result<(), Box<dyn st
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_parent_gitignore_applies_regardless_of_cwd() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let inner = temp.path().join("outer").join("inner");
+    fs::create_dir_all(&inner)?;
+    fs::write(temp.path().join("outer").join(".gitignore"), "*.log\n")?;
+    fs::write(inner.join(".gitignore"), "secret.txt\n")?;
+    fs::write(inner.join("app.log"), "Log")?;
+    fs::write(inner.join("secret.txt"), "Secret")?;
+    fs::write(inner.join("main.rs"), "fn main() {}")?;
+
+    // The same files are selected whether the input is given relative to the
+    // temp dir, as an absolute path, or as "." from inside the directory.
+    for (cwd, input) in [
+        (temp.path().to_path_buf(), "outer/inner".to_string()),
+        (
+            temp.path().to_path_buf(),
+            inner.to_string_lossy().to_string(),
+        ),
+        (inner.clone(), ".".to_string()),
+    ] {
+        dircat_cmd()
+            .arg(&input)
+            .current_dir(&cwd)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("## File: main.rs"))
+            .stdout(predicate::str::contains("## File: app.log").not())
+            .stdout(predicate::str::contains("## File: secret.txt").not());
+    }
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_no_ignore_parent_skips_only_parent_rules() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let inner = temp.path().join("outer").join("inner");
+    fs::create_dir_all(&inner)?;
+    fs::write(temp.path().join("outer").join(".gitignore"), "*.log\n")?;
+    fs::write(inner.join(".gitignore"), "secret.txt\n")?;
+    fs::write(inner.join("app.log"), "Log")?;
+    fs::write(inner.join("secret.txt"), "Secret")?;
+    fs::write(inner.join("main.rs"), "fn main() {}")?;
+
+    for (cwd, input) in [
+        (temp.path().to_path_buf(), "outer/inner".to_string()),
+        (inner.clone(), ".".to_string()),
+    ] {
+        dircat_cmd()
+            .arg(&input)
+            .arg("--no-ignore-parent")
+            .current_dir(&cwd)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("## File: main.rs"))
+            .stdout(predicate::str::contains("## File: app.log")) // Parent rule skipped
+            .stdout(predicate::str::contains("## File: secret.txt").not()); // Own rule kept
+    }
+
+    temp.close()?;
+    Ok(())
+}
@@ -53,6 +53,100 @@ fn test_ignore_glob_pattern() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_ignore_glob_expands_braces() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir_all(temp.path().join("src"))?;
+    fs::write(temp.path().join("src/main.rs"), "Main")?;
+    fs::write(temp.path().join("src/notes.md"), "Notes")?;
+    fs::write(temp.path().join("Cargo.toml"), "Manifest")?;
+
+    dircat_cmd()
+        .arg("-i")
+        .arg("src/*.{rs,md}")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: Cargo.toml"))
+        .stdout(predicate::str::contains("## File: src/main.rs").not())
+        .stdout(predicate::str::contains("## File: src/notes.md").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_negated_ignore_reincludes_gitignored_file() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join(".gitignore"), "*.log\n")?;
+    fs::write(temp.path().join("debug.log"), "Debug log")?;
+    fs::write(temp.path().join("important.log"), "Important log")?;
+    fs::write(temp.path().join("main.rs"), "Main")?;
+
+    dircat_cmd()
+        .arg("-i")
+        .arg("!important.log")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: main.rs"))
+        .stdout(predicate::str::contains("## File: important.log"))
+        .stdout(predicate::str::contains("debug.log").not());
+
+    // The last matching --ignore pattern wins.
+    dircat_cmd()
+        .arg("-i")
+        .arg("*.rs")
+        .arg("-i")
+        .arg("!main.rs")
+        .arg("-t")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: main.rs"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_exclude_from_file_merges_with_ignore() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let src_dir = temp.path().join("src");
+    fs::create_dir_all(&src_dir)?;
+    fs::write(src_dir.join("main.rs"), "Main")?;
+    fs::write(temp.path().join("app.log"), "Log")?;
+    fs::write(temp.path().join("notes.tmp"), "Temp")?;
+    fs::write(temp.path().join("keep.md"), "Keep")?;
+    let list = tempdir()?;
+    let list_path = list.path().join("excludes");
+    fs::write(&list_path, "# Shared excludes\n\n*.log\n  src/*  \n")?;
+
+    dircat_cmd()
+        .arg("--exclude-from")
+        .arg(list_path.to_str().unwrap())
+        .arg("-i")
+        .arg("*.tmp")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: keep.md"))
+        .stdout(predicate::str::contains("## File: app.log").not())
+        .stdout(predicate::str::contains("## File: notes.tmp").not())
+        .stdout(predicate::str::contains("## File: src/main.rs").not());
+
+    dircat_cmd()
+        .arg("--exclude-from")
+        .arg("missing-excludes.txt")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing-excludes.txt"));
+
+    temp.close()?;
+    Ok(())
+}
+
 #[test]
 fn test_ignore_glob_directory() -> Result<(), Box<dyn std::error::Error>> {
     let temp = tempdir()?;
@@ -151,3 +245,115 @@ fn test_ignore_only_invalid_glob_patterns() -> Result<(), Box<dyn std::error::Er
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_dircatignore_is_honored_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join(".dircatignore"), "*.snap\n")?;
+    fs::write(temp.path().join("main.rs"), "Main")?;
+    fs::write(temp.path().join("out.snap"), "Snapshot")?;
+
+    dircat_cmd()
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: main.rs"))
+        .stdout(predicate::str::contains("## File: out.snap").not());
+
+    // -t disables all ignore files, including .dircatignore
+    dircat_cmd()
+        .arg("-t")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: out.snap"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_ignore_file_flag_honors_custom_names() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let sub = temp.path().join("sub");
+    fs::create_dir(&sub)?;
+    fs::write(temp.path().join(".aiignore"), "*.csv\n")?;
+    fs::write(sub.join(".cursorignore"), "notes.md\n")?;
+    fs::write(temp.path().join("main.rs"), "Main")?;
+    fs::write(temp.path().join("data.csv"), "a,b")?;
+    fs::write(sub.join("notes.md"), "Notes")?;
+
+    // Without the flag, the tool-specific files are ordinary files.
+    dircat_cmd()
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: data.csv"))
+        .stdout(predicate::str::contains("## File: sub/notes.md"));
+
+    dircat_cmd()
+        .args([
+            "--ignore-file",
+            ".aiignore",
+            "--ignore-file",
+            ".cursorignore",
+        ])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: main.rs"))
+        .stdout(predicate::str::contains("## File: data.csv").not())
+        .stdout(predicate::str::contains("## File: sub/notes.md").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_default_excludes_apply_without_gitignore() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir_all(temp.path().join("node_modules/pkg"))?;
+    fs::create_dir_all(temp.path().join("target/debug"))?;
+    fs::write(temp.path().join("main.rs"), "Main")?;
+    fs::write(temp.path().join("node_modules/pkg/index.js"), "Vendored")?;
+    fs::write(temp.path().join("target/debug/out.txt"), "Build output")?;
+
+    dircat_cmd()
+        .arg("-t") // No .gitignore handling, default excludes still apply
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: main.rs"))
+        .stdout(predicate::str::contains("Vendored").not())
+        .stdout(predicate::str::contains("Build output").not());
+
+    dircat_cmd()
+        .args(["-t", "--no-default-excludes"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "## File: node_modules/pkg/index.js",
+        ))
+        .stdout(predicate::str::contains("## File: target/debug/out.txt"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_default_excludes_do_not_skip_input_path() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let target = temp.path().join("target");
+    fs::create_dir_all(&target)?;
+    fs::write(target.join("report.txt"), "Report")?;
+
+    dircat_cmd()
+        .arg(target.to_str().unwrap())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: report.txt"));
+
+    temp.close()?;
+    Ok(())
+}
@@ -53,3 +53,32 @@ fn test_no_lockfiles_flag() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_skip_secrets() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join(".env"), "API_KEY=secret")?;
+    fs::write(temp.path().join(".env.example"), "API_KEY=")?;
+    fs::write(temp.path().join("server.pem"), "-----BEGIN KEY-----")?;
+    fs::write(temp.path().join("main.rs"), "fn main() {}")?;
+
+    // Kept by default
+    dircat_cmd()
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("API_KEY=secret"));
+
+    dircat_cmd()
+        .arg("--skip-secrets")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: main.rs"))
+        .stdout(predicate::str::contains("## File: .env.example"))
+        .stdout(predicate::str::contains("API_KEY=secret").not())
+        .stdout(predicate::str::contains("server.pem").not());
+
+    temp.close()?;
+    Ok(())
+}
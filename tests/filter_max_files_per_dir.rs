@@ -0,0 +1,74 @@
+// tests/filter_max_files_per_dir.rs
+
+mod common;
+
+use assert_cmd::prelude::*;
+use common::dircat_cmd;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_max_files_per_dir_keeps_first_files_by_path() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let generated = temp.path().join("generated");
+    fs::create_dir(&generated)?;
+    for name in ["d.txt", "a.txt", "c.txt", "b.txt"] {
+        fs::write(generated.join(name), name)?;
+    }
+    fs::write(temp.path().join("main.rs"), "fn main() {}")?;
+    fs::write(temp.path().join("lib.rs"), "pub fn lib() {}")?;
+
+    dircat_cmd()
+        .args(["--max-files-per-dir", "2"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        // Root directory: both files fit.
+        .stdout(predicate::str::contains("## File: lib.rs"))
+        .stdout(predicate::str::contains("## File: main.rs"))
+        // Noisy directory: only the first two by path are kept.
+        .stdout(predicate::str::contains("## File: generated/a.txt"))
+        .stdout(predicate::str::contains("## File: generated/b.txt"))
+        .stdout(predicate::str::contains("## File: generated/c.txt").not())
+        .stdout(predicate::str::contains("## File: generated/d.txt").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_max_files_per_dir_exempts_last_files() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    for name in ["a.txt", "b.txt", "README.md"] {
+        fs::write(temp.path().join(name), name)?;
+    }
+
+    dircat_cmd()
+        .args(["--max-files-per-dir", "1", "--last", "README.md"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: a.txt"))
+        .stdout(predicate::str::contains("## File: b.txt").not())
+        .stdout(predicate::str::contains("## File: README.md"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_max_files_per_dir_rejects_zero() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "A")?;
+
+    dircat_cmd()
+        .args(["--max-files-per-dir", "0"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must be greater than 0"));
+
+    temp.close()?;
+    Ok(())
+}
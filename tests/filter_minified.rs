@@ -0,0 +1,45 @@
+// tests/filter_minified.rs
+
+mod common;
+
+use assert_cmd::prelude::*;
+use common::dircat_cmd;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_minified_kept_by_default() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("bundle.js"), "var a=1;".repeat(200))?;
+    fs::write(temp.path().join("app.js"), "const a = 1;\n")?;
+
+    dircat_cmd()
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: bundle.js"))
+        .stdout(predicate::str::contains("## File: app.js"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_skip_minified_flag() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    // No ".min" in the name; detection is based on content alone.
+    fs::write(temp.path().join("bundle.js"), "var a=1;".repeat(200))?;
+    fs::write(temp.path().join("app.js"), "const a = 1;\n")?;
+
+    dircat_cmd()
+        .arg("--skip-minified")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: bundle.js").not())
+        .stdout(predicate::str::contains("## File: app.js"));
+
+    temp.close()?;
+    Ok(())
+}
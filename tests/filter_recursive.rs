@@ -29,3 +29,25 @@ fn test_no_recursive() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_max_depth_limits_descent() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir_all(temp.path().join("sub/deep"))?;
+    fs::write(temp.path().join("a.txt"), "Top Level")?;
+    fs::write(temp.path().join("sub/b.txt"), "Sub Level")?;
+    fs::write(temp.path().join("sub/deep/c.txt"), "Deep Level")?;
+
+    dircat_cmd()
+        .arg("--max-depth")
+        .arg("2")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: a.txt"))
+        .stdout(predicate::str::contains("## File: sub/b.txt"))
+        .stdout(predicate::str::contains("Deep Level").not());
+
+    temp.close()?;
+    Ok(())
+}
@@ -194,3 +194,26 @@ fn test_path_regex_windows_separators() -> Result<(), Box<dyn std::error::Error>
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_regex_size_limit_rejects_oversized_pattern() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "A")?;
+
+    dircat_cmd()
+        .args(["-r", "a{1000}", "--regex-size-limit", "10k"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid path regex"));
+
+    // The same pattern compiles under the default limit.
+    dircat_cmd()
+        .args(["-r", "a{1000}"])
+        .current_dir(temp.path())
+        .assert()
+        .stderr(predicate::str::contains("Invalid path regex").not());
+
+    temp.close()?;
+    Ok(())
+}
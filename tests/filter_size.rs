@@ -95,3 +95,22 @@ fn test_max_size_megabytes_binary() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_max_lines_skips_long_files() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("schema.json"), "{}\n".repeat(50))?;
+    fs::write(temp.path().join("main.rs"), "fn main() {}\n")?;
+
+    dircat_cmd()
+        .arg("--max-lines")
+        .arg("10")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: main.rs"))
+        .stdout(predicate::str::contains("schema.json").not());
+
+    temp.close()?;
+    Ok(())
+}
@@ -94,3 +94,24 @@ fn test_backticks_with_filename_spaces() -> Result<(), Box<dyn std::error::Error
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_markdown_safe_widens_fence_for_nested_fences() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(
+        temp.path().join("guide.md"),
+        "Example:\n```sh\necho hi\n```\nTrailing ````",
+    )?;
+
+    dircat_cmd()
+        .arg("--markdown-safe")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: guide.md\n`````md\n"))
+        .stdout(predicate::str::contains("\n\u{200B}```sh\n"))
+        .stdout(predicate::str::contains("Trailing ````\n`````\n"));
+
+    temp.close()?;
+    Ok(())
+}
@@ -28,3 +28,65 @@ fn test_filename_only_header() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_toc_links_to_file_headers() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir(temp.path().join("src"))?;
+    fs::write(temp.path().join("src/main.rs"), "fn main() {}")?;
+    fs::write(temp.path().join("README.md"), "# Readme")?;
+
+    dircat_cmd()
+        .arg("--toc")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "## Table of Contents\n\n- [README.md](#file-readmemd)\n- [src/main.rs](#file-srcmainrs)\n\n## File: README.md\n",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_index_headers_follow_final_order() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.rs"), "A")?;
+    fs::write(temp.path().join("b.rs"), "B")?;
+    fs::write(temp.path().join("c.rs"), "C")?;
+
+    dircat_cmd()
+        .arg("--index-headers")
+        .arg("-z")
+        .arg("a.rs")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File 1: b.rs\n"))
+        .stdout(predicate::str::contains("## File 2: c.rs\n"))
+        .stdout(predicate::str::contains("## File 3: a.rs\n"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_anchor_comments_precede_file_blocks() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir(temp.path().join("src"))?;
+    fs::write(temp.path().join("src").join("lib.rs"), "pub fn a() {}")?;
+
+    dircat_cmd()
+        .arg("--anchor-comments")
+        .arg("--collapsible")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "<!-- dircat:file path=\"src/lib.rs\" size=\"13\" -->\n<details>\n",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
@@ -80,3 +80,26 @@ fn test_custom_ticks_alias() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_ticks_policy_per_lang_widens_markdown_only() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("README.md"), "Usage:\n```sh\nrun\n```")?;
+    fs::write(temp.path().join("gen.py"), "DOC = \"```\"")?;
+
+    dircat_cmd()
+        .arg("--ticks-policy")
+        .arg("per-lang")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "## File: README.md\n````md\nUsage:\n```sh\nrun\n```\n````\n",
+        ))
+        .stdout(predicate::str::contains(
+            "## File: gen.py\n```py\nDOC = \"```\"\n```\n",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
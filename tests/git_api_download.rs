@@ -52,6 +52,28 @@ fn test_api_download_public_repo() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Tests that `--max-depth` stops the API listing below the requested directory.
+#[test]
+#[ignore = "requires network access and is slow"]
+fn test_api_download_respects_max_depth() -> Result<(), Box<dyn std::error::Error>> {
+    let repo_url = "https://github.com/git-fixtures/basic";
+
+    let temp_cache = tempdir()?;
+
+    dircat_cmd()
+        .arg(repo_url)
+        .arg("--git-download")
+        .arg("--max-depth")
+        .arg("1")
+        .env("DIRCAT_TEST_CACHE_DIR", temp_cache.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: CHANGELOG"))
+        .stdout(predicate::str::contains("## File: go/example.go").not());
+
+    Ok(())
+}
+
 /// Tests downloading a single file from a public remote repository via a blob URL.
 /// This is a slow, network-dependent test.
 #[test]
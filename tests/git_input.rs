@@ -238,3 +238,52 @@ fn test_show_download_path() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_relative_base_git_root_rebases_paths() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = tempdir()?;
+    git2::Repository::init(temp_dir.path())?;
+    fs::create_dir_all(temp_dir.path().join("crates/core/src"))?;
+    fs::write(
+        temp_dir.path().join("crates/core/src/lib.rs"),
+        "pub fn f() {}",
+    )?;
+
+    dircat_cmd()
+        .arg("crates/core")
+        .arg("--relative-base")
+        .arg("git-root")
+        .current_dir(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: crates/core/src/lib.rs"));
+
+    // The default stays relative to the input path.
+    dircat_cmd()
+        .arg("crates/core")
+        .current_dir(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: src/lib.rs"));
+
+    Ok(())
+}
+
+#[test]
+fn test_relative_base_git_root_outside_repo_keeps_paths() -> Result<(), Box<dyn std::error::Error>>
+{
+    let temp_dir = tempdir()?;
+    fs::create_dir_all(temp_dir.path().join("sub"))?;
+    fs::write(temp_dir.path().join("sub/a.txt"), "A")?;
+
+    dircat_cmd()
+        .arg("sub")
+        .arg("--relative-base")
+        .arg("git-root")
+        .current_dir(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: a.txt"));
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ mod common;
 use dircat::config::{self, ConfigBuilder, ResolvedInput};
 use dircat::core_types::FileInfo;
 use dircat::errors::Error;
-use dircat::{discover, process_files, CancellationToken, Config};
+use dircat::{discover, execute, process_files, CancellationToken, Config};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tempfile::{tempdir, TempDir};
@@ -123,6 +123,129 @@ fn test_discover_with_no_matching_files() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_discover_with_directories() -> anyhow::Result<()> {
+    let harness = TestHarness::new();
+    harness.file("src/nested/a.rs", b"A");
+    harness.file("b.txt", b"B");
+
+    let (config, resolved) = build_and_resolve(harness.builder().include_dirs(true));
+
+    let discovered: Vec<FileInfo> =
+        discover(&config.discovery, &resolved, &harness.token)?.collect();
+    let dirs: Vec<String> = discovered
+        .iter()
+        .filter(|fi| fi.is_dir)
+        .map(|fi| fi.relative_path.to_string_lossy().replace('\\', "/"))
+        .collect();
+    assert_eq!(dirs, vec!["src", "src/nested"]); // The root itself is not yielded
+    assert_eq!(discovered.iter().filter(|fi| !fi.is_dir).count(), 2);
+
+    // Directories pass through processing without content.
+    let processed: Vec<FileInfo> =
+        process_files(discovered.into_iter(), &config.processing, &harness.token)
+            .collect::<dircat::errors::Result<_>>()?;
+    assert_eq!(processed.len(), 4);
+    assert!(processed
+        .iter()
+        .filter(|fi| fi.is_dir)
+        .all(|fi| fi.processed_content.is_none()));
+
+    Ok(())
+}
+
+#[test]
+fn test_execute_reports_and_excludes_empty_dirs() -> anyhow::Result<()> {
+    let harness = TestHarness::new();
+    harness.file("src/a.rs", b"A");
+    harness.file("logs/out.log", b"L");
+    fs::create_dir_all(harness.root.join("empty/inner"))?;
+
+    let rel = |fi: &FileInfo| fi.relative_path.to_string_lossy().replace('\\', "/");
+    let config = harness
+        .builder()
+        .exclude_extensions(vec!["log".to_string()])
+        .include_dirs(true)
+        .exclude_empty_dirs(true)
+        .report_empty_dirs(true)
+        .build()?;
+    let result = execute(&config, &harness.token, None)?;
+    let dirs: Vec<String> = result
+        .files
+        .iter()
+        .filter(|fi| fi.is_dir)
+        .map(rel)
+        .collect();
+    assert_eq!(dirs, vec!["src"]);
+    let empty: Vec<String> = result
+        .empty_dirs
+        .iter()
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .collect();
+    assert_eq!(empty, vec!["empty", "empty/inner", "logs"]);
+
+    // Reporting alone does not add directory entries to the results.
+    let config = harness.builder().report_empty_dirs(true).build()?;
+    let result = execute(&config, &harness.token, None)?;
+    assert!(result.files.iter().all(|fi| !fi.is_dir));
+    assert_eq!(result.empty_dirs.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_discover_callback_vetoes_files() -> anyhow::Result<()> {
+    let harness = TestHarness::new();
+    harness.file("src/main.rs", b"fn main() {}");
+    harness.file("generated/api.rs", b"// generated");
+    harness.file("big.rs", b"const DATA: [u8; 4] = [0; 4];");
+
+    let seen = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let seen_in_callback = seen.clone();
+    let (config, resolved) = build_and_resolve(
+        harness
+            .builder()
+            .extensions(vec!["rs".to_string()])
+            .discover_callback(move |fi| {
+                seen_in_callback.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                !fi.relative_path.starts_with("generated") && fi.size < 20
+            }),
+    );
+
+    let mut kept: Vec<String> = discover(&config.discovery, &resolved, &harness.token)?
+        .map(|fi| fi.relative_path.to_string_lossy().replace('\\', "/"))
+        .collect();
+    kept.sort();
+    assert_eq!(kept, vec!["src/main.rs"]);
+    assert_eq!(seen.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_post_process_callback_runs_after_filters() -> anyhow::Result<()> {
+    let harness = TestHarness::new();
+    harness.file("a.rs", b"// comment\nfn main() {}");
+    harness.file("b.txt", b"plain");
+
+    let config = harness
+        .builder()
+        .remove_comments(true)
+        .post_process_callback(|fi, content| {
+            format!("[{}] {}", fi.relative_path.display(), content.trim())
+        })
+        .build()?;
+    let result = execute(&config, &harness.token, None)?;
+    let contents: Vec<&str> = result
+        .files
+        .iter()
+        .filter_map(|fi| fi.processed_content.as_deref())
+        .collect();
+    assert_eq!(contents, vec!["[a.rs] fn main() {}", "[b.txt] plain"]);
+
+    Ok(())
+}
+
 #[test]
 fn test_process_iterator_reads_and_filters_content() -> anyhow::Result<()> {
     let harness = TestHarness::new();
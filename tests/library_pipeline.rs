@@ -274,3 +274,33 @@ fn test_format_to_string() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_execute_preserve_input_order_follows_discovery_index() -> anyhow::Result<()> {
+    let temp_dir = tempdir()?;
+    for name in ["c.txt", "a.txt", "z.md", "b.txt"] {
+        fs::write(temp_dir.path().join(name), name)?;
+    }
+    let list = temp_dir.path().join("files.lst");
+    fs::write(&list, "c.txt\nz.md\na.txt\nb.txt\n")?;
+    let token = CancellationToken::new();
+    let names = |preserve: bool| -> anyhow::Result<Vec<String>> {
+        let config = ConfigBuilder::new()
+            .input_path(list.to_str().unwrap())
+            .treat_input_as_list(true)
+            .process_last(vec!["z.md".to_string()])
+            .preserve_input_order(preserve)
+            .build()?;
+        let result = execute(&config, &token, None)?;
+        Ok(result
+            .files
+            .iter()
+            .map(|fi| fi.relative_path.to_string_lossy().into_owned())
+            .collect())
+    };
+
+    // The list order is kept, with the --last file still at the end.
+    assert_eq!(names(true)?, vec!["c.txt", "a.txt", "b.txt", "z.md"]);
+    assert_eq!(names(false)?, vec!["a.txt", "b.txt", "c.txt", "z.md"]);
+    Ok(())
+}
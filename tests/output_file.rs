@@ -4,6 +4,7 @@ mod common;
 
 use assert_cmd::prelude::*;
 use common::dircat_cmd;
+use predicates::prelude::*;
 use std::fs;
 use tempfile::tempdir;
 
@@ -35,3 +36,281 @@ fn test_output_to_file() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_output_template_names_file_from_input() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let src_path = temp.path().join("frontend");
+    fs::create_dir(&src_path)?;
+    fs::write(src_path.join("a.txt"), "Content A")?;
+
+    dircat_cmd()
+        .arg("frontend")
+        .arg("--output-template")
+        .arg("{input_name}-{index}.{ext}")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout("");
+
+    let output_content = fs::read_to_string(temp.path().join("frontend-1.md"))?;
+    assert!(output_content.contains("## File: a.txt"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[cfg(feature = "archive")]
+#[test]
+fn test_save_and_replay_archive() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let src_path = temp.path().join("src");
+    fs::create_dir(&src_path)?;
+    fs::write(src_path.join("a.rs"), "fn a() {}\n")?;
+    fs::write(src_path.join("b.txt"), "Content B\nMore B\n")?;
+    let archive_path = temp.path().join("snapshot.json");
+
+    dircat_cmd()
+        .arg("src")
+        .arg("--save-archive")
+        .arg(archive_path.to_str().unwrap())
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("## File: a.rs"));
+
+    // Replaying works without the original files and honours new formatting options.
+    fs::remove_dir_all(&src_path)?;
+    dircat_cmd()
+        .arg("--from-archive")
+        .arg(archive_path.to_str().unwrap())
+        .arg("-L")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("## File: a.rs"))
+        .stdout(predicates::str::contains("1 | fn a() {}"))
+        .stdout(predicates::str::contains("1 | Content B"));
+
+    // Filters that read the content apply to the replayed files too.
+    dircat_cmd()
+        .arg("--from-archive")
+        .arg(archive_path.to_str().unwrap())
+        .arg("--max-lines")
+        .arg("1")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("## File: a.rs"))
+        .stdout(predicates::str::contains("## File: b.txt").not());
+
+    temp.close()?;
+    Ok(())
+}
+
+#[cfg(all(feature = "archive", unix))]
+#[test]
+fn test_replay_archive_runs_transform_cmd() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "hello\n")?;
+    let archive_path = temp.path().join("snapshot.json");
+
+    dircat_cmd()
+        .arg("a.txt")
+        .arg("--save-archive")
+        .arg(&archive_path)
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    // A replay runs the same content steps as a run from disk.
+    dircat_cmd()
+        .arg("--from-archive")
+        .arg(&archive_path)
+        .args(["--transform-cmd", "tr a-z A-Z"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("HELLO"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[cfg(feature = "archive")]
+#[test]
+fn test_save_archive_keeps_only_processed_files() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let src_path = temp.path().join("src");
+    fs::create_dir(&src_path)?;
+    fs::write(src_path.join("a.rs"), "fn a() {}\n")?;
+    fs::write(src_path.join("image.bin"), [0u8, 159, 146, 150])?;
+    let archive_path = temp.path().join("snapshot.json");
+
+    // A dry run reads no content, so it saves no archive.
+    dircat_cmd()
+        .arg("src")
+        .arg("--dry-run")
+        .arg("--save-archive")
+        .arg(&archive_path)
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    assert!(!archive_path.exists());
+
+    dircat_cmd()
+        .arg("src")
+        .arg("--save-archive")
+        .arg(&archive_path)
+        .current_dir(temp.path())
+        .assert()
+        .success();
+    let archive = fs::read_to_string(&archive_path)?;
+    assert!(archive.contains("\"a.rs\""));
+    assert!(!archive.contains("image.bin"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_roundtrip_output_unpacks_to_same_files() -> Result<(), Box<dyn std::error::Error>> {
+    let src = tempdir()?;
+    fs::create_dir(src.path().join("sub"))?;
+    fs::write(src.path().join("a.txt"), "alpha\n")?;
+    fs::write(src.path().join("sub").join("b.rs"), "fn b() {}")?;
+    let packed = src.path().join("packed.txt");
+
+    dircat_cmd()
+        .arg(src.path())
+        .arg("--roundtrip")
+        .arg("-o")
+        .arg(&packed)
+        .assert()
+        .success();
+
+    let dest = tempdir()?;
+    dircat_cmd()
+        .arg(dest.path())
+        .arg("--unpack")
+        .arg(&packed)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Unpacked 2 files"));
+
+    assert_eq!(fs::read_to_string(dest.path().join("a.txt"))?, "alpha\n");
+    assert_eq!(
+        fs::read_to_string(dest.path().join("sub").join("b.rs"))?,
+        "fn b() {}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_markdown_output_unpacks_to_same_files() -> Result<(), Box<dyn std::error::Error>> {
+    let src = tempdir()?;
+    fs::create_dir(src.path().join("sub"))?;
+    fs::write(src.path().join("a.txt"), "alpha\n")?;
+    fs::write(src.path().join("sub").join("b.md"), "```sh\necho b\n```\n")?;
+    let bundle = src.path().join("bundle.md");
+
+    dircat_cmd()
+        .arg(src.path())
+        .arg("--summary")
+        .arg("--markdown-safe")
+        .arg("-o")
+        .arg(&bundle)
+        .assert()
+        .success();
+
+    let dest = tempdir()?;
+    dircat_cmd()
+        .arg(dest.path())
+        .arg("--unpack")
+        .arg(&bundle)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Unpacked 2 files"));
+
+    assert_eq!(fs::read_to_string(dest.path().join("a.txt"))?, "alpha\n");
+    assert_eq!(
+        fs::read_to_string(dest.path().join("sub").join("b.md"))?,
+        "```sh\necho b\n```\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_verify_detects_stale_output() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let src = temp.path().join("src");
+    fs::create_dir(&src)?;
+    fs::write(src.join("a.txt"), "one\ntwo\n")?;
+    let bundle = temp.path().join("bundle.md");
+
+    dircat_cmd()
+        .arg("src")
+        .arg("-o")
+        .arg(&bundle)
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    // Freshly written output verifies cleanly and is left untouched.
+    dircat_cmd()
+        .arg("src")
+        .arg("--verify")
+        .arg(&bundle)
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout("");
+
+    fs::write(src.join("a.txt"), "one\nTWO\n")?;
+    dircat_cmd()
+        .arg("src")
+        .arg("--verify")
+        .arg(&bundle)
+        .current_dir(temp.path())
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "is out of date: first difference at line 4 (5 lines in the file, 5 lines generated)",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_verify_excludes_bundle_inside_input() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "one\n")?;
+
+    // The second run must not pick up the bundle written by the first.
+    for _ in 0..2 {
+        dircat_cmd()
+            .args([".", "-o", "bundle.md"])
+            .current_dir(temp.path())
+            .assert()
+            .success();
+    }
+    let bundle = fs::read_to_string(temp.path().join("bundle.md"))?;
+    assert!(!bundle.contains("bundle.md"));
+
+    dircat_cmd()
+        .args([".", "--verify", "bundle.md"])
+        .current_dir(temp.path())
+        .assert()
+        .success();
+
+    fs::write(temp.path().join("b.txt"), "two\n")?;
+    dircat_cmd()
+        .args([".", "--verify", "./bundle.md"])
+        .current_dir(temp.path())
+        .assert()
+        .code(1);
+
+    temp.close()?;
+    Ok(())
+}
@@ -45,6 +45,34 @@ fn test_output_multiple_files_separators() -> Result<(), Box<dyn std::error::Err
     Ok(())
 }
 
+#[test]
+fn test_output_block_separator_options() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "Content A")?;
+    fs::write(temp.path().join("b.txt"), "Content B")?;
+
+    dircat_cmd()
+        .arg("--no-block-separator")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "## File: a.txt\n```txt\nContent A\n```\n## File: b.txt\n```txt\nContent B\n```\n",
+        ));
+
+    dircat_cmd()
+        .args(["--block-separator", "---\\n"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::eq(
+            "## File: a.txt\n```txt\nContent A\n```\n---\n## File: b.txt\n```txt\nContent B\n```\n",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
 #[test]
 fn test_output_single_file_with_summary_separator() -> Result<(), Box<dyn std::error::Error>> {
     let temp = tempdir()?;
@@ -83,6 +111,26 @@ fn test_output_multiple_files_with_summary_separators() -> Result<(), Box<dyn st
     Ok(())
 }
 
+#[test]
+fn test_output_no_fence_keeps_headers_and_summary() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("b.txt"), "Content B\n")?;
+    fs::write(temp.path().join("a.txt"), "Content A")?;
+
+    let expected_output = "## File: a.txt\nContent A\n\n## File: b.txt\nContent B\n\n---\nProcessed Files: (2)\n- a.txt\n- b.txt\n";
+
+    dircat_cmd()
+        .arg("--no-fence")
+        .arg("-s")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::eq(expected_output));
+
+    temp.close()?;
+    Ok(())
+}
+
 #[test]
 fn test_output_no_files_is_empty() -> Result<(), Box<dyn std::error::Error>> {
     let temp = tempdir()?;
@@ -100,3 +148,157 @@ fn test_output_no_files_is_empty() -> Result<(), Box<dyn std::error::Error>> {
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_output_custom_delimiter() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("b.txt"), "Content B")?;
+    fs::write(temp.path().join("a.txt"), "Content A\n")?;
+
+    let expected_output = "<<<a.txt>>>\nContent A\n<<<b.txt>>>\nContent B\n";
+
+    dircat_cmd()
+        .arg("--delimiter")
+        .arg("<<<{path}>>>")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::eq(expected_output));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_output_delimiter_requires_placeholder() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "Content A")?;
+
+    dircat_cmd()
+        .arg("--delimiter")
+        .arg("=====")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("{path}"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_fence_lang_overrides_extension_language() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("App.tsx"), "export {};")?;
+    fs::write(temp.path().join("lib.h"), "int x;")?;
+
+    dircat_cmd()
+        .arg("--fence-lang")
+        .arg("tsx=typescriptreact")
+        .arg("--fence-lang")
+        .arg("h=cpp")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("```typescriptreact\nexport {};"))
+        .stdout(predicate::str::contains("```cpp\nint x;"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_front_page_precedes_file_blocks() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "Content A")?;
+
+    let output = dircat_cmd()
+        .arg("--front-page")
+        .arg("--deterministic")
+        .current_dir(temp.path())
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+
+    let version = format!("- Version: dircat {}\n", env!("CARGO_PKG_VERSION"));
+    assert!(stdout.starts_with("# dircat bundle\n\n"));
+    assert!(stdout.contains(&version));
+    assert!(stdout.contains("output.front_page = true\n"));
+    assert!(!stdout.contains("- Date:"));
+    assert!(stdout.ends_with("```\n\n## File: a.txt\n```txt\nContent A\n```\n"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_index_lists_files_without_content() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir(temp.path().join("src"))?;
+    fs::write(temp.path().join("src").join("main.rs"), "fn main() {}")?;
+    fs::write(temp.path().join("README.md"), "# Title")?;
+    fs::write(temp.path().join("LICENSE"), "MIT")?;
+
+    dircat_cmd()
+        .arg("--index")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout("LICENSE (3 bytes)\nREADME.md (7 bytes, md)\nsrc/main.rs (12 bytes, rs)\n");
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_format_json_writes_document() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "say \"hi\"\n")?;
+    fs::write(temp.path().join("b.rs"), "fn b() {}")?;
+
+    dircat_cmd()
+        .args(["--format", "json", "--summary"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(concat!(
+            "{\n  \"files\": [\n",
+            "    {\"relative_path\": \"a.txt\", \"size\": 9, \"is_binary\": false, ",
+            "\"processed_content\": \"say \\\"hi\\\"\\n\"},\n",
+            "    {\"relative_path\": \"b.rs\", \"size\": 9, \"is_binary\": false, ",
+            "\"processed_content\": \"fn b() {}\"}\n",
+            "  ],\n  \"summary\": {\"files\": [\"a.txt\", \"b.rs\"], \"total_files\": 2, ",
+            "\"total_size\": 18}\n}\n"
+        ));
+
+    dircat_cmd()
+        .args(["--format", "json", "--dry-run"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout("[\n  \"a.txt\",\n  \"b.rs\"\n]\n");
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_format_xml_escapes_paths_and_content() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("R&D.md"), "a ]]> b\n")?;
+
+    dircat_cmd()
+        .args(["--format", "xml", "--summary"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<files>\n",
+            "  <file path=\"R&amp;D.md\" size=\"8\"><![CDATA[a ]]]]><![CDATA[> b\n]]></file>\n",
+            "  <summary count=\"1\">\n    <path>R&amp;D.md</path>\n  </summary>\n",
+            "</files>\n"
+        ));
+
+    temp.close()?;
+    Ok(())
+}
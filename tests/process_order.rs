@@ -273,3 +273,116 @@ fn test_last_does_not_override_custom_ignore_i() -> Result<(), Box<dyn std::erro
 
     Ok(())
 }
+
+#[test]
+fn test_last_sort_by_path_ignores_pattern_order() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.md"), "A")?;
+    fs::write(temp.path().join("b.txt"), "B")?;
+    fs::write(temp.path().join("main.rs"), "fn main() {}")?;
+
+    // By default, *.txt files come before *.md files as the pattern is listed first.
+    let output = dircat_cmd()
+        .args(["-z", "*.txt", "*.md"])
+        .current_dir(temp.path())
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.find("## File: b.txt").unwrap() < stdout.find("## File: a.md").unwrap());
+
+    // With --last-sort by-path, they are ordered alphabetically.
+    let output = dircat_cmd()
+        .args(["-z", "*.txt", "*.md", "--last-sort", "by-path"])
+        .current_dir(temp.path())
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let main_pos = stdout.find("## File: main.rs").unwrap();
+    let a_pos = stdout.find("## File: a.md").unwrap();
+    let b_pos = stdout.find("## File: b.txt").unwrap();
+    assert!(main_pos < a_pos && a_pos < b_pos);
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_shard_selects_slice_of_sorted_files() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    for name in ["a.txt", "b.txt", "c.txt", "d.txt", "e.txt"] {
+        fs::write(temp.path().join(name), name)?;
+    }
+
+    let headers = |shard: &str| -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let output = dircat_cmd()
+            .arg("--shard")
+            .arg(shard)
+            .current_dir(temp.path())
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?
+            .lines()
+            .filter_map(|l| l.strip_prefix("## File: ").map(str::to_string))
+            .collect())
+    };
+
+    assert_eq!(headers("1/2")?, ["a.txt", "b.txt"]);
+    assert_eq!(headers("2/2")?, ["c.txt", "d.txt", "e.txt"]);
+
+    dircat_cmd()
+        .arg("--shard")
+        .arg("3/2")
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--shard"));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_budget_tokens_stops_at_first_file_over_budget() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "a".repeat(40))?; // ~10 tokens
+    fs::write(temp.path().join("b.txt"), "b".repeat(40))?; // ~10 tokens
+    fs::write(temp.path().join("c.txt"), "c")?; // Would fit, but selection stops at b.txt
+
+    dircat_cmd()
+        .arg("--budget-tokens")
+        .arg("15")
+        .arg("-s")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("## File: a.txt"))
+        .stdout(predicate::str::contains("## File: b.txt").not())
+        .stdout(predicate::str::contains("## File: c.txt").not())
+        .stdout(predicate::str::ends_with(
+            "Dropped for Token Budget: (2)\n- b.txt\n- c.txt\n",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_budget_tokens_keeps_first_file_over_budget() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "a".repeat(40))?; // ~10 tokens
+    fs::write(temp.path().join("b.txt"), "b")?;
+
+    dircat_cmd()
+        .arg("--budget-tokens")
+        .arg("5")
+        .arg("-s")
+        .arg("--format")
+        .arg("json")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"relative_path\": \"a.txt\""))
+        .stdout(predicate::str::contains("\"over_budget\": [\"b.txt\"]"))
+        .stderr(predicate::str::contains("over the budget of 5"));
+
+    temp.close()?;
+    Ok(())
+}
@@ -86,3 +86,91 @@ fn test_summary_with_counts_and_binary() -> Result<(), Box<dyn std::error::Error
     temp.close()?;
     Ok(())
 }
+
+#[test]
+fn test_stats_report_goes_to_stderr() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "one\ntwo\n")?;
+    fs::write(temp.path().join("b.txt"), "three")?;
+
+    dircat_cmd()
+        .arg("--stats")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dircat stats").not())
+        .stderr(predicate::str::contains("--- dircat stats ---"))
+        .stderr(predicate::str::contains("Files included:   2"))
+        .stderr(predicate::str::contains("Total bytes:      13"))
+        .stderr(predicate::str::contains("Total lines:      3"))
+        .stderr(predicate::str::contains("Estimated tokens: ~4"))
+        .stderr(predicate::str::contains("Elapsed:"))
+        .stderr(predicate::str::contains("Throughput:       Processed "));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_top_lists_largest_files_with_line_counts() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("big.txt"), "line\n".repeat(100))?;
+    fs::write(temp.path().join("mid.txt"), "line\n".repeat(10))?;
+    fs::write(temp.path().join("tiny.txt"), "x\n")?;
+
+    dircat_cmd()
+        .arg("--top")
+        .arg("2")
+        .arg("-C")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with(
+            "Largest Files: (2)\n- big.txt (500 bytes, 100 lines)\n- mid.txt (50 bytes, 10 lines)\n",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_content_hash_report_groups_identical_files() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "same\n")?;
+    fs::write(temp.path().join("b.txt"), "same\n")?;
+    fs::write(temp.path().join("c.txt"), "different\n")?;
+
+    dircat_cmd()
+        .arg("--content-hash-report")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        // The file blocks are still written for every file.
+        .stdout(predicate::str::contains("## File: b.txt\n"))
+        .stdout(predicate::str::ends_with(
+            "Duplicate Files: (1)\n- 2 copies of 5 bytes: a.txt, b.txt\n",
+        ));
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_counts_in_header() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "one two\nthree\n")?;
+
+    dircat_cmd()
+        .arg("--counts-in-header")
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "## File: a.txt (L:2 W:3 C:14)\n```txt\n",
+        ))
+        // Unlike -C, no summary is added.
+        .stdout(predicate::str::contains("Processed Files").not());
+
+    temp.close()?;
+    Ok(())
+}
@@ -0,0 +1,46 @@
+// tests/summary_only_paths.rs
+
+mod common;
+
+use assert_cmd::prelude::*;
+use common::dircat_cmd;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_summary_only_paths_lists_processed_files() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir(temp.path().join("src"))?;
+    fs::write(temp.path().join("src").join("main.rs"), "fn main() {}")?;
+    fs::write(temp.path().join("README.md"), "# Readme")?;
+    fs::write(temp.path().join("image.bin"), b"\x00\x01\x02\x03")?;
+
+    // Binary files are filtered out by processing, so they are not listed,
+    // and counts are not shown even when requested.
+    dircat_cmd()
+        .args(["--summary-only-paths", "-C"])
+        .current_dir(temp.path())
+        .assert()
+        .success()
+        .stdout("- README.md\n- src/main.rs\n");
+
+    temp.close()?;
+    Ok(())
+}
+
+#[test]
+fn test_summary_only_paths_conflicts_with_delimiter() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::write(temp.path().join("a.txt"), "A")?;
+
+    dircat_cmd()
+        .args(["--summary-only-paths", "--delimiter", "== {path} =="])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--summary-only-paths"));
+
+    temp.close()?;
+    Ok(())
+}